@@ -1,4 +1,5 @@
 //! PUS Service 5 Events
+use crate::ecss::PusServiceId;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,12 @@ pub enum Subservice {
     TmDisabledEventsReport = 8,
 }
 
+impl From<Subservice> for (u8, u8) {
+    fn from(subservice: Subservice) -> Self {
+        (PusServiceId::Event.into(), subservice.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
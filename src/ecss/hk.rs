@@ -1,4 +1,5 @@
 //! PUS Service 3 Housekeeping
+use crate::ecss::PusServiceId;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -29,3 +30,9 @@ pub enum Subservice {
     TcGenerateOneShotDiag = 28,
     TcModifyDiagCollectionInterval = 32,
 }
+
+impl From<Subservice> for (u8, u8) {
+    fn from(subservice: Subservice) -> Self {
+        (PusServiceId::Housekeeping.into(), subservice.into())
+    }
+}
@@ -3,10 +3,12 @@
 //!
 //! You can find the PUS telecommand definitions in the [crate::tc] module and ithe PUS telemetry definitions
 //! inside the [crate::tm] module.
-use crate::{ByteConversionError, CcsdsPacket, SizeMissmatch};
+use crate::tc::PUS_TC_MIN_LEN_WITHOUT_APP_DATA;
+use crate::tm::PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA;
+use crate::{ByteConversionError, CcsdsPacket, PacketType, SizeMissmatch, SpHeader, SpHeaderError};
 use core::fmt::{Debug, Display, Formatter};
 use core::mem::size_of;
-use crc::{Crc, CRC_16_IBM_3740};
+use crc::{Algorithm, Crc, CRC_16_IBM_3740};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -16,12 +18,53 @@ use std::error::Error;
 pub mod event;
 pub mod hk;
 pub mod scheduling;
+pub mod test;
 pub mod verification;
 
 pub type CrcType = u16;
 
 /// CRC algorithm used by the PUS standard.
 pub const CRC_CCITT_FALSE: Crc<u16> = Crc::<u16>::new(&CRC_16_IBM_3740);
+
+/// CRC16 algorithm which is used for the packet error control field of a PUS packet.
+///
+/// The PUS standard mandates CRC-16/CCITT-FALSE, which is the default. Missions which use a
+/// different polynomial can specify any CRC16 algorithm of the [crc] crate, for example
+/// `PusCrcKind::Custom(&crc::CRC_16_XMODEM)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PusCrcKind {
+    /// CRC-16/CCITT-FALSE, also known as CRC-16/IBM-3740.
+    CcittFalse,
+    Custom(&'static Algorithm<u16>),
+}
+
+impl Default for PusCrcKind {
+    fn default() -> Self {
+        PusCrcKind::CcittFalse
+    }
+}
+
+impl PusCrcKind {
+    pub fn algorithm(&self) -> &'static Algorithm<u16> {
+        match self {
+            PusCrcKind::CcittFalse => &CRC_16_IBM_3740,
+            PusCrcKind::Custom(algorithm) => algorithm,
+        }
+    }
+
+    /// Returns the CRC calculator for the algorithm. Please note that the lookup table needs to
+    /// be calculated at run-time for custom algorithms.
+    pub fn crc(&self) -> Crc<u16> {
+        match self {
+            PusCrcKind::CcittFalse => CRC_CCITT_FALSE,
+            PusCrcKind::Custom(algorithm) => Crc::<u16>::new(algorithm),
+        }
+    }
+
+    pub fn checksum(&self, bytes: &[u8]) -> u16 {
+        self.crc().checksum(bytes)
+    }
+}
 pub const CCSDS_HEADER_LEN: usize = size_of::<crate::zc::SpHeader>();
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
@@ -75,10 +118,31 @@ pub enum PusServiceId {
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PusVersion {
-    EsaPus = 0,
-    PusA = 1,
-    PusC = 2,
-    Invalid = 0b1111,
+    EsaPus,
+    PusA,
+    PusC,
+    /// Unknown PUS version. Contains the raw 4-bit version field which was found.
+    Invalid(u8),
+}
+
+impl PusVersion {
+    /// Create a PUS version from the raw 4-bit version field. Only the lower four bits of the
+    /// passed value are used. Unknown values are mapped to [PusVersion::Invalid] which preserves
+    /// the raw value.
+    pub fn from_raw(raw: u8) -> Self {
+        let raw = raw & 0b1111;
+        Self::try_from(raw).unwrap_or(PusVersion::Invalid(raw))
+    }
+
+    /// Raw 4-bit version field value.
+    pub const fn raw(&self) -> u8 {
+        match self {
+            PusVersion::EsaPus => 0,
+            PusVersion::PusA => 1,
+            PusVersion::PusC => 2,
+            PusVersion::Invalid(raw) => *raw & 0b1111,
+        }
+    }
 }
 
 impl TryFrom<u8> for PusVersion {
@@ -86,9 +150,9 @@ impl TryFrom<u8> for PusVersion {
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            x if x == PusVersion::EsaPus as u8 => Ok(PusVersion::EsaPus),
-            x if x == PusVersion::PusA as u8 => Ok(PusVersion::PusA),
-            x if x == PusVersion::PusC as u8 => Ok(PusVersion::PusC),
+            0 => Ok(PusVersion::EsaPus),
+            1 => Ok(PusVersion::PusA),
+            2 => Ok(PusVersion::PusC),
             _ => Err(()),
         }
     }
@@ -147,12 +211,25 @@ pub enum RealPfc {
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PusError {
+    /// Contains the PUS version which was found. Unknown versions are represented by
+    /// [PusVersion::Invalid], which contains the raw 4-bit version field.
     VersionNotSupported(PusVersion),
     IncorrectCrc(u16),
     RawDataTooShort(usize),
     NoRawData,
     /// CRC16 needs to be calculated first
     CrcCalculationMissing,
+    /// The length derived from the CCSDS data length field does not match the packet length
+    /// which was provided externally.
+    LengthFieldMismatch(SizeMissmatch),
+    /// The packet length exceeds the maximum allowed packet length.
+    PacketTooLarge {
+        /// Packet length advertised by the CCSDS data length field, or the length of a packet
+        /// which is about to be serialized.
+        advertised: usize,
+        max: usize,
+    },
+    SpHeaderError(SpHeaderError),
     ByteConversionError(ByteConversionError),
 }
 
@@ -160,7 +237,11 @@ impl Display for PusError {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             PusError::VersionNotSupported(v) => {
-                write!(f, "PUS version {v:?} not supported")
+                write!(
+                    f,
+                    "PUS version {v:?} with raw value {:#06b} not supported",
+                    v.raw()
+                )
             }
             PusError::IncorrectCrc(crc) => {
                 write!(f, "crc16 {crc:#04x} is incorrect")
@@ -177,6 +258,19 @@ impl Display for PusError {
             PusError::CrcCalculationMissing => {
                 write!(f, "crc16 was not calculated")
             }
+            PusError::LengthFieldMismatch(missmatch) => {
+                write!(
+                    f,
+                    "packet length {} derived from length field does not match expected length {}",
+                    missmatch.found, missmatch.expected
+                )
+            }
+            PusError::PacketTooLarge { advertised, max } => {
+                write!(f, "packet length {advertised} exceeds maximum length {max}")
+            }
+            PusError::SpHeaderError(e) => {
+                write!(f, "space packet header error: {e}")
+            }
             PusError::ByteConversionError(e) => {
                 write!(f, "low level byte conversion error: {e}")
             }
@@ -187,10 +281,65 @@ impl Display for PusError {
 #[cfg(feature = "std")]
 impl Error for PusError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        if let PusError::ByteConversionError(e) = self {
-            return Some(e);
+        match self {
+            PusError::ByteConversionError(e) => Some(e),
+            PusError::SpHeaderError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Error type for operations which can also fail with an I/O error, for example
+/// [crate::tc::PusTc::write_to_writer].
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub enum StdPusError {
+    IoError(std::io::Error),
+    PusError(PusError),
+}
+
+#[cfg(feature = "std")]
+impl From<PusError> for StdPusError {
+    fn from(v: PusError) -> Self {
+        Self::PusError(v)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for StdPusError {
+    fn from(v: std::io::Error) -> Self {
+        Self::IoError(v)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for StdPusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StdPusError::IoError(e) => {
+                write!(f, "io error: {e}")
+            }
+            StdPusError::PusError(e) => {
+                write!(f, "pus error: {e}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for StdPusError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StdPusError::IoError(e) => Some(e),
+            StdPusError::PusError(e) => Some(e),
         }
-        None
+    }
+}
+
+impl From<SpHeaderError> for PusError {
+    fn from(e: SpHeaderError) -> Self {
+        PusError::SpHeaderError(e)
     }
 }
 
@@ -213,6 +362,39 @@ pub trait PusPacket: CcsdsPacket {
     fn crc16(&self) -> Option<u16>;
 }
 
+/// Object safe variant of [PusPacket], which is implemented for all [PusPacket]s. It can be used
+/// to handle telecommands and telemetry uniformly, for example as `&dyn GenericPusPacket`.
+pub trait GenericPusPacket: CcsdsPacket {
+    fn pus_version(&self) -> PusVersion;
+    fn service(&self) -> u8;
+    fn subservice(&self) -> u8;
+
+    fn user_data(&self) -> Option<&[u8]>;
+    fn crc16(&self) -> Option<u16>;
+}
+
+impl<T: PusPacket> GenericPusPacket for T {
+    fn pus_version(&self) -> PusVersion {
+        PusPacket::pus_version(self)
+    }
+
+    fn service(&self) -> u8 {
+        PusPacket::service(self)
+    }
+
+    fn subservice(&self) -> u8 {
+        PusPacket::subservice(self)
+    }
+
+    fn user_data(&self) -> Option<&[u8]> {
+        PusPacket::user_data(self)
+    }
+
+    fn crc16(&self) -> Option<u16> {
+        PusPacket::crc16(self)
+    }
+}
+
 pub(crate) fn crc_from_raw_data(raw_data: &[u8]) -> Result<u16, PusError> {
     if raw_data.len() < 2 {
         return Err(PusError::RawDataTooShort(raw_data.len()));
@@ -224,13 +406,47 @@ pub(crate) fn crc_from_raw_data(raw_data: &[u8]) -> Result<u16, PusError> {
     ))
 }
 
+#[cfg(test)]
 pub(crate) fn calc_pus_crc16(bytes: &[u8]) -> u16 {
     let mut digest = CRC_CCITT_FALSE.digest();
     digest.update(bytes);
     digest.finalize()
 }
 
+/// Walk through a buffer of concatenated PUS packets using the CCSDS data length field of each
+/// packet and recalculate and rewrite the CRC16 trailer of each packet with the given CRC16
+/// algorithm. Returns the number of updated packets.
+///
+/// This stops at a trailing partial packet and at data which can not be a PUS packet, which
+/// includes zero padding after the last packet. This is data without the secondary header flag
+/// or with a length below [PUS_TC_MIN_LEN_WITHOUT_APP_DATA] for telecommands or below
+/// [PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA] for telemetry.
+pub fn recompute_all_crcs(buf: &mut [u8], crc_kind: PusCrcKind) -> Result<usize, PusError> {
+    let mut current_idx = 0;
+    let mut updated_packets = 0;
+    while buf.len() - current_idx >= CCSDS_HEADER_LEN {
+        let (sp_header, _) = SpHeader::from_be_bytes(&buf[current_idx..])?;
+        let min_len = match sp_header.ptype() {
+            PacketType::Tc => PUS_TC_MIN_LEN_WITHOUT_APP_DATA,
+            PacketType::Tm => PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA,
+        };
+        if !sp_header.sec_header_flag() || sp_header.total_len() < min_len {
+            break;
+        }
+        let packet_end = current_idx + sp_header.total_len();
+        if packet_end > buf.len() {
+            break;
+        }
+        let crc16 = crc_kind.checksum(&buf[current_idx..packet_end - 2]);
+        buf[packet_end - 2..packet_end].copy_from_slice(&crc16.to_be_bytes());
+        current_idx = packet_end;
+        updated_packets += 1;
+    }
+    Ok(updated_packets)
+}
+
 pub(crate) fn crc_procedure(
+    crc_kind: PusCrcKind,
     calc_on_serialization: bool,
     cached_crc16: &Option<u16>,
     start_idx: usize,
@@ -239,7 +455,7 @@ pub(crate) fn crc_procedure(
 ) -> Result<u16, PusError> {
     let crc16;
     if calc_on_serialization {
-        crc16 = calc_pus_crc16(&slice[start_idx..curr_idx])
+        crc16 = crc_kind.checksum(&slice[start_idx..curr_idx])
     } else if cached_crc16.is_none() {
         return Err(PusError::CrcCalculationMissing);
     } else {
@@ -399,8 +615,130 @@ pub type EcssEnumU64 = GenericEcssEnumWrapper<u64>;
 
 #[cfg(test)]
 mod tests {
-    use crate::ecss::{EcssEnumU16, EcssEnumU32, EcssEnumU8, EcssEnumeration};
-    use crate::ByteConversionError;
+    use crate::ecss::{
+        recompute_all_crcs, EcssEnumU16, EcssEnumU32, EcssEnumU8, EcssEnumeration,
+        GenericPusPacket, PusCrcKind, PusError, PusVersion,
+    };
+    use crate::tc::{PusTc, PusTcSecondaryHeader};
+    use crate::tm::{PusTm, PusTmSecondaryHeader};
+    use crate::{ByteConversionError, CcsdsPacket, SpHeader};
+
+    #[test]
+    fn test_pus_version_raw_conversion() {
+        assert_eq!(PusVersion::from_raw(2), PusVersion::PusC);
+        assert_eq!(PusVersion::PusC.raw(), 2);
+        let invalid = PusVersion::from_raw(0b0111);
+        assert_eq!(invalid, PusVersion::Invalid(0b0111));
+        assert_eq!(invalid.raw(), 0b0111);
+        assert!(PusVersion::try_from(0b0111).is_err());
+        // Only the lower four bits are used.
+        assert_eq!(PusVersion::from_raw(0x12), PusVersion::PusC);
+        assert_eq!(PusVersion::from_raw(0xf7), invalid);
+    }
+
+    #[test]
+    fn test_generic_pus_packet() {
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let tc = PusTc::new(
+            &mut sph,
+            PusTcSecondaryHeader::new_simple(17, 1),
+            Some(&[1, 2]),
+            true,
+        );
+        let mut sph = SpHeader::tm_unseg(0x03, 0x35, 0).unwrap();
+        let stamp = [0; 7];
+        let tm = PusTm::new(
+            &mut sph,
+            PusTmSecondaryHeader::new_simple(17, 2, &stamp),
+            None,
+            true,
+        );
+        let packets: [&dyn GenericPusPacket; 2] = [&tc, &tm];
+        let summary: std::vec::Vec<_> = packets
+            .iter()
+            .map(|packet| {
+                (
+                    packet.apid(),
+                    packet.service(),
+                    packet.subservice(),
+                    packet.user_data().map(|data| data.len()),
+                    packet.pus_version(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (0x02, 17, 1, Some(2), PusVersion::PusC),
+                (0x03, 17, 2, None, PusVersion::PusC)
+            ]
+        );
+        assert_eq!(packets[0].crc16(), None);
+    }
+
+    #[test]
+    fn test_version_not_supported_display() {
+        let error = PusError::VersionNotSupported(PusVersion::Invalid(0b0111));
+        assert_eq!(
+            std::format!("{error}"),
+            "PUS version Invalid(7) with raw value 0b0111 not supported"
+        );
+    }
+
+    #[test]
+    fn test_recompute_all_crcs() {
+        let mut buf: [u8; 64] = [0; 64];
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let mut current_idx = 0;
+        for app_data in [[1, 2, 3].as_slice(), &[], &[4, 5]] {
+            let pus_tc = PusTc::new_simple(&mut sph, 17, 1, Some(app_data), true);
+            current_idx += pus_tc.write_to_bytes(&mut buf[current_idx..]).unwrap();
+        }
+        assert_eq!(current_idx, 16 + 13 + 15);
+        // Renumber all packets and corrupt the CRC of the first packet.
+        for offset in [0, 16, 29] {
+            buf[offset + 3] = 0x42;
+        }
+        buf[15] = 0;
+        // Trailing partial packet
+        buf.copy_within(0..6, current_idx);
+        assert_eq!(
+            recompute_all_crcs(&mut buf[0..current_idx + 10], PusCrcKind::CcittFalse).unwrap(),
+            3
+        );
+        let mut current_idx = 0;
+        for _ in 0..3 {
+            let (pus_tc, size) = PusTc::from_bytes(&buf[current_idx..]).unwrap();
+            assert_eq!(pus_tc.seq_count(), 0x42);
+            current_idx += size;
+        }
+    }
+
+    #[test]
+    fn test_recompute_all_crcs_stops_at_invalid_packets() {
+        let mut buf: [u8; 64] = [0; 64];
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let pus_tc = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 3]), true);
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        buf[size - 1] ^= 0xff;
+        // Trailing zero padding, which parses as a packet with a data length of 0.
+        assert_eq!(
+            recompute_all_crcs(&mut buf, PusCrcKind::CcittFalse).unwrap(),
+            1
+        );
+        assert!(PusTc::from_bytes(&buf).is_ok());
+        assert!(buf[size..].iter().all(|byte| *byte == 0));
+
+        // Packet without the secondary header flag.
+        let sph = SpHeader::tc_unseg(0x02, 0x35, 8).unwrap();
+        sph.write_to_be_bytes(&mut buf[size..]).unwrap();
+        let unchanged = buf;
+        assert_eq!(
+            recompute_all_crcs(&mut buf, PusCrcKind::CcittFalse).unwrap(),
+            1
+        );
+        assert_eq!(buf, unchanged);
+    }
 
     #[test]
     fn test_enum_u8() {
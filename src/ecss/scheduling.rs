@@ -1,4 +1,5 @@
 //! PUS Service 11 Scheduling
+use crate::ecss::PusServiceId;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -45,6 +46,12 @@ pub enum Subservice {
     TmReportAllGroupsStatus = 27,
 }
 
+impl From<Subservice> for (u8, u8) {
+    fn from(subservice: Subservice) -> Self {
+        (PusServiceId::Scheduling.into(), subservice.into())
+    }
+}
+
 /// This status applies to sub-schedules and groups as well as specified in ECSS-E-ST-70-41C 8.11.3
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -0,0 +1,36 @@
+//! PUS Service 17 Test
+use crate::ecss::PusServiceId;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+pub enum Subservice {
+    TcPing = 1,
+    TmPingReply = 2,
+}
+
+impl From<Subservice> for (u8, u8) {
+    fn from(subservice: Subservice) -> Self {
+        (PusServiceId::Test.into(), subservice.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conv_into_u8() {
+        let subservice: u8 = Subservice::TmPingReply.into();
+        assert_eq!(subservice, 2);
+    }
+
+    #[test]
+    fn test_conv_into_service_subservice_pair() {
+        let pair: (u8, u8) = Subservice::TcPing.into();
+        assert_eq!(pair, (17, 1));
+    }
+}
@@ -1,4 +1,5 @@
 //! PUS Service 1 Verification
+use crate::ecss::PusServiceId;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,12 @@ pub enum Subservice {
     TmCompletionFailure = 8,
 }
 
+impl From<Subservice> for (u8, u8) {
+    fn from(subservice: Subservice) -> Self {
+        (PusServiceId::Verification.into(), subservice.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
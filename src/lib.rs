@@ -30,11 +30,15 @@
 //!  - [`alloc`](https://doc.rust-lang.org/alloc/): Enables features which operate on containers
 //!     like [`alloc::vec::Vec`](https://doc.rust-lang.org/beta/alloc/vec/struct.Vec.html).
 //!     Enabled by the `std` feature.
+//!  - [`chrono`](https://docs.rs/chrono/latest/chrono/): Enables the `DateTime<Utc>` conversions
+//!     of the time providers, the ISO-8601 formatting and the `time::ascii` module.
 //!
 //! ### Optional features
 //!
 //!  - [`serde`](https://serde.rs/): Adds `serde` support for most types by adding `Serialize` and
 //!    `Deserialize` `derive`s
+//!  - [`time`](https://docs.rs/time/latest/time/): Adds conversions between the time providers
+//!    and the `time::OffsetDateTime` type of the `time` crate as an alternative to `chrono`.
 //!
 //! ## Module
 //!
@@ -69,10 +73,13 @@ use std::error::Error;
 use serde::{Deserialize, Serialize};
 
 pub mod ecss;
+pub mod seq_count;
 pub mod tc;
 pub mod time;
 pub mod tm;
 
+pub use time::CcsdsTimeProvider;
+
 mod private {
     pub trait Sealed {}
 }
@@ -442,6 +449,28 @@ pub enum SpHeaderError {
     SeqCountTooLarge(u16),
 }
 
+impl Display for SpHeaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SpHeaderError::ApidTooLarge(apid) => {
+                write!(
+                    f,
+                    "APID {apid:#06x} exceeds the maximum value {MAX_APID:#06x}"
+                )
+            }
+            SpHeaderError::SeqCountTooLarge(seq_count) => {
+                write!(
+                    f,
+                    "sequence count {seq_count} exceeds the maximum value {MAX_SEQ_COUNT}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SpHeaderError {}
+
 impl Default for SpHeader {
     /// The default function sets the sequence flag field to [SequenceFlags::Unsegmented]. The data
     /// length field is set to 1, which denotes an empty space packets.
@@ -533,6 +562,18 @@ impl SpHeader {
         Self::tc(apid, SequenceFlags::Unsegmented, seq_count, data_len)
     }
 
+    /// Create the space packet header for a telemetry reply to a received telecommand.
+    ///
+    /// The reply uses the APID of the telecommand, so that the reply is routed back to the same
+    /// application process. If replies should be sent with a different APID, it can be changed
+    /// with [SpHeader::set_apid] afterwards. The sequence flags are set to
+    /// [SequenceFlags::Unsegmented] and the data length field is set to 0, which is updated by
+    /// the telemetry packet constructors. Returns [None] if the sequence count exceeds
+    /// [MAX_SEQ_COUNT].
+    pub fn tm_reply_for(tc: &(impl CcsdsPacket + ?Sized), seq_count: u16) -> Option<Self> {
+        Self::tm_unseg(tc.apid(), seq_count, 0)
+    }
+
     //noinspection RsTraitImplementation
     delegate!(to self.packet_id {
         /// Returns [false] and fails if the APID exceeds [MAX_APID]
@@ -595,6 +636,32 @@ impl SpHeader {
     }
 }
 
+/// Plausibility check whether the passed slice starts with a complete space packet. This checks
+/// that the packet version number is 0 as specified by CCSDS 133.0-B-2 and that the packet length
+/// advertised by the data length field does not exceed the slice length. This can be used to
+/// resynchronize to a packet stream after corrupted data.
+pub fn looks_like_ccsds_packet(slice: &[u8]) -> bool {
+    plausible_sp_header(slice).is_some()
+}
+
+/// Like [looks_like_ccsds_packet], but additionally requires the APID of the packet to be
+/// contained in the `allowed_apids` list. This makes the check a lot more reliable for
+/// environments with a known set of APIDs, because random data rarely matches a small allowlist.
+pub fn looks_like_ccsds_packet_for(slice: &[u8], allowed_apids: &[u16]) -> bool {
+    match plausible_sp_header(slice) {
+        Some(sp_header) => allowed_apids.contains(&sp_header.apid()),
+        None => false,
+    }
+}
+
+fn plausible_sp_header(slice: &[u8]) -> Option<SpHeader> {
+    let (sp_header, _) = SpHeader::from_be_bytes(slice).ok()?;
+    if sp_header.ccsds_version() != 0 || sp_header.total_len() > slice.len() {
+        return None;
+    }
+    Some(sp_header)
+}
+
 impl CcsdsPacket for SpHeader {
     #[inline]
     fn ccsds_version(&self) -> u8 {
@@ -725,7 +792,8 @@ mod tests {
     #[cfg(feature = "serde")]
     use crate::CcsdsPrimaryHeader;
     use crate::{
-        packet_type_in_raw_packet_id, zc, CcsdsPacket, PacketId, PacketSequenceCtrl, PacketType,
+        looks_like_ccsds_packet, looks_like_ccsds_packet_for, packet_type_in_raw_packet_id, zc,
+        CcsdsPacket, PacketId, PacketSequenceCtrl, PacketType, MAX_SEQ_COUNT,
     };
     use crate::{SequenceFlags, SpHeader};
     use alloc::vec;
@@ -741,6 +809,32 @@ mod tests {
 
     const PACKET_ID_TM: PacketId = PacketId::const_tm(true, 0x22);
 
+    #[test]
+    fn test_looks_like_ccsds_packet() {
+        let sp_header = SpHeader::tc_unseg(0x42, 12, 1).unwrap();
+        let mut buf: [u8; 16] = [0; 16];
+        sp_header.write_to_be_bytes(&mut buf).unwrap();
+        assert!(looks_like_ccsds_packet(&buf));
+        assert!(looks_like_ccsds_packet(&buf[0..8]));
+        // Incomplete packet
+        assert!(!looks_like_ccsds_packet(&buf[0..7]));
+        assert!(!looks_like_ccsds_packet(&buf[0..4]));
+        // Invalid packet version number
+        buf[0] |= 0b0010_0000;
+        assert!(!looks_like_ccsds_packet(&buf));
+    }
+
+    #[test]
+    fn test_looks_like_ccsds_packet_for_apids() {
+        let sp_header = SpHeader::tc_unseg(0x42, 12, 1).unwrap();
+        let mut buf: [u8; 16] = [0; 16];
+        sp_header.write_to_be_bytes(&mut buf).unwrap();
+        assert!(looks_like_ccsds_packet_for(&buf, &[0x02, 0x42]));
+        assert!(!looks_like_ccsds_packet_for(&buf, &[0x02, 0x43]));
+        assert!(!looks_like_ccsds_packet_for(&buf, &[]));
+        assert!(!looks_like_ccsds_packet_for(&buf[0..4], &[0x42]));
+    }
+
     #[test]
     fn verify_const_packet_id() {
         assert_eq!(PACKET_ID_TM.apid(), 0x22);
@@ -975,6 +1069,16 @@ mod tests {
         verify_sp_fields(PacketType::Tm, &sp_header);
     }
 
+    #[test]
+    fn test_tm_reply_for_tc() {
+        let tc_header = SpHeader::tc_unseg(0x42, 12, 0).unwrap();
+        let sp_header = SpHeader::tm_reply_for(&tc_header, 25);
+        assert!(sp_header.is_some());
+        let sp_header = sp_header.unwrap();
+        verify_sp_fields(PacketType::Tm, &sp_header);
+        assert!(SpHeader::tm_reply_for(&tc_header, MAX_SEQ_COUNT + 1).is_none());
+    }
+
     fn verify_sp_fields(ptype: PacketType, sp_header: &SpHeader) {
         assert_eq!(sp_header.ptype(), ptype);
         assert_eq!(sp_header.sequence_flags(), SequenceFlags::Unsegmented);
@@ -0,0 +1,95 @@
+//! Helpers to monitor the CCSDS packet sequence count of space packet streams.
+#[cfg(feature = "alloc")]
+use crate::CcsdsPacket;
+#[cfg(feature = "alloc")]
+use alloc::collections::{BTreeMap, VecDeque};
+
+/// Detects duplicate packet sequence counts inside a packet stream, separately for each APID.
+///
+/// The last `window_size` distinct sequence counts of each APID are kept in a sliding window,
+/// and a packet is considered a duplicate if its sequence count is still contained in that window.
+/// Unlike a gap detection, this catches retransmissions and loopbacks. Because only set membership
+/// is checked, the wraparound of the 14-bit sequence count does not require special handling.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateDetector {
+    window_size: usize,
+    windows: BTreeMap<u16, VecDeque<u16>>,
+}
+
+#[cfg(feature = "alloc")]
+impl DuplicateDetector {
+    pub fn new(window_size: usize) -> Self {
+        Self {
+            window_size,
+            windows: BTreeMap::new(),
+        }
+    }
+
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Check whether the sequence count of the passed packet was already seen for its APID.
+    /// Returns true for a duplicate. Otherwise, the sequence count is added to the sliding
+    /// window of the APID and false is returned.
+    pub fn check_duplicate(&mut self, packet: &(impl CcsdsPacket + ?Sized)) -> bool {
+        if self.window_size == 0 {
+            return false;
+        }
+        let window = self.windows.entry(packet.apid()).or_default();
+        let seq_count = packet.seq_count();
+        if window.contains(&seq_count) {
+            return true;
+        }
+        if window.len() == self.window_size {
+            window.pop_front();
+        }
+        window.push_back(seq_count);
+        false
+    }
+
+    /// Clear the sliding windows of all APIDs.
+    pub fn reset(&mut self) {
+        self.windows.clear();
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{SpHeader, MAX_SEQ_COUNT};
+
+    #[test]
+    fn test_basic_duplicate_detection() {
+        let mut detector = DuplicateDetector::new(4);
+        assert_eq!(detector.window_size(), 4);
+        for seq_count in 0..4 {
+            let sph = SpHeader::tm_unseg(0x02, seq_count, 0).unwrap();
+            assert!(!detector.check_duplicate(&sph));
+        }
+        let sph = SpHeader::tm_unseg(0x02, 2, 0).unwrap();
+        assert!(detector.check_duplicate(&sph));
+        // Same sequence count for a different APID is not a duplicate.
+        let sph = SpHeader::tm_unseg(0x03, 2, 0).unwrap();
+        assert!(!detector.check_duplicate(&sph));
+        detector.reset();
+        let sph = SpHeader::tm_unseg(0x02, 2, 0).unwrap();
+        assert!(!detector.check_duplicate(&sph));
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let mut detector = DuplicateDetector::new(2);
+        for seq_count in [MAX_SEQ_COUNT - 1, MAX_SEQ_COUNT, 0] {
+            let sph = SpHeader::tm_unseg(0x02, seq_count, 0).unwrap();
+            assert!(!detector.check_duplicate(&sph));
+        }
+        // Dropped out of the window
+        let sph = SpHeader::tm_unseg(0x02, MAX_SEQ_COUNT - 1, 0).unwrap();
+        assert!(!detector.check_duplicate(&sph));
+        let sph = SpHeader::tm_unseg(0x02, 0, 0).unwrap();
+        assert!(detector.check_duplicate(&sph));
+    }
+}
@@ -22,7 +22,7 @@
 //! let size = pus_tc
 //!     .write_to_bytes(test_buf.as_mut_slice())
 //!     .expect("Error writing TC to buffer");
-//! assert_eq!(size, 13);
+//! assert_eq!(size, PusTc::min_len());
 //! println!("{:?}", &test_buf[0..size]);
 //!
 //! // Deserialize from the raw byte representation
@@ -32,55 +32,248 @@
 //! assert_eq!(pus_tc.apid(), 0x02);
 //! ```
 use crate::ecss::{
-    ccsds_impl, crc_from_raw_data, crc_procedure, sp_header_impls, user_data_from_raw,
-    verify_crc16_from_raw, CrcType, PusError, PusPacket, PusVersion, CRC_CCITT_FALSE,
+    ccsds_impl, crc_from_raw_data, crc_procedure, user_data_from_raw, verify_crc16_from_raw,
+    CrcType, PusCrcKind, PusError, PusPacket, PusVersion,
 };
-use crate::SpHeader;
 use crate::{
-    ByteConversionError, CcsdsPacket, PacketType, SequenceFlags, SizeMissmatch, CCSDS_HEADER_LEN,
+    ByteConversionError, CcsdsPacket, PacketId, PacketSequenceCtrl, PacketType, SequenceFlags,
+    SizeMissmatch, CCSDS_HEADER_LEN, MAX_APID, MAX_SEQ_COUNT,
 };
+use crate::{SpHeader, SpHeaderError};
+use core::fmt::{Display, Formatter};
+use core::hash::{Hash, Hasher};
 use core::mem::size_of;
+use core::ops::{BitAnd, BitOr, BitOrAssign, Range};
 use delegate::delegate;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use zerocopy::AsBytes;
 
+#[cfg(feature = "std")]
+use crate::ecss::StdPusError;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::vec;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
-/// PUS C secondary header length is fixed
+/// PUS C secondary header length with the default two byte source ID field. See
+/// [SourceIdWidth] for missions which use a different source ID width.
 pub const PUC_TC_SECONDARY_HEADER_LEN: usize = size_of::<zc::PusTcSecondaryHeader>();
+/// Length of a telecommand with the default secondary header layout and without application
+/// data or spare bytes, which is the minimum length of a PUS C telecommand with the default
+/// layout. It can be used to size static buffers, see also [PusTc::min_len].
 pub const PUS_TC_MIN_LEN_WITHOUT_APP_DATA: usize =
     CCSDS_HEADER_LEN + PUC_TC_SECONDARY_HEADER_LEN + size_of::<CrcType>();
-const PUS_VERSION: PusVersion = PusVersion::PusC;
+/// Length of the optional destination ID field of the PUS TC secondary header.
+pub const PUS_TC_DESTINATION_ID_LEN: usize = size_of::<u16>();
+const PUS_TC_SECONDARY_HEADER_MAX_LEN: usize =
+    PUC_TC_SECONDARY_HEADER_LEN + PUS_TC_DESTINATION_ID_LEN;
+/// Maximum packet length which can be expressed with the CCSDS data length field.
+pub const PUS_TC_MAX_LEN: usize = u16::MAX as usize + CCSDS_HEADER_LEN + 1;
+
+/// Acknowledgement flags of the PUS TC secondary header. Only the four lowest bits of the raw
+/// value are used, which is guaranteed by all constructors of this type.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AckFlags(u8);
+
+impl AckFlags {
+    pub const ACCEPTANCE: Self = Self(0b1000);
+    pub const START: Self = Self(0b0100);
+    pub const PROGRESS: Self = Self(0b0010);
+    pub const COMPLETION: Self = Self(0b0001);
+    pub const ALL: Self = Self(0b1111);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns [None] if any bit outside of the four acknowledgement flags is set.
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        if bits > Self::ALL.0 {
+            return None;
+        }
+        Some(Self(bits))
+    }
+
+    /// Ignores all bits outside of the four acknowledgement flags.
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for AckFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AckFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for AckFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl From<AckFlags> for u8 {
+    fn from(value: AckFlags) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<u8> for AckFlags {
+    /// The raw value which was rejected.
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_bits(value).ok_or(value)
+    }
+}
+
+pub const ACK_ALL: AckFlags = AckFlags::ALL;
+
+/// Width of the source ID field of the PUS TC secondary header, which is mission specific. The
+/// default is [SourceIdWidth::TwoBytes].
+///
+/// Other widths are supported by [PusTcSecondaryHeader] and [PusTc]. [PusTcHeaderView],
+/// [PusTcReader] and [PusTc::parse_offsets] always assume the default width.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SourceIdWidth {
+    /// The source ID field is omitted and the source ID is always 0.
+    None,
+    OneByte,
+    TwoBytes,
+}
+
+impl Default for SourceIdWidth {
+    fn default() -> Self {
+        SourceIdWidth::TwoBytes
+    }
+}
+
+impl SourceIdWidth {
+    pub const fn num_bytes(&self) -> usize {
+        match self {
+            SourceIdWidth::None => 0,
+            SourceIdWidth::OneByte => 1,
+            SourceIdWidth::TwoBytes => 2,
+        }
+    }
+
+    /// Length of the PUS TC secondary header with this source ID width.
+    pub const fn sec_header_len(&self) -> usize {
+        PUC_TC_SECONDARY_HEADER_LEN - SourceIdWidth::TwoBytes.num_bytes() + self.num_bytes()
+    }
+
+    const fn mask(&self) -> u16 {
+        match self {
+            SourceIdWidth::None => 0,
+            SourceIdWidth::OneByte => 0xff,
+            SourceIdWidth::TwoBytes => 0xffff,
+        }
+    }
+}
+
+/// Layout of the mission specific fields of the PUS TC secondary header, which is required to
+/// parse a raw secondary header. The default layout is the 5 byte secondary header with a two
+/// byte source ID field and without a destination ID field.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PusTcSecondaryHeaderLayout {
+    pub source_id_width: SourceIdWidth,
+    /// A two byte destination ID field follows the source ID field.
+    pub destination_id: bool,
+}
+
+impl PusTcSecondaryHeaderLayout {
+    /// Length of the PUS TC secondary header with this layout.
+    pub const fn sec_header_len(&self) -> usize {
+        let len = self.source_id_width.sec_header_len();
+        if self.destination_id {
+            return len + PUS_TC_DESTINATION_ID_LEN;
+        }
+        len
+    }
+}
+
+impl From<SourceIdWidth> for PusTcSecondaryHeaderLayout {
+    fn from(source_id_width: SourceIdWidth) -> Self {
+        Self {
+            source_id_width,
+            destination_id: false,
+        }
+    }
+}
 
-#[derive(Copy, Clone, PartialEq, Debug)]
-enum AckOpts {
-    Acceptance = 0b1000,
-    Start = 0b0100,
-    Progress = 0b0010,
-    Completion = 0b0001,
+/// Mission specific options to parse a raw telecommand with [PusTc::from_bytes_with_config].
+/// The default configuration parses a telecommand like [PusTc::from_bytes].
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct PusTcParseConfig {
+    pub layout: PusTcSecondaryHeaderLayout,
+    /// Used to verify the CRC16 and when the parsed packet is serialized again.
+    pub crc_kind: PusCrcKind,
+    /// Number of bytes before the CRC16 which are extracted as spare bytes instead of being part
+    /// of the application data.
+    pub num_spare_bytes: usize,
+    /// Packets with a total length advertised by the CCSDS data length field which exceeds this
+    /// length are rejected with [PusError::PacketTooLarge] before any slicing of the packet data.
+    pub max_len: Option<usize>,
 }
 
-pub const ACK_ALL: u8 = AckOpts::Acceptance as u8
-    | AckOpts::Start as u8
-    | AckOpts::Progress as u8
-    | AckOpts::Completion as u8;
+/// Check whether the raw PUS telecommand in the passed slice contains application data. Only
+/// the CCSDS data length field of the space packet header is read for this, the rest of the
+/// packet is not parsed or validated.
+pub fn pus_tc_has_app_data(slice: &[u8]) -> Result<bool, PusError> {
+    let (sp_header, _) = SpHeader::from_be_bytes(slice)?;
+    Ok(sp_header.total_len() > PUS_TC_MIN_LEN_WITHOUT_APP_DATA)
+}
 
 pub trait GenericPusTcSecondaryHeader {
     fn pus_version(&self) -> PusVersion;
-    fn ack_flags(&self) -> u8;
+    fn ack_flags(&self) -> AckFlags;
     fn service(&self) -> u8;
     fn subservice(&self) -> u8;
     fn source_id(&self) -> u16;
+    /// Destination ID used to route the telecommand, if the secondary header contains the
+    /// optional destination ID field.
+    fn destination_id(&self) -> Option<u16> {
+        None
+    }
 }
 
 pub mod zc {
     use crate::ecss::{PusError, PusVersion};
-    use crate::tc::GenericPusTcSecondaryHeader;
+    use crate::tc::{AckFlags, GenericPusTcSecondaryHeader};
     use zerocopy::{AsBytes, FromBytes, NetworkEndian, Unaligned, U16};
 
-    #[derive(FromBytes, AsBytes, Unaligned)]
+    /// Zero-copy representation of the default 5 byte PUS C telecommand secondary header with a
+    /// two byte source ID field. The [AsBytes] and [FromBytes] implementations can be used to
+    /// reuse the secondary header for custom packet layouts.
+    #[derive(FromBytes, AsBytes, Unaligned, Copy, Clone, PartialEq, Eq, Debug)]
     #[repr(C)]
     pub struct PusTcSecondaryHeader {
         version_ack: u8,
@@ -96,21 +289,21 @@ pub mod zc {
                 return Err(PusError::VersionNotSupported(value.version));
             }
             Ok(PusTcSecondaryHeader {
-                version_ack: ((value.version as u8) << 4) | value.ack,
+                version_ack: (value.version.raw() << 4) | value.ack.bits(),
                 service: value.service,
                 subservice: value.subservice,
-                source_id: U16::from(value.source_id),
+                source_id: U16::from(value.source_id()),
             })
         }
     }
 
     impl GenericPusTcSecondaryHeader for PusTcSecondaryHeader {
         fn pus_version(&self) -> PusVersion {
-            PusVersion::try_from(self.version_ack >> 4 & 0b1111).unwrap_or(PusVersion::Invalid)
+            PusVersion::from_raw(self.version_ack >> 4 & 0b1111)
         }
 
-        fn ack_flags(&self) -> u8 {
-            self.version_ack & 0b1111
+        fn ack_flags(&self) -> AckFlags {
+            AckFlags::from_bits_truncate(self.version_ack)
         }
 
         fn service(&self) -> u8 {
@@ -127,6 +320,17 @@ pub mod zc {
     }
 
     impl PusTcSecondaryHeader {
+        /// Create a PUS C secondary header. Only the four lowest bits of the acknowledgement
+        /// flags are used.
+        pub fn new(service: u8, subservice: u8, ack: AckFlags, source_id: u16) -> Self {
+            PusTcSecondaryHeader {
+                version_ack: (PusVersion::PusC.raw() << 4) | (ack.bits() & AckFlags::ALL.bits()),
+                service,
+                subservice,
+                source_id: U16::from(source_id),
+            }
+        }
+
         pub fn write_to_bytes(&self, slice: &mut [u8]) -> Option<()> {
             self.write_to(slice)
         }
@@ -137,14 +341,24 @@ pub mod zc {
     }
 }
 
-#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+/// High-level PUS TC secondary header.
+///
+/// The [PartialEq] implementation compares the source ID truncated to the
+/// [Self::source_id_width], like it is serialized.
+#[derive(Eq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PusTcSecondaryHeader {
     pub service: u8,
     pub subservice: u8,
+    /// Only the bits which fit into the [Self::source_id_width] are used.
+    /// [GenericPusTcSecondaryHeader::source_id] returns the truncated source ID.
     pub source_id: u16,
-    pub ack: u8,
+    pub ack: AckFlags,
     pub version: PusVersion,
+    /// Only the bytes of the source ID which fit into this width are serialized.
+    pub source_id_width: SourceIdWidth,
+    /// The destination ID field is only serialized if this is set.
+    pub destination_id: Option<u16>,
 }
 
 impl GenericPusTcSecondaryHeader for PusTcSecondaryHeader {
@@ -152,7 +366,7 @@ impl GenericPusTcSecondaryHeader for PusTcSecondaryHeader {
         self.version
     }
 
-    fn ack_flags(&self) -> u8 {
+    fn ack_flags(&self) -> AckFlags {
         self.ack
     }
 
@@ -164,8 +378,26 @@ impl GenericPusTcSecondaryHeader for PusTcSecondaryHeader {
         self.subservice
     }
 
+    /// Returns the source ID truncated to the configured [SourceIdWidth], which is always 0 if
+    /// the source ID field is omitted.
     fn source_id(&self) -> u16 {
-        self.source_id
+        self.source_id & self.source_id_width.mask()
+    }
+
+    fn destination_id(&self) -> Option<u16> {
+        self.destination_id
+    }
+}
+
+impl PartialEq for PusTcSecondaryHeader {
+    fn eq(&self, other: &Self) -> bool {
+        self.service == other.service
+            && self.subservice == other.subservice
+            && self.source_id() == other.source_id()
+            && self.ack == other.ack
+            && self.version == other.version
+            && self.source_id_width == other.source_id_width
+            && self.destination_id == other.destination_id
     }
 }
 
@@ -178,7 +410,9 @@ impl TryFrom<zc::PusTcSecondaryHeader> for PusTcSecondaryHeader {
             subservice: value.subservice(),
             source_id: value.source_id(),
             ack: value.ack_flags(),
-            version: PUS_VERSION,
+            version: value.pus_version(),
+            source_id_width: SourceIdWidth::TwoBytes,
+            destination_id: None,
         })
     }
 }
@@ -191,20 +425,177 @@ impl PusTcSecondaryHeader {
             ack: ACK_ALL,
             source_id: 0,
             version: PusVersion::PusC,
+            source_id_width: SourceIdWidth::default(),
+            destination_id: None,
         }
     }
 
-    pub fn new(service: u8, subservice: u8, ack: u8, source_id: u16) -> Self {
+    pub fn new(service: u8, subservice: u8, ack: AckFlags, source_id: u16) -> Self {
         PusTcSecondaryHeader {
             service,
             subservice,
-            ack: ack & 0b1111,
+            ack,
             source_id,
             version: PusVersion::PusC,
+            source_id_width: SourceIdWidth::default(),
+            destination_id: None,
+        }
+    }
+
+    /// Create a secondary header from a typed service and subservice pair, for example the
+    /// subservice enumerations like [crate::ecss::test::Subservice] which convert into the
+    /// matching service and subservice numbers. A raw `(service, subservice)` tuple can be used
+    /// for custom services.
+    pub fn new_for<S: Into<(u8, u8)>>(kind: S, ack: AckFlags, source_id: u16) -> Self {
+        let (service, subservice) = kind.into();
+        Self::new(service, subservice, ack, source_id)
+    }
+
+    /// Layout of the raw secondary header, which is required to parse it again.
+    pub const fn layout(&self) -> PusTcSecondaryHeaderLayout {
+        PusTcSecondaryHeaderLayout {
+            source_id_width: self.source_id_width,
+            destination_id: self.destination_id.is_some(),
+        }
+    }
+
+    /// Length of the raw secondary header, which depends on the [SourceIdWidth] and on whether
+    /// a destination ID is present.
+    pub const fn len_packed(&self) -> usize {
+        self.layout().sec_header_len()
+    }
+
+    /// Write the raw secondary header to the passed buffer. Returns the written length, which is
+    /// always [Self::len_packed] on success.
+    pub fn write_to_bytes(&self, buf: &mut [u8]) -> Result<usize, PusError> {
+        let len = self.len_packed();
+        if buf.len() < len {
+            return Err(ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                found: buf.len(),
+                expected: len,
+            })
+            .into());
+        }
+        if self.version != PusVersion::PusC {
+            return Err(PusError::VersionNotSupported(self.version));
+        }
+        buf[0] = (self.version.raw() << 4) | self.ack.bits();
+        buf[1] = self.service;
+        buf[2] = self.subservice;
+        let source_id = self.source_id.to_be_bytes();
+        let source_id_end = self.source_id_width.sec_header_len();
+        buf[3..source_id_end].copy_from_slice(&source_id[2 - self.source_id_width.num_bytes()..]);
+        if let Some(destination_id) = self.destination_id {
+            buf[source_id_end..len].copy_from_slice(&destination_id.to_be_bytes());
+        }
+        Ok(len)
+    }
+
+    /// Parse a secondary header with the default two byte source ID field from the start of the
+    /// passed slice. On success, it returns a tuple containing the header and the consumed length.
+    pub fn from_bytes(slice: &[u8]) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_source_id_width(slice, SourceIdWidth::TwoBytes)
+    }
+
+    /// Like [Self::from_bytes], but for a secondary header with the given source ID width.
+    pub fn from_bytes_with_source_id_width(
+        slice: &[u8],
+        source_id_width: SourceIdWidth,
+    ) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_layout(slice, source_id_width.into())
+    }
+
+    /// Like [Self::from_bytes], but for a secondary header with the given layout, which may
+    /// include the destination ID field.
+    pub fn from_bytes_with_layout(
+        slice: &[u8],
+        layout: PusTcSecondaryHeaderLayout,
+    ) -> Result<(Self, usize), PusError> {
+        let len = layout.sec_header_len();
+        let source_id_width = layout.source_id_width;
+        if slice.len() < len {
+            return Err(ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                found: slice.len(),
+                expected: len,
+            })
+            .into());
         }
+        let source_id = match source_id_width {
+            SourceIdWidth::None => 0,
+            SourceIdWidth::OneByte => slice[3] as u16,
+            SourceIdWidth::TwoBytes => u16::from_be_bytes([slice[3], slice[4]]),
+        };
+        let destination_id = if layout.destination_id {
+            let start = source_id_width.sec_header_len();
+            Some(u16::from_be_bytes([slice[start], slice[start + 1]]))
+        } else {
+            None
+        };
+        Ok((
+            PusTcSecondaryHeader {
+                service: slice[1],
+                subservice: slice[2],
+                source_id,
+                ack: AckFlags::from_bits_truncate(slice[0]),
+                version: PusVersion::from_raw(slice[0] >> 4),
+                source_id_width,
+                destination_id,
+            },
+            len,
+        ))
     }
 }
 
+/// Result of [PusTc::from_bytes_detailed], which contains the parsed packet together with
+/// the consumed and remaining byte count of the input slice.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct ParsedPacket<'raw_data> {
+    pub packet: PusTc<'raw_data>,
+    /// Number of bytes of the input slice which were consumed by the packet.
+    pub consumed: usize,
+    /// Number of bytes left in the input slice after the packet.
+    pub trailing: usize,
+}
+
+/// Flat representation of the decoded fields of a [PusTc] with a stable C layout, which can be
+/// used for FFI bindings. It can be created with [PusTc::to_flat].
+///
+/// The application data pointer borrows from the data the [PusTc] was created from, so it is only
+/// valid as long as that data is alive. It is a null pointer if there is no application data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct PusTcFlat {
+    pub apid: u16,
+    pub seq_count: u16,
+    pub service: u8,
+    pub subservice: u8,
+    pub source_id: u16,
+    pub ack: u8,
+    pub app_data_ptr: *const u8,
+    pub app_data_len: usize,
+    /// Only valid if [PusTcFlat::crc16_valid] is set.
+    pub crc16: u16,
+    pub crc16_valid: bool,
+}
+
+/// Content based key of a telecommand which can be used to detect duplicate commands, created
+/// with [PusTc::dedup_key].
+///
+/// It contains the APID, the service and subservice, the acknowledgement flags, the source ID,
+/// the optional destination ID and the application data. The sequence count, the sequence flags,
+/// the spare bytes and the CRC16 are not included, so two commands which only differ in their
+/// sequence count have the same key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PusTcDedupKey<'raw_data> {
+    pub apid: u16,
+    pub service: u8,
+    pub subservice: u8,
+    pub ack: AckFlags,
+    pub source_id: u16,
+    pub destination_id: Option<u16>,
+    pub app_data: Option<&'raw_data [u8]>,
+}
+
 /// This class models the PUS C telecommand packet. It is the primary data structure to generate the
 /// raw byte representation of a PUS telecommand or to deserialize from one from raw bytes.
 ///
@@ -212,7 +603,10 @@ impl PusTcSecondaryHeader {
 /// [serde] feature is used, which allows to send around TC packets in a raw byte format using a
 /// serde provider like [postcard](https://docs.rs/postcard/latest/postcard/).
 ///
-/// There is no spare bytes support yet.
+/// Optional spare bytes, which are serialized between the application data and the CRC16, can be
+/// set with [PusTc::set_spare_bytes]. Because the length of the spare bytes can not be derived
+/// from the packet itself, [PusTc::from_bytes_with_spare_bytes] needs to be used to parse packets
+/// containing them.
 ///
 /// # Lifetimes
 ///
@@ -230,6 +624,9 @@ pub struct PusTc<'raw_data> {
     #[cfg_attr(feature = "serde", serde(skip))]
     raw_data: Option<&'raw_data [u8]>,
     app_data: Option<&'raw_data [u8]>,
+    spare_bytes: Option<&'raw_data [u8]>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    crc_kind: PusCrcKind,
     crc16: Option<u16>,
 }
 
@@ -246,6 +643,9 @@ impl<'raw_data> PusTc<'raw_data> {
     /// * `set_ccsds_len` - Can be used to automatically update the CCSDS space packet data length
     ///     field. If this is not set to true, [PusTc::update_ccsds_data_len] can be called to set
     ///     the correct value to this field manually
+    ///
+    /// The CCSDS data length field is not checked for overflows, [PusTc::try_new] can be used
+    /// for this.
     pub fn new(
         sp_header: &mut SpHeader,
         sec_header: PusTcSecondaryHeader,
@@ -258,8 +658,10 @@ impl<'raw_data> PusTc<'raw_data> {
             sp_header: *sp_header,
             raw_data: None,
             app_data,
+            spare_bytes: None,
             sec_header,
             calc_crc_on_serialization: true,
+            crc_kind: PusCrcKind::default(),
             crc16: None,
         };
         if set_ccsds_len {
@@ -268,6 +670,31 @@ impl<'raw_data> PusTc<'raw_data> {
         pus_tc
     }
 
+    /// Like [PusTc::new], but the CCSDS data length field is always set and
+    /// [PusError::PacketTooLarge] is returned if the packet length exceeds [PUS_TC_MAX_LEN].
+    pub fn try_new(
+        sp_header: &mut SpHeader,
+        sec_header: PusTcSecondaryHeader,
+        app_data: Option<&'raw_data [u8]>,
+    ) -> Result<Self, PusError> {
+        let mut pus_tc = Self::new(sp_header, sec_header, app_data, false);
+        pus_tc.try_update_ccsds_data_len()?;
+        Ok(pus_tc)
+    }
+
+    /// Like [PusTc::new], but the sequence flags of the space packet header are set to
+    /// [SequenceFlags::Unsegmented] automatically, which is the correct value for a telecommand
+    /// which is transmitted inside a single space packet.
+    pub fn new_auto_seg(
+        sp_header: &mut SpHeader,
+        sec_header: PusTcSecondaryHeader,
+        app_data: Option<&'raw_data [u8]>,
+        set_ccsds_len: bool,
+    ) -> Self {
+        sp_header.set_seq_flags(SequenceFlags::Unsegmented);
+        Self::new(sp_header, sec_header, app_data, set_ccsds_len)
+    }
+
     /// Simplified version of the [PusTc::new] function which allows to only specify service and
     /// subservice instead of the full PUS TC secondary header.
     pub fn new_simple(
@@ -285,31 +712,103 @@ impl<'raw_data> PusTc<'raw_data> {
         )
     }
 
+    /// Returns a reference to the space packet header. Use [Self::sp_header_owned] if the header
+    /// needs to outlive the packet.
     pub fn sp_header(&self) -> &SpHeader {
         &self.sp_header
     }
 
+    /// Returns a copy of the space packet header which is not bound to the lifetime of the
+    /// packet, for example to keep the header for a reply after the packet was dropped.
+    pub fn sp_header_owned(&self) -> SpHeader {
+        self.sp_header
+    }
+
+    /// Minimum length of a telecommand, which is [PUS_TC_MIN_LEN_WITHOUT_APP_DATA]. In contrast
+    /// to [Self::len_packed], this can be used in const contexts, for example to size static
+    /// buffers for telecommands without application data.
+    pub const fn min_len() -> usize {
+        PUS_TC_MIN_LEN_WITHOUT_APP_DATA
+    }
+
+    /// Length of the serialized telecommand, including the application data, the spare bytes and
+    /// the CRC16.
     pub fn len_packed(&self) -> usize {
-        let mut length = PUS_TC_MIN_LEN_WITHOUT_APP_DATA;
+        let mut length = CCSDS_HEADER_LEN + self.sec_header.len_packed() + size_of::<CrcType>();
         if let Some(app_data) = self.app_data {
             length += app_data.len();
         }
-        length
+        length + self.spare_bytes_len()
     }
 
-    pub fn set_ack_field(&mut self, ack: u8) -> bool {
-        if ack > 0b1111 {
-            return false;
-        }
-        self.sec_header.ack = ack & 0b1111;
-        true
+    /// Set the spare bytes which are serialized between the application data and the CRC16.
+    /// [PusTc::update_ccsds_data_len] needs to be called afterwards if the CCSDS data length
+    /// field is not updated otherwise.
+    pub fn set_spare_bytes(&mut self, spare_bytes: Option<&'raw_data [u8]>) {
+        self.spare_bytes = spare_bytes;
+        self.raw_data = None;
+    }
+
+    pub fn spare_bytes(&self) -> Option<&'raw_data [u8]> {
+        self.spare_bytes
+    }
+
+    fn spare_bytes_len(&self) -> usize {
+        self.spare_bytes.map_or(0, |spare_bytes| spare_bytes.len())
+    }
+
+    pub fn set_ack_field(&mut self, ack: AckFlags) {
+        self.sec_header.ack = ack;
+        self.raw_data = None;
     }
 
     pub fn set_source_id(&mut self, source_id: u16) {
         self.sec_header.source_id = source_id;
+        self.raw_data = None;
+    }
+
+    /// Set the CRC16 algorithm which is used for the packet error control field. The default
+    /// is [PusCrcKind::CcittFalse]. The cached CRC16 is invalidated.
+    pub fn set_crc_kind(&mut self, crc_kind: PusCrcKind) {
+        self.crc_kind = crc_kind;
+        self.crc16 = None;
+        self.raw_data = None;
+    }
+
+    pub fn crc_kind(&self) -> PusCrcKind {
+        self.crc_kind
+    }
+
+    /// Set the service type. The cached CRC16 is invalidated, so [PusTc::calc_own_crc16] needs
+    /// to be called again if [PusTc::calc_crc_on_serialization] is not set.
+    pub fn set_service(&mut self, service: u8) {
+        self.sec_header.service = service;
+        self.crc16 = None;
+        self.raw_data = None;
+    }
+
+    /// Set the subservice type. The cached CRC16 is invalidated, so [PusTc::calc_own_crc16] needs
+    /// to be called again if [PusTc::calc_crc_on_serialization] is not set.
+    pub fn set_subservice(&mut self, subservice: u8) {
+        self.sec_header.subservice = subservice;
+        self.crc16 = None;
+        self.raw_data = None;
+    }
+
+    pub fn set_apid(&mut self, apid: u16) -> bool {
+        self.raw_data = None;
+        self.sp_header.set_apid(apid)
+    }
+
+    pub fn set_seq_count(&mut self, seq_count: u16) -> bool {
+        self.raw_data = None;
+        self.sp_header.set_seq_count(seq_count)
     }
 
-    sp_header_impls!();
+    pub fn set_seq_flags(&mut self, seq_flag: SequenceFlags) {
+        self.raw_data = None;
+        self.sp_header.set_seq_flags(seq_flag)
+    }
 
     /// Calculate the CCSDS space packet data length field and sets it
     /// This is called automatically if the `set_ccsds_len` argument in the [PusTc::new] call was
@@ -317,35 +816,91 @@ impl<'raw_data> PusTc<'raw_data> {
     /// If this was not done or the application data is set or changed after construction,
     /// this function needs to be called to ensure that the data length field of the CCSDS header
     /// is set correctly.
+    ///
+    /// The value is truncated if the packet length exceeds [PUS_TC_MAX_LEN].
+    /// [PusTc::try_update_ccsds_data_len] can be used to detect this.
     pub fn update_ccsds_data_len(&mut self) {
-        self.sp_header.data_len =
-            self.len_packed() as u16 - size_of::<crate::zc::SpHeader>() as u16 - 1;
+        self.sp_header.data_len = self.ccsds_data_len_value();
+    }
+
+    /// Checked version of [PusTc::update_ccsds_data_len] which returns
+    /// [PusError::PacketTooLarge] with the packet length if it exceeds [PUS_TC_MAX_LEN]. The
+    /// header is not updated in this case.
+    pub fn try_update_ccsds_data_len(&mut self) -> Result<(), PusError> {
+        let len_packed = self.len_packed();
+        if len_packed > PUS_TC_MAX_LEN {
+            return Err(PusError::PacketTooLarge {
+                advertised: len_packed,
+                max: PUS_TC_MAX_LEN,
+            });
+        }
+        self.update_ccsds_data_len();
+        Ok(())
+    }
+
+    /// Calculate the value of the CCSDS space packet data length field from [PusTc::len_packed]
+    /// without updating the header. This can be used to check whether the data length field of
+    /// the current header is set correctly.
+    pub fn ccsds_data_len_value(&self) -> u16 {
+        (self.len_packed() - CCSDS_HEADER_LEN - 1) as u16
     }
 
     /// This function should be called before the TC packet is serialized if
     /// [PusTc::calc_crc_on_serialization] is set to False. It will calculate and cache the CRC16.
-    pub fn calc_own_crc16(&mut self) {
-        let mut digest = CRC_CCITT_FALSE.digest();
+    ///
+    /// The secondary header is serialized for this, so [PusError::VersionNotSupported] is
+    /// returned if the secondary header does not have the PUS C version.
+    pub fn calc_own_crc16(&mut self) -> Result<(), PusError> {
+        let crc = self.crc_kind.crc();
+        let mut digest = crc.digest();
         let sph_zc = crate::zc::SpHeader::from(self.sp_header);
         digest.update(sph_zc.as_bytes());
-        let pus_tc_header = zc::PusTcSecondaryHeader::try_from(self.sec_header).unwrap();
-        digest.update(pus_tc_header.as_bytes());
+        let mut sec_header_buf = [0; PUS_TC_SECONDARY_HEADER_MAX_LEN];
+        let sec_header_len = self.sec_header.write_to_bytes(&mut sec_header_buf)?;
+        digest.update(&sec_header_buf[0..sec_header_len]);
         if let Some(app_data) = self.app_data {
             digest.update(app_data);
         }
-        self.crc16 = Some(digest.finalize())
+        if let Some(spare_bytes) = self.spare_bytes {
+            digest.update(spare_bytes);
+        }
+        self.crc16 = Some(digest.finalize());
+        Ok(())
     }
 
     /// This helper function calls both [PusTc::update_ccsds_data_len] and [PusTc::calc_own_crc16].
-    pub fn update_packet_fields(&mut self) {
+    pub fn update_packet_fields(&mut self) -> Result<(), PusError> {
         self.update_ccsds_data_len();
-        self.calc_own_crc16();
+        self.calc_own_crc16()
+    }
+
+    /// Recalculate the CRC16 of the raw telecommand in `buf` and write it into the CRC16 trailer
+    /// of `buf`, for example after the application data was modified in-place. The telecommand
+    /// is then parsed again from `buf` with the passed configuration, so the returned packet
+    /// reflects all modifications of the headers and the application data. On success, it
+    /// returns a tuple containing the instance and the found byte length of the packet.
+    ///
+    /// The space packet header and the secondary header are checked like in
+    /// [Self::from_bytes_with_config] before `buf` is modified.
+    pub fn refresh_from_buffer(
+        buf: &'raw_data mut [u8],
+        config: &PusTcParseConfig,
+    ) -> Result<(Self, usize), PusError> {
+        let sp_header = PusTc::sp_header_from_bytes(buf, config)?;
+        let (_, total_len) = PusTc::from_bytes_with_total_len_unchecked(
+            buf,
+            sp_header,
+            sp_header.total_len(),
+            config,
+        )?;
+        let crc16 = config.crc_kind.checksum(&buf[0..total_len - 2]);
+        buf[total_len - 2..total_len].copy_from_slice(&crc16.to_be_bytes());
+        Self::from_bytes_with_config(buf, config)
     }
 
     /// Write the raw PUS byte representation to a provided buffer.
     pub fn write_to_bytes(&self, slice: &mut [u8]) -> Result<usize, PusError> {
         let mut curr_idx = 0;
-        let tc_header_len = size_of::<zc::PusTcSecondaryHeader>();
         let total_size = self.len_packed();
         if total_size > slice.len() {
             return Err(ByteConversionError::ToSliceTooSmall(SizeMissmatch {
@@ -356,17 +911,17 @@ impl<'raw_data> PusTc<'raw_data> {
         }
         self.sp_header.write_to_be_bytes(slice)?;
         curr_idx += CCSDS_HEADER_LEN;
-        let sec_header = zc::PusTcSecondaryHeader::try_from(self.sec_header).unwrap();
-        sec_header
-            .write_to_bytes(&mut slice[curr_idx..curr_idx + tc_header_len])
-            .ok_or(ByteConversionError::ZeroCopyToError)?;
-
-        curr_idx += tc_header_len;
+        curr_idx += self.sec_header.write_to_bytes(&mut slice[curr_idx..])?;
         if let Some(app_data) = self.app_data {
             slice[curr_idx..curr_idx + app_data.len()].copy_from_slice(app_data);
             curr_idx += app_data.len();
         }
+        if let Some(spare_bytes) = self.spare_bytes {
+            slice[curr_idx..curr_idx + spare_bytes.len()].copy_from_slice(spare_bytes);
+            curr_idx += spare_bytes.len();
+        }
         let crc16 = crc_procedure(
+            self.crc_kind,
             self.calc_crc_on_serialization,
             &self.crc16,
             0,
@@ -378,131 +933,1204 @@ impl<'raw_data> PusTc<'raw_data> {
         Ok(curr_idx)
     }
 
+    /// Like [Self::write_to_bytes], but starts writing at the given `offset` inside the buffer.
+    /// Returns the index after the last written byte, which can be used as the offset for
+    /// the next packet. [ByteConversionError::ToSliceTooSmall] is returned if the packet does not
+    /// fit into the buffer at the given offset.
+    pub fn write_to_bytes_at(&self, buf: &mut [u8], offset: usize) -> Result<usize, PusError> {
+        let too_small = |expected: usize| {
+            ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                found: buf.len(),
+                expected,
+            })
+        };
+        // An offset close to usize::MAX must not wrap around the end of the address space.
+        let end = offset
+            .checked_add(self.len_packed())
+            .ok_or(too_small(usize::MAX))?;
+        if end > buf.len() {
+            return Err(too_small(end).into());
+        }
+        let written_len = self.write_to_bytes(&mut buf[offset..])?;
+        Ok(offset + written_len)
+    }
+
     #[cfg(feature = "alloc")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     pub fn append_to_vec(&self, vec: &mut Vec<u8>) -> Result<usize, PusError> {
-        let sph_zc = crate::zc::SpHeader::from(self.sp_header);
-        let mut appended_len = PUS_TC_MIN_LEN_WITHOUT_APP_DATA;
-        if let Some(app_data) = self.app_data {
-            appended_len += app_data.len();
+        self.append_to_vec_returning_crc(vec)
+            .map(|(appended_len, _)| appended_len)
+    }
+
+    /// Serialize the packet into a newly allocated vector. The CRC16 is handled like in
+    /// [PusTc::write_to_bytes].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_vec(&self) -> Result<Vec<u8>, PusError> {
+        let mut vec = Vec::with_capacity(self.len_packed());
+        self.append_to_vec(&mut vec)?;
+        Ok(vec)
+    }
+
+    /// Serialize the packet directly into the passed writer, for example a file or a TCP
+    /// stream, without an intermediate buffer. Returns the number of written bytes. The CRC16 is
+    /// handled like in [PusTc::write_to_bytes].
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    pub fn write_to_writer<W: std::io::Write>(&self, writer: &mut W) -> Result<usize, StdPusError> {
+        // Check the cached CRC16 before anything is written to the writer.
+        let cached_crc16 = match self.calc_crc_on_serialization {
+            true => None,
+            false => Some(self.crc16.ok_or(PusError::CrcCalculationMissing)?),
         };
+        let sph_zc = crate::zc::SpHeader::from(self.sp_header);
+        let mut sec_header_buf = [0; PUS_TC_SECONDARY_HEADER_MAX_LEN];
+        let sec_header_len = self.sec_header.write_to_bytes(&mut sec_header_buf)?;
+        let crc = self.crc_kind.crc();
+        let mut digest = crc.digest();
+        for part in [
+            Some(sph_zc.as_bytes()),
+            Some(&sec_header_buf[0..sec_header_len]),
+            self.app_data,
+            self.spare_bytes,
+        ]
+        .iter()
+        .flatten()
+        {
+            writer.write_all(part)?;
+            digest.update(part);
+        }
+        let crc16 = cached_crc16.unwrap_or_else(|| digest.finalize());
+        writer.write_all(&crc16.to_be_bytes())?;
+        Ok(self.len_packed())
+    }
+
+    /// Like [PusTc::append_to_vec], but also returns the CRC16 which was appended to the
+    /// vector.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn append_to_vec_returning_crc(&self, vec: &mut Vec<u8>) -> Result<(usize, u16), PusError> {
+        let sph_zc = crate::zc::SpHeader::from(self.sp_header);
+        let appended_len = self.len_packed();
         let start_idx = vec.len();
         let mut ser_len = 0;
         vec.extend_from_slice(sph_zc.as_bytes());
         ser_len += sph_zc.as_bytes().len();
-        // The PUS version is hardcoded to PUS C
-        let pus_tc_header = zc::PusTcSecondaryHeader::try_from(self.sec_header).unwrap();
-        vec.extend_from_slice(pus_tc_header.as_bytes());
-        ser_len += pus_tc_header.as_bytes().len();
+        let mut sec_header_buf = [0; PUS_TC_SECONDARY_HEADER_MAX_LEN];
+        let sec_header_len = self.sec_header.write_to_bytes(&mut sec_header_buf)?;
+        vec.extend_from_slice(&sec_header_buf[0..sec_header_len]);
+        ser_len += sec_header_len;
         if let Some(app_data) = self.app_data {
             vec.extend_from_slice(app_data);
             ser_len += app_data.len();
         }
+        if let Some(spare_bytes) = self.spare_bytes {
+            vec.extend_from_slice(spare_bytes);
+            ser_len += spare_bytes.len();
+        }
         let crc16 = crc_procedure(
+            self.crc_kind,
             self.calc_crc_on_serialization,
             &self.crc16,
-            start_idx,
+            0,
             ser_len,
-            &vec[start_idx..ser_len],
+            &vec[start_idx..start_idx + ser_len],
         )?;
         vec.extend_from_slice(crc16.to_be_bytes().as_slice());
-        Ok(appended_len)
+        Ok((appended_len, crc16))
     }
 
     /// Create a [PusTc] instance from a raw slice. On success, it returns a tuple containing
     /// the instance and the found byte length of the packet.
     pub fn from_bytes(slice: &'raw_data [u8]) -> Result<(Self, usize), PusError> {
-        let raw_data_len = slice.len();
-        if raw_data_len < PUS_TC_MIN_LEN_WITHOUT_APP_DATA {
-            return Err(PusError::RawDataTooShort(raw_data_len));
-        }
-        let mut current_idx = 0;
-        let (sp_header, _) = SpHeader::from_be_bytes(&slice[0..CCSDS_HEADER_LEN])?;
-        current_idx += CCSDS_HEADER_LEN;
-        let total_len = sp_header.total_len();
-        if raw_data_len < total_len || total_len < PUS_TC_MIN_LEN_WITHOUT_APP_DATA {
-            return Err(PusError::RawDataTooShort(raw_data_len));
-        }
-        let sec_header = zc::PusTcSecondaryHeader::from_bytes(
-            &slice[current_idx..current_idx + PUC_TC_SECONDARY_HEADER_LEN],
-        )
-        .ok_or(ByteConversionError::ZeroCopyFromError)?;
-        current_idx += PUC_TC_SECONDARY_HEADER_LEN;
-        let raw_data = &slice[0..total_len];
-        let pus_tc = PusTc {
-            sp_header,
-            sec_header: PusTcSecondaryHeader::try_from(sec_header).unwrap(),
-            raw_data: Some(raw_data),
-            app_data: user_data_from_raw(current_idx, total_len, raw_data_len, slice)?,
-            calc_crc_on_serialization: false,
-            crc16: Some(crc_from_raw_data(raw_data)?),
-        };
-        verify_crc16_from_raw(raw_data, pus_tc.crc16.expect("CRC16 invalid"))?;
-        Ok((pus_tc, total_len))
+        Self::from_bytes_with_config(slice, &PusTcParseConfig::default())
     }
 
-    #[deprecated(since = "0.5.2", note = "use raw_bytes() instead")]
-    pub fn raw(&self) -> Option<&'raw_data [u8]> {
-        self.raw_bytes()
+    /// Like [Self::from_bytes], but with mission specific options like the secondary header
+    /// layout, the CRC16 algorithm, spare bytes and a maximum packet length, which can be
+    /// combined freely.
+    pub fn from_bytes_with_config(
+        slice: &'raw_data [u8],
+        config: &PusTcParseConfig,
+    ) -> Result<(Self, usize), PusError> {
+        let sp_header = Self::sp_header_from_bytes(slice, config)?;
+        Self::from_bytes_with_total_len(slice, sp_header, sp_header.total_len(), config)
     }
 
-    /// If [Self] was constructed [Self::from_bytes], this function will return the slice it was
-    /// constructed from. Otherwise, [None] will be returned.
-    pub fn raw_bytes(&self) -> Option<&'raw_data [u8]> {
-        self.raw_data
+    /// Like [Self::from_bytes], but the CRC16 is only cached and not verified. This allows to
+    /// inspect packets with an invalid CRC16, for example when replaying captured data.
+    /// [Self::verify_crc] can be used to verify the CRC16 separately.
+    pub fn from_bytes_unchecked(slice: &'raw_data [u8]) -> Result<(Self, usize), PusError> {
+        let config = PusTcParseConfig::default();
+        let sp_header = Self::sp_header_from_bytes(slice, &config)?;
+        let total_len = sp_header.total_len();
+        Self::from_bytes_with_total_len_unchecked(slice, sp_header, total_len, &config)
     }
-}
 
-impl PartialEq for PusTc<'_> {
-    fn eq(&self, other: &Self) -> bool {
-        self.sp_header == other.sp_header
-            && self.sec_header == other.sec_header
-            && self.app_data == other.app_data
+    /// Verify the CRC16 stored in the raw data the packet was parsed from, using the configured
+    /// [PusCrcKind]. Returns [PusError::NoRawData] if the packet was not created from a raw
+    /// slice.
+    pub fn verify_crc(&self) -> Result<(), PusError> {
+        let raw_data = self.raw_data.ok_or(PusError::NoRawData)?;
+        let crc16 = crc_from_raw_data(raw_data)?;
+        if self.crc_kind.checksum(&raw_data[0..raw_data.len() - 2]) != crc16 {
+            return Err(PusError::IncorrectCrc(crc16));
+        }
+        Ok(())
     }
-}
-
-//noinspection RsTraitImplementation
-impl CcsdsPacket for PusTc<'_> {
-    ccsds_impl!();
-}
 
-//noinspection RsTraitImplementation
-impl PusPacket for PusTc<'_> {
-    delegate!(to self.sec_header {
-        fn pus_version(&self) -> PusVersion;
-        fn service(&self) -> u8;
-        fn subservice(&self) -> u8;
-    });
+    /// Check whether the packet is well-formed without serializing it. This checks that
+    ///
+    /// - the APID and the sequence count fit into their fields, otherwise
+    ///   [PusError::SpHeaderError] is returned.
+    /// - the packet length can be expressed with the CCSDS data length field, otherwise
+    ///   [PusError::PacketTooLarge] is returned.
+    /// - the PUS version is PUS C, otherwise [PusError::VersionNotSupported] is returned.
+    pub fn validate(&self) -> Result<(), PusError> {
+        if self.apid() > MAX_APID {
+            return Err(SpHeaderError::ApidTooLarge(self.apid()).into());
+        }
+        if self.seq_count() > MAX_SEQ_COUNT {
+            return Err(SpHeaderError::SeqCountTooLarge(self.seq_count()).into());
+        }
+        if self.len_packed() > PUS_TC_MAX_LEN {
+            return Err(PusError::PacketTooLarge {
+                advertised: self.len_packed(),
+                max: PUS_TC_MAX_LEN,
+            });
+        }
+        if self.sec_header.version != PusVersion::PusC {
+            return Err(PusError::VersionNotSupported(self.sec_header.version));
+        }
+        Ok(())
+    }
 
-    fn user_data(&self) -> Option<&[u8]> {
-        self.app_data
+    /// Check that the CCSDS data length field matches the current packet fields, for example
+    /// after the application data was changed without calling [Self::update_ccsds_data_len].
+    /// Returns [PusError::LengthFieldMismatch] with the packet length derived from the CCSDS data
+    /// length field and the expected packet length otherwise.
+    pub fn verify_length_consistency(&self) -> Result<(), PusError> {
+        let total_len = self.sp_header.total_len();
+        if total_len != self.len_packed() {
+            return Err(PusError::LengthFieldMismatch(SizeMissmatch {
+                found: total_len,
+                expected: self.len_packed(),
+            }));
+        }
+        Ok(())
     }
 
-    fn crc16(&self) -> Option<u16> {
-        self.crc16
+    /// Like [Self::from_bytes], but for missions which use a source ID field width which is
+    /// different from the default two bytes.
+    pub fn from_bytes_with_source_id_width(
+        slice: &'raw_data [u8],
+        source_id_width: SourceIdWidth,
+    ) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_layout(slice, source_id_width.into())
     }
-}
 
-//noinspection RsTraitImplementation
-impl GenericPusTcSecondaryHeader for PusTc<'_> {
+    /// Like [Self::from_bytes], but for missions which use the given secondary header layout,
+    /// for example with the optional destination ID field.
+    pub fn from_bytes_with_layout(
+        slice: &'raw_data [u8],
+        layout: PusTcSecondaryHeaderLayout,
+    ) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_config(
+            slice,
+            &PusTcParseConfig {
+                layout,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Like [Self::from_bytes], but the CRC16 is verified with the passed CRC16 algorithm,
+    /// which is also used when the returned packet is serialized again.
+    pub fn from_bytes_with_crc_kind(
+        slice: &'raw_data [u8],
+        crc_kind: PusCrcKind,
+    ) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_config(
+            slice,
+            &PusTcParseConfig {
+                crc_kind,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Like [Self::from_bytes], but rejects packets with a total length advertised by the CCSDS
+    /// data length field which exceeds `max_len` with [PusError::PacketTooLarge]. This check is
+    /// performed before any slicing of the packet data.
+    pub fn from_bytes_capped(
+        slice: &'raw_data [u8],
+        max_len: usize,
+    ) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_config(
+            slice,
+            &PusTcParseConfig {
+                max_len: Some(max_len),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Like [Self::from_bytes], but returns a [ParsedPacket] which also contains the number of
+    /// trailing bytes left in the input slice. This can be used to parse a stream of packets.
+    pub fn from_bytes_detailed(
+        slice: &'raw_data [u8],
+    ) -> Result<ParsedPacket<'raw_data>, PusError> {
+        let (packet, consumed) = Self::from_bytes(slice)?;
+        Ok(ParsedPacket {
+            packet,
+            consumed,
+            trailing: slice.len() - consumed,
+        })
+    }
+
+    /// Like [Self::from_bytes], but returns the remainder of the input slice after the parsed
+    /// packet instead of the consumed length. This is useful to parse concatenated packets.
+    pub fn from_bytes_partial(slice: &'raw_data [u8]) -> Result<(Self, &'raw_data [u8]), PusError> {
+        let (packet, consumed) = Self::from_bytes(slice)?;
+        Ok((packet, &slice[consumed..]))
+    }
+
+    /// Create a [PusTc] instance from a raw slice when the total packet length is already known,
+    /// for example because it was delivered out-of-band by the transport framing.
+    ///
+    /// The `known_len` is used as the authoritative packet length to extract the application data
+    /// and to locate the CRC16, instead of the CCSDS data length field. If `check_len_field` is
+    /// set to true, the length derived from the CCSDS data length field is checked against
+    /// `known_len` and [PusError::LengthFieldMismatch] is returned if they disagree.
+    pub fn from_bytes_with_known_len(
+        slice: &'raw_data [u8],
+        known_len: usize,
+        check_len_field: bool,
+    ) -> Result<(Self, usize), PusError> {
+        let config = PusTcParseConfig::default();
+        let sp_header = Self::sp_header_from_bytes(slice, &config)?;
+        if check_len_field && sp_header.total_len() != known_len {
+            return Err(PusError::LengthFieldMismatch(SizeMissmatch {
+                found: sp_header.total_len(),
+                expected: known_len,
+            }));
+        }
+        Self::from_bytes_with_total_len(slice, sp_header, known_len, &config)
+    }
+
+    /// Like [Self::from_bytes], but the last `num_spare_bytes` bytes before the CRC16 are
+    /// extracted as spare bytes instead of being part of the application data. Returns
+    /// [PusError::RawDataTooShort] if the packet is too short to contain the spare bytes.
+    pub fn from_bytes_with_spare_bytes(
+        slice: &'raw_data [u8],
+        num_spare_bytes: usize,
+    ) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_config(
+            slice,
+            &PusTcParseConfig {
+                num_spare_bytes,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Check that the slice can hold a telecommand with the configured layout and parse the
+    /// space packet header. The total packet length is checked against the configured maximum
+    /// length.
+    fn sp_header_from_bytes(slice: &[u8], config: &PusTcParseConfig) -> Result<SpHeader, PusError> {
+        let min_len = CCSDS_HEADER_LEN + config.layout.sec_header_len() + size_of::<CrcType>();
+        if slice.len() < min_len {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        let (sp_header, _) = SpHeader::from_be_bytes(&slice[0..CCSDS_HEADER_LEN])?;
+        if let Some(max_len) = config.max_len {
+            if sp_header.total_len() > max_len {
+                return Err(PusError::PacketTooLarge {
+                    advertised: sp_header.total_len(),
+                    max: max_len,
+                });
+            }
+        }
+        Ok(sp_header)
+    }
+
+    fn from_bytes_with_total_len(
+        slice: &'raw_data [u8],
+        sp_header: SpHeader,
+        total_len: usize,
+        config: &PusTcParseConfig,
+    ) -> Result<(Self, usize), PusError> {
+        let (pus_tc, total_len) =
+            Self::from_bytes_with_total_len_unchecked(slice, sp_header, total_len, config)?;
+        pus_tc.verify_crc()?;
+        Ok((pus_tc, total_len))
+    }
+
+    fn from_bytes_with_total_len_unchecked(
+        slice: &'raw_data [u8],
+        sp_header: SpHeader,
+        total_len: usize,
+        config: &PusTcParseConfig,
+    ) -> Result<(Self, usize), PusError> {
+        let raw_data_len = slice.len();
+        let num_spare_bytes = config.num_spare_bytes;
+        let min_len = CCSDS_HEADER_LEN + config.layout.sec_header_len() + size_of::<CrcType>();
+        if raw_data_len < total_len || total_len < min_len + num_spare_bytes {
+            return Err(PusError::RawDataTooShort(raw_data_len));
+        }
+        let mut current_idx = CCSDS_HEADER_LEN;
+        let (sec_header, sec_header_len) =
+            PusTcSecondaryHeader::from_bytes_with_layout(&slice[current_idx..], config.layout)?;
+        current_idx += sec_header_len;
+        let raw_data = &slice[0..total_len];
+        let pus_tc = PusTc {
+            sp_header,
+            sec_header,
+            raw_data: Some(raw_data),
+            app_data: user_data_from_raw(
+                current_idx,
+                total_len - num_spare_bytes,
+                raw_data_len,
+                slice,
+            )?,
+            spare_bytes: match num_spare_bytes {
+                0 => None,
+                _ => Some(&raw_data[total_len - 2 - num_spare_bytes..total_len - 2]),
+            },
+            calc_crc_on_serialization: false,
+            crc_kind: config.crc_kind,
+            crc16: Some(crc_from_raw_data(raw_data)?),
+        };
+        Ok((pus_tc, total_len))
+    }
+
+    /// Determine the byte offsets of the packet fields inside the raw slice without creating a
+    /// [PusTc] instance. Only the packet length is validated by using the CCSDS data length
+    /// field, the CRC16 is not verified.
+    pub fn parse_offsets(slice: &[u8]) -> Result<PusTcOffsets, PusError> {
+        if slice.len() < PUS_TC_MIN_LEN_WITHOUT_APP_DATA {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        let total_len = u16::from_be_bytes([slice[4], slice[5]]) as usize + CCSDS_HEADER_LEN + 1;
+        if slice.len() < total_len || total_len < PUS_TC_MIN_LEN_WITHOUT_APP_DATA {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        let app_data_start = CCSDS_HEADER_LEN + PUC_TC_SECONDARY_HEADER_LEN;
+        let crc_pos = total_len - size_of::<CrcType>();
+        Ok(PusTcOffsets {
+            sec_header_start: CCSDS_HEADER_LEN,
+            app_data: app_data_start..crc_pos,
+            crc_pos,
+        })
+    }
+
+    /// Iterate over all telecommands which are stored back to back inside the passed slice.
+    /// Each item also contains the byte range of the packet inside the slice.
+    pub fn iter_packets_with_ranges(slice: &'raw_data [u8]) -> PusTcRangeIter<'raw_data> {
+        PusTcRangeIter {
+            raw_data: slice,
+            offset: 0,
+            failed: false,
+        }
+    }
+
+    /// Stricter version of the [PartialEq] implementation, which also requires the cached CRC16
+    /// of both packets to be equal. Please note that the CRC16 is only cached after a call to
+    /// [PusTc::calc_own_crc16] or if the packet was created with [PusTc::from_bytes].
+    pub fn strict_eq(&self, other: &Self) -> bool {
+        self == other && self.crc16 == other.crc16
+    }
+
+    /// Less strict version of the [PartialEq] implementation which ignores the sequence count of
+    /// the space packet header. The cached CRC16 is not compared either. This can be used to
+    /// check whether two packets contain the same command.
+    pub fn eq_ignoring_seq_count(&self, other: &Self) -> bool {
+        let mut other_sp_header = other.sp_header;
+        other_sp_header.psc.set_seq_count(self.seq_count());
+        self.sp_header == other_sp_header
+            && self.sec_header == other.sec_header
+            && self.app_data == other.app_data
+            && self.spare_bytes == other.spare_bytes
+    }
+
+    /// Content based key of the packet which excludes the sequence count and the CRC16. See
+    /// [PusTcDedupKey] for the list of included fields.
+    pub fn dedup_key(&self) -> PusTcDedupKey<'raw_data> {
+        PusTcDedupKey {
+            apid: self.apid(),
+            service: self.sec_header.service,
+            subservice: self.sec_header.subservice,
+            ack: self.sec_header.ack,
+            source_id: self.sec_header.source_id(),
+            destination_id: self.sec_header.destination_id,
+            app_data: self.app_data,
+        }
+    }
+
+    /// Create a [PusTcFlat] representation of the packet for FFI bindings.
+    pub fn to_flat(&self) -> PusTcFlat {
+        let (app_data_ptr, app_data_len) = match self.app_data {
+            Some(app_data) => (app_data.as_ptr(), app_data.len()),
+            None => (core::ptr::null(), 0),
+        };
+        PusTcFlat {
+            apid: self.apid(),
+            seq_count: self.seq_count(),
+            service: self.sec_header.service,
+            subservice: self.sec_header.subservice,
+            source_id: self.sec_header.source_id(),
+            ack: self.sec_header.ack.bits(),
+            app_data_ptr,
+            app_data_len,
+            crc16: self.crc16.unwrap_or(0),
+            crc16_valid: self.crc16.is_some(),
+        }
+    }
+
+    #[deprecated(since = "0.5.2", note = "use raw_bytes() instead")]
+    pub fn raw(&self) -> Option<&'raw_data [u8]> {
+        self.raw_bytes()
+    }
+
+    /// If [Self] was constructed [Self::from_bytes], this function will return the slice it was
+    /// constructed from. Otherwise, [None] will be returned. The slice is discarded if a packet
+    /// field is modified with one of the setters, so it can not become stale. This is not
+    /// possible for direct modifications of the public [Self::sec_header] field.
+    pub fn raw_bytes(&self) -> Option<&'raw_data [u8]> {
+        self.raw_data
+    }
+
+    /// Returns the slice the packet was constructed from if it is available, which allows to
+    /// forward received packets unchanged. Otherwise, for example if the packet was modified
+    /// after parsing, the packet is serialized into a new vector.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn as_raw_or_serialize(&self) -> Result<Cow<'raw_data, [u8]>, PusError> {
+        match self.raw_data {
+            Some(raw_data) => Ok(Cow::Borrowed(raw_data)),
+            None => Ok(Cow::Owned(self.to_vec()?)),
+        }
+    }
+
+    /// Create a [PusTcOwned] which owns a copy of the application data and the spare bytes, so
+    /// it is not bound to the lifetime of the data this packet borrows from.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_owned(&self) -> PusTcOwned {
+        PusTcOwned::from(*self)
+    }
+}
+
+/// Owned variant of [PusTc] which stores the application data and the spare bytes in vectors.
+/// It can be created with [PusTc::to_owned] and be moved and stored freely.
+///
+/// Serialization is performed by the [PusTc] returned by [PusTcOwned::as_pus_tc]. The CRC16 is
+/// always calculated on serialization, using the [PusCrcKind] of the packet the owned variant was
+/// created from. Like for [PusTc], the CRC algorithm is not serialized with serde.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PusTcOwned {
+    sp_header: SpHeader,
+    pub sec_header: PusTcSecondaryHeader,
+    app_data: Option<Vec<u8>>,
+    spare_bytes: Option<Vec<u8>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    crc_kind: PusCrcKind,
+}
+
+#[cfg(feature = "alloc")]
+impl PusTcOwned {
+    /// Create an owned telecommand. The CCSDS data length field is always updated.
+    pub fn new(
+        sp_header: &mut SpHeader,
+        sec_header: PusTcSecondaryHeader,
+        app_data: Option<Vec<u8>>,
+    ) -> Self {
+        let mut owned = PusTc::new(sp_header, sec_header, None, false).to_owned();
+        owned.app_data = app_data;
+        owned.update_ccsds_data_len();
+        owned
+    }
+
+    /// Borrowed view on the packet, which provides the full [PusTc] API.
+    pub fn as_pus_tc(&self) -> PusTc<'_> {
+        let mut sp_header = self.sp_header;
+        let mut pus_tc = PusTc::new(
+            &mut sp_header,
+            self.sec_header,
+            self.app_data.as_deref(),
+            false,
+        );
+        pus_tc.set_spare_bytes(self.spare_bytes.as_deref());
+        pus_tc.set_crc_kind(self.crc_kind);
+        pus_tc
+    }
+
+    pub fn sp_header(&self) -> &SpHeader {
+        &self.sp_header
+    }
+
+    pub fn app_data(&self) -> Option<&[u8]> {
+        self.app_data.as_deref()
+    }
+
+    /// Replace the application data. The CCSDS data length field is updated accordingly.
+    pub fn set_app_data(&mut self, app_data: Option<Vec<u8>>) {
+        self.app_data = app_data;
+        self.update_ccsds_data_len();
+    }
+
+    pub fn spare_bytes(&self) -> Option<&[u8]> {
+        self.spare_bytes.as_deref()
+    }
+
+    /// Replace the spare bytes. The CCSDS data length field is updated accordingly.
+    pub fn set_spare_bytes(&mut self, spare_bytes: Option<Vec<u8>>) {
+        self.spare_bytes = spare_bytes;
+        self.update_ccsds_data_len();
+    }
+
+    /// Set the CRC16 algorithm which is used for the packet error control field. The default
+    /// is [PusCrcKind::CcittFalse].
+    pub fn set_crc_kind(&mut self, crc_kind: PusCrcKind) {
+        self.crc_kind = crc_kind;
+    }
+
+    pub fn crc_kind(&self) -> PusCrcKind {
+        self.crc_kind
+    }
+
+    pub fn len_packed(&self) -> usize {
+        self.as_pus_tc().len_packed()
+    }
+
+    pub fn write_to_bytes(&self, slice: &mut [u8]) -> Result<usize, PusError> {
+        self.as_pus_tc().write_to_bytes(slice)
+    }
+
+    pub fn append_to_vec(&self, vec: &mut Vec<u8>) -> Result<usize, PusError> {
+        self.as_pus_tc().append_to_vec(vec)
+    }
+
+    fn update_ccsds_data_len(&mut self) {
+        self.sp_header.data_len = self.as_pus_tc().ccsds_data_len_value();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<PusTc<'_>> for PusTcOwned {
+    fn from(pus_tc: PusTc<'_>) -> Self {
+        Self {
+            sp_header: pus_tc.sp_header,
+            sec_header: pus_tc.sec_header,
+            app_data: pus_tc.app_data.map(|app_data| app_data.to_vec()),
+            spare_bytes: pus_tc.spare_bytes.map(|spare_bytes| spare_bytes.to_vec()),
+            crc_kind: pus_tc.crc_kind,
+        }
+    }
+}
+
+/// Iterator over consecutive PUS telecommands inside a raw buffer, created with
+/// [PusTc::iter_packets_with_ranges]. Each item contains the parsed packet and its byte range
+/// inside the buffer.
+///
+/// The iteration ends when the end of the buffer is reached or when the remaining data can not
+/// be parsed as a telecommand. [PusTcRangeIter::offset] can be used to check where the iteration
+/// stopped.
+#[derive(Debug)]
+pub struct PusTcRangeIter<'raw_data> {
+    raw_data: &'raw_data [u8],
+    offset: usize,
+    failed: bool,
+}
+
+impl PusTcRangeIter<'_> {
+    /// Offset of the next packet to parse. After the iteration ended, this is equal to the
+    /// buffer length if all data was consumed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'raw_data> Iterator for PusTcRangeIter<'raw_data> {
+    type Item = (PusTc<'raw_data>, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.offset >= self.raw_data.len() {
+            return None;
+        }
+        match PusTc::from_bytes(&self.raw_data[self.offset..]) {
+            Ok((pus_tc, packet_len)) => {
+                let range = self.offset..self.offset + packet_len;
+                self.offset += packet_len;
+                Some((pus_tc, range))
+            }
+            Err(_) => {
+                self.failed = true;
+                None
+            }
+        }
+    }
+}
+
+/// Iterator which splits application data which is too large for a single telecommand into
+/// multiple serialized space packets with the segmentation sequence flags
+/// [SequenceFlags::FirstSegment], [SequenceFlags::ContinuationSegment] and
+/// [SequenceFlags::LastSegment]. Application data which fits into one packet yields a single
+/// [SequenceFlags::Unsegmented] packet.
+///
+/// Only the first segment contains the PUS TC secondary header, which is serialized with its own
+/// [PusTcSecondaryHeader::layout], so custom source ID widths and the destination ID field are
+/// supported. All segments share the same APID, the sequence count is incremented for each
+/// segment and wraps around at [crate::MAX_SEQ_COUNT]. Each segment ends with a CRC16 calculated
+/// over the segment itself with the configured [PusCrcKind].
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+pub struct PusTcSegmentIter<'app_data> {
+    apid: u16,
+    seq_count: u16,
+    sec_header: PusTcSecondaryHeader,
+    app_data: &'app_data [u8],
+    max_packet_len: usize,
+    crc_kind: PusCrcKind,
+    offset: usize,
+    first: bool,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<'app_data> PusTcSegmentIter<'app_data> {
+    /// Create a new segmenter. `max_packet_len` is the maximum length of a single segment
+    /// including all headers and the CRC16, which can be at most [PUS_TC_MAX_LEN]. The CRC16 of
+    /// each segment is calculated with `crc_kind`.
+    ///
+    /// Returns [PusError::SpHeaderError] for an invalid APID or start sequence count,
+    /// [PusError::PacketTooLarge] if `max_packet_len` exceeds [PUS_TC_MAX_LEN] and
+    /// [ByteConversionError::ToSliceTooSmall] if it can not hold at least one application data
+    /// byte in the first segment.
+    pub fn new(
+        apid: u16,
+        seq_count: u16,
+        sec_header: PusTcSecondaryHeader,
+        app_data: &'app_data [u8],
+        max_packet_len: usize,
+        crc_kind: PusCrcKind,
+    ) -> Result<Self, PusError> {
+        SpHeader::const_new_from_single_fields(
+            PacketType::Tc,
+            true,
+            apid,
+            SequenceFlags::Unsegmented,
+            seq_count,
+            0,
+        )?;
+        if max_packet_len > PUS_TC_MAX_LEN {
+            return Err(PusError::PacketTooLarge {
+                advertised: max_packet_len,
+                max: PUS_TC_MAX_LEN,
+            });
+        }
+        let min_len = CCSDS_HEADER_LEN + sec_header.len_packed() + size_of::<CrcType>() + 1;
+        if max_packet_len < min_len {
+            return Err(ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                found: max_packet_len,
+                expected: min_len,
+            })
+            .into());
+        }
+        // Also checks the PUS version, so serializing the segments can not fail.
+        sec_header.write_to_bytes(&mut [0; PUS_TC_SECONDARY_HEADER_MAX_LEN])?;
+        Ok(Self {
+            apid,
+            seq_count,
+            sec_header,
+            app_data,
+            max_packet_len,
+            crc_kind,
+            offset: 0,
+            first: true,
+            done: false,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for PusTcSegmentIter<'_> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let sec_header_len = match self.first {
+            true => self.sec_header.len_packed(),
+            false => 0,
+        };
+        let capacity =
+            self.max_packet_len - CCSDS_HEADER_LEN - sec_header_len - size_of::<CrcType>();
+        let chunk_len = core::cmp::min(capacity, self.app_data.len() - self.offset);
+        let last = self.offset + chunk_len == self.app_data.len();
+        let seq_flags = match (self.first, last) {
+            (true, true) => SequenceFlags::Unsegmented,
+            (true, false) => SequenceFlags::FirstSegment,
+            (false, false) => SequenceFlags::ContinuationSegment,
+            (false, true) => SequenceFlags::LastSegment,
+        };
+        let packet_len = CCSDS_HEADER_LEN + sec_header_len + chunk_len + size_of::<CrcType>();
+        let sp_header = SpHeader::const_new_from_single_fields(
+            PacketType::Tc,
+            self.first,
+            self.apid,
+            seq_flags,
+            self.seq_count,
+            (packet_len - CCSDS_HEADER_LEN - 1) as u16,
+        )
+        .expect("APID and sequence count are checked in PusTcSegmentIter::new");
+        let mut packet = vec![0; packet_len];
+        sp_header
+            .write_to_be_bytes(&mut packet)
+            .expect("packet buffer is larger than the space packet header");
+        let mut curr_idx = CCSDS_HEADER_LEN;
+        if self.first {
+            curr_idx += self
+                .sec_header
+                .write_to_bytes(&mut packet[curr_idx..])
+                .expect("secondary header is checked in PusTcSegmentIter::new");
+        }
+        packet[curr_idx..curr_idx + chunk_len]
+            .copy_from_slice(&self.app_data[self.offset..self.offset + chunk_len]);
+        curr_idx += chunk_len;
+        let crc16 = self.crc_kind.checksum(&packet[0..curr_idx]);
+        packet[curr_idx..].copy_from_slice(&crc16.to_be_bytes());
+        self.offset += chunk_len;
+        self.seq_count = (self.seq_count + 1) & MAX_SEQ_COUNT;
+        self.first = false;
+        self.done = last;
+        Some(packet)
+    }
+}
+
+/// Byte offsets of the fields of a raw PUS telecommand, returned by [PusTc::parse_offsets].
+///
+/// The accessor methods read the fields on demand and expect the same slice which was passed to
+/// [PusTc::parse_offsets]. They panic if a shorter slice is passed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PusTcOffsets {
+    pub sec_header_start: usize,
+    pub app_data: Range<usize>,
+    pub crc_pos: usize,
+}
+
+impl PusTcOffsets {
+    /// Total length of the packet including the CRC16.
+    pub fn total_len(&self) -> usize {
+        self.crc_pos + size_of::<CrcType>()
+    }
+
+    pub fn service(&self, slice: &[u8]) -> u8 {
+        slice[self.sec_header_start + 1]
+    }
+
+    pub fn subservice(&self, slice: &[u8]) -> u8 {
+        slice[self.sec_header_start + 2]
+    }
+
+    pub fn source_id(&self, slice: &[u8]) -> u16 {
+        u16::from_be_bytes([
+            slice[self.sec_header_start + 3],
+            slice[self.sec_header_start + 4],
+        ])
+    }
+
+    pub fn app_data<'slice>(&self, slice: &'slice [u8]) -> &'slice [u8] {
+        &slice[self.app_data.clone()]
+    }
+
+    pub fn crc16(&self, slice: &[u8]) -> u16 {
+        u16::from_be_bytes([slice[self.crc_pos], slice[self.crc_pos + 1]])
+    }
+}
+
+/// Lightweight view on a raw PUS telecommand which only validates the space packet header and
+/// the PUS TC secondary header. Neither the CRC16 nor the bounds of the application data are
+/// checked, which allows cheap routing decisions before paying for the full validation.
+/// [PusTcHeaderView::into_full] can be used to complete the parsing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PusTcHeaderView<'raw_data> {
+    raw_data: &'raw_data [u8],
+    sp_header: SpHeader,
+    sec_header: PusTcSecondaryHeader,
+}
+
+impl<'raw_data> PusTcHeaderView<'raw_data> {
+    pub fn parse(slice: &'raw_data [u8]) -> Result<Self, PusError> {
+        let header_len = CCSDS_HEADER_LEN + PUC_TC_SECONDARY_HEADER_LEN;
+        if slice.len() < header_len {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        let (sp_header, _) = SpHeader::from_be_bytes(&slice[0..CCSDS_HEADER_LEN])?;
+        let (sec_header, _) = PusTcSecondaryHeader::from_bytes(&slice[CCSDS_HEADER_LEN..])?;
+        Ok(Self {
+            raw_data: slice,
+            sp_header,
+            sec_header,
+        })
+    }
+
+    pub fn sp_header(&self) -> &SpHeader {
+        &self.sp_header
+    }
+
+    pub fn sec_header(&self) -> &PusTcSecondaryHeader {
+        &self.sec_header
+    }
+
+    /// Complete the parsing of the telecommand, including the verification of the CRC16.
+    pub fn into_full(self) -> Result<(PusTc<'raw_data>, usize), PusError> {
+        PusTc::from_bytes(self.raw_data)
+    }
+}
+
+//noinspection RsTraitImplementation
+impl CcsdsPacket for PusTcHeaderView<'_> {
+    ccsds_impl!();
+}
+
+//noinspection RsTraitImplementation
+impl GenericPusTcSecondaryHeader for PusTcHeaderView<'_> {
+    delegate!(to self.sec_header {
+        fn pus_version(&self) -> PusVersion;
+        fn service(&self) -> u8;
+        fn subservice(&self) -> u8;
+        fn source_id(&self) -> u16;
+        fn ack_flags(&self) -> AckFlags;
+        fn destination_id(&self) -> Option<u16>;
+    });
+}
+
+/// Zero-copy reader for a raw PUS telecommand. Only the packet length is validated on creation,
+/// all fields are read lazily from the underlying buffer when they are requested.
+///
+/// The CRC16 is not verified automatically. [PusTcReader::verify_crc] can be used to do this
+/// explicitly, which allows to skip the check if the packet integrity was already verified
+/// by a lower layer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PusTcReader<'raw_data> {
+    raw_data: &'raw_data [u8],
+}
+
+impl<'raw_data> PusTcReader<'raw_data> {
+    /// Create a new reader. The length of the packet is determined by the CCSDS data length
+    /// field and the raw data of the reader is truncated to it.
+    pub fn new(slice: &'raw_data [u8]) -> Result<Self, PusError> {
+        if slice.len() < PUS_TC_MIN_LEN_WITHOUT_APP_DATA {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        let total_len = u16::from_be_bytes([slice[4], slice[5]]) as usize + CCSDS_HEADER_LEN + 1;
+        if slice.len() < total_len || total_len < PUS_TC_MIN_LEN_WITHOUT_APP_DATA {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        Ok(Self {
+            raw_data: &slice[..total_len],
+        })
+    }
+
+    /// Raw bytes of the whole packet, including the CRC16.
+    pub fn raw_bytes(&self) -> &'raw_data [u8] {
+        self.raw_data
+    }
+
+    pub fn app_data(&self) -> Option<&'raw_data [u8]> {
+        let app_data_start = CCSDS_HEADER_LEN + PUC_TC_SECONDARY_HEADER_LEN;
+        let crc_pos = self.raw_data.len() - size_of::<CrcType>();
+        if crc_pos == app_data_start {
+            return None;
+        }
+        Some(&self.raw_data[app_data_start..crc_pos])
+    }
+
+    /// CRC16 stored in the last two bytes of the packet.
+    pub fn crc16(&self) -> u16 {
+        let len = self.raw_data.len();
+        u16::from_be_bytes([self.raw_data[len - 2], self.raw_data[len - 1]])
+    }
+
+    /// Verify the CRC16 of the packet. Returns [PusError::IncorrectCrc] with the stored CRC16
+    /// if the check fails.
+    pub fn verify_crc(&self) -> Result<(), PusError> {
+        verify_crc16_from_raw(self.raw_data, self.crc16())
+    }
+
+    /// Convert the reader into a full [PusTc] instance, including the verification of the CRC16.
+    pub fn into_pus_tc(self) -> Result<PusTc<'raw_data>, PusError> {
+        PusTc::from_bytes(self.raw_data).map(|(pus_tc, _)| pus_tc)
+    }
+}
+
+impl CcsdsPacket for PusTcReader<'_> {
+    fn ccsds_version(&self) -> u8 {
+        (self.raw_data[0] >> 5) & 0b111
+    }
+
+    fn packet_id(&self) -> PacketId {
+        PacketId::from(u16::from_be_bytes([self.raw_data[0], self.raw_data[1]]))
+    }
+
+    fn psc(&self) -> PacketSequenceCtrl {
+        PacketSequenceCtrl::from(u16::from_be_bytes([self.raw_data[2], self.raw_data[3]]))
+    }
+
+    fn data_len(&self) -> u16 {
+        u16::from_be_bytes([self.raw_data[4], self.raw_data[5]])
+    }
+}
+
+impl GenericPusTcSecondaryHeader for PusTcReader<'_> {
+    fn pus_version(&self) -> PusVersion {
+        PusVersion::from_raw(self.raw_data[CCSDS_HEADER_LEN] >> 4)
+    }
+
+    fn service(&self) -> u8 {
+        self.raw_data[CCSDS_HEADER_LEN + 1]
+    }
+
+    fn subservice(&self) -> u8 {
+        self.raw_data[CCSDS_HEADER_LEN + 2]
+    }
+
+    fn source_id(&self) -> u16 {
+        u16::from_be_bytes([
+            self.raw_data[CCSDS_HEADER_LEN + 3],
+            self.raw_data[CCSDS_HEADER_LEN + 4],
+        ])
+    }
+
+    fn ack_flags(&self) -> AckFlags {
+        AckFlags::from_bits_truncate(self.raw_data[CCSDS_HEADER_LEN])
+    }
+}
+
+/// Builder for unsegmented [PusTc] packets which allows to set the fields of the space packet
+/// header and the secondary header with chained calls.
+///
+/// The CCSDS data length field of the built packet is set correctly. The acknowledgement flags
+/// default to [ACK_ALL], all other fields default to 0.
+///
+/// # Example
+///
+/// ```
+/// use spacepackets::CcsdsPacket;
+/// use spacepackets::ecss::PusPacket;
+/// use spacepackets::tc::{PusTcBuilder, ACK_ALL};
+///
+/// let app_data = [1, 2, 3];
+/// let pus_tc = PusTcBuilder::new()
+///     .apid(0x02)
+///     .seq_count(5)
+///     .service(17)
+///     .subservice(1)
+///     .ack(ACK_ALL)
+///     .source_id(3)
+///     .app_data(&app_data)
+///     .build()
+///     .unwrap();
+/// assert_eq!(pus_tc.apid(), 0x02);
+/// assert_eq!(pus_tc.service(), 17);
+/// assert_eq!(pus_tc.user_data(), Some(app_data.as_slice()));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PusTcBuilder<'app_data> {
+    apid: u16,
+    seq_count: u16,
+    sec_header: PusTcSecondaryHeader,
+    app_data: Option<&'app_data [u8]>,
+}
+
+impl Default for PusTcBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'app_data> PusTcBuilder<'app_data> {
+    pub fn new() -> Self {
+        Self {
+            apid: 0,
+            seq_count: 0,
+            sec_header: PusTcSecondaryHeader::new_simple(0, 0),
+            app_data: None,
+        }
+    }
+
+    /// The APID is checked against [crate::MAX_APID] in [Self::build].
+    pub fn apid(mut self, apid: u16) -> Self {
+        self.apid = apid;
+        self
+    }
+
+    /// The sequence count is checked against [crate::MAX_SEQ_COUNT] in [Self::build].
+    pub fn seq_count(mut self, seq_count: u16) -> Self {
+        self.seq_count = seq_count;
+        self
+    }
+
+    pub fn service(mut self, service: u8) -> Self {
+        self.sec_header.service = service;
+        self
+    }
+
+    pub fn subservice(mut self, subservice: u8) -> Self {
+        self.sec_header.subservice = subservice;
+        self
+    }
+
+    pub fn ack(mut self, ack: AckFlags) -> Self {
+        self.sec_header.ack = ack;
+        self
+    }
+
+    pub fn source_id(mut self, source_id: u16) -> Self {
+        self.sec_header.source_id = source_id;
+        self
+    }
+
+    /// Add the optional destination ID field to the secondary header. It is omitted by default.
+    pub fn destination_id(mut self, destination_id: u16) -> Self {
+        self.sec_header.destination_id = Some(destination_id);
+        self
+    }
+
+    pub fn app_data(mut self, app_data: &'app_data [u8]) -> Self {
+        self.app_data = Some(app_data);
+        self
+    }
+
+    /// Create the telecommand. Returns [PusError::SpHeaderError] if the APID or the sequence
+    /// count exceed the bit width of their space packet header fields, and
+    /// [PusError::PacketTooLarge] if the packet length exceeds [PUS_TC_MAX_LEN].
+    pub fn build(self) -> Result<PusTc<'app_data>, PusError> {
+        let mut sp_header = SpHeader::const_new_from_single_fields(
+            PacketType::Tc,
+            true,
+            self.apid,
+            SequenceFlags::Unsegmented,
+            self.seq_count,
+            0,
+        )?;
+        PusTc::try_new(&mut sp_header, self.sec_header, self.app_data)
+    }
+}
+
+impl PartialEq for PusTc<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sp_header == other.sp_header
+            && self.sec_header == other.sec_header
+            && self.app_data == other.app_data
+            && self.spare_bytes == other.spare_bytes
+    }
+}
+
+/// Equivalent to [PusTc::from_bytes], but the consumed length is discarded.
+impl<'raw_data> TryFrom<&'raw_data [u8]> for PusTc<'raw_data> {
+    type Error = PusError;
+
+    fn try_from(slice: &'raw_data [u8]) -> Result<Self, Self::Error> {
+        Ok(Self::from_bytes(slice)?.0)
+    }
+}
+
+/// Only hashes the fields of [PusTc::dedup_key]. This is consistent with the [PartialEq]
+/// implementation because all of these fields are also compared there.
+impl Hash for PusTc<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dedup_key().hash(state);
+    }
+}
+
+/// Compact summary of the packet fields for logging purposes, for example
+/// `PUS TC[apid=0x02, seq=52, 17/1, ack=0xF, app_data=3B]`. The raw data is not printed.
+impl Display for PusTc<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "PUS TC[apid={:#04x}, seq={}, {}/{}, ack={:#X}, app_data={}B]",
+            self.apid(),
+            self.seq_count(),
+            self.sec_header.service,
+            self.sec_header.subservice,
+            self.sec_header.ack.bits(),
+            self.app_data.map_or(0, |app_data| app_data.len())
+        )
+    }
+}
+
+//noinspection RsTraitImplementation
+impl CcsdsPacket for PusTc<'_> {
+    ccsds_impl!();
+}
+
+//noinspection RsTraitImplementation
+impl PusPacket for PusTc<'_> {
+    delegate!(to self.sec_header {
+        fn pus_version(&self) -> PusVersion;
+        fn service(&self) -> u8;
+        fn subservice(&self) -> u8;
+    });
+
+    fn user_data(&self) -> Option<&[u8]> {
+        self.app_data
+    }
+
+    fn crc16(&self) -> Option<u16> {
+        self.crc16
+    }
+}
+
+//noinspection RsTraitImplementation
+impl GenericPusTcSecondaryHeader for PusTc<'_> {
     delegate!(to self.sec_header {
         fn pus_version(&self) -> PusVersion;
         fn service(&self) -> u8;
         fn subservice(&self) -> u8;
         fn source_id(&self) -> u16;
-        fn ack_flags(&self) -> u8;
+        fn ack_flags(&self) -> AckFlags;
+        fn destination_id(&self) -> Option<u16>;
     });
 }
 
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::ecss::PusVersion::PusC;
-    use crate::ecss::{PusError, PusPacket};
-    use crate::tc::ACK_ALL;
-    use crate::tc::{GenericPusTcSecondaryHeader, PusTc, PusTcSecondaryHeader};
-    use crate::{ByteConversionError, SpHeader};
-    use crate::{CcsdsPacket, SequenceFlags};
+    use crate::ecss::{
+        calc_pus_crc16, test, PusCrcKind, PusError, PusPacket, PusVersion, StdPusError,
+    };
+    use crate::tc::{
+        pus_tc_has_app_data, ACK_ALL, PUC_TC_SECONDARY_HEADER_LEN, PUS_TC_DESTINATION_ID_LEN,
+        PUS_TC_MAX_LEN, PUS_TC_MIN_LEN_WITHOUT_APP_DATA,
+    };
+    use crate::tc::{
+        zc, AckFlags, GenericPusTcSecondaryHeader, PusTc, PusTcBuilder, PusTcHeaderView,
+        PusTcOwned, PusTcParseConfig, PusTcReader, PusTcSecondaryHeader, PusTcSegmentIter,
+        SourceIdWidth,
+    };
+    use crate::{
+        ByteConversionError, SizeMissmatch, SpHeader, SpHeaderError, CCSDS_HEADER_LEN,
+        MAX_SEQ_COUNT,
+    };
+    use crate::{CcsdsPacket, PacketType, SequenceFlags};
+    use alloc::borrow::Cow;
+    use alloc::vec;
     use alloc::vec::Vec;
+    use crc::{Crc, CRC_16_XMODEM};
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::error::Error;
+    use std::hash::{Hash, Hasher};
+    use std::string::ToString;
+    use zerocopy::AsBytes;
 
     fn base_ping_tc_full_ctor() -> PusTc<'static> {
         let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
@@ -527,6 +2155,16 @@ mod tests {
         verify_test_tc(&pus_tc, false, 13);
     }
 
+    #[test]
+    fn test_auto_seg_flags() {
+        let mut sph = SpHeader::tc(0x02, SequenceFlags::FirstSegment, 0x34, 0).unwrap();
+        let tc_header = PusTcSecondaryHeader::new_simple(17, 1);
+        let pus_tc = PusTc::new_auto_seg(&mut sph, tc_header, None, true);
+        assert_eq!(pus_tc.sequence_flags(), SequenceFlags::Unsegmented);
+        assert_eq!(sph.sequence_flags(), SequenceFlags::Unsegmented);
+        verify_test_tc(&pus_tc, false, 13);
+    }
+
     #[test]
     fn test_serialization() {
         let pus_tc = base_ping_tc_simple_ctor();
@@ -560,7 +2198,9 @@ mod tests {
         let mut tc = PusTc::new_simple(&mut sph, 17, 1, None, false);
         tc.calc_crc_on_serialization = false;
         assert_eq!(tc.data_len(), 0);
-        tc.update_packet_fields();
+        assert_eq!(tc.ccsds_data_len_value(), 6);
+        assert_eq!(tc.data_len(), 0);
+        tc.update_packet_fields().unwrap();
         assert_eq!(tc.data_len(), 6);
     }
     #[test]
@@ -582,15 +2222,339 @@ mod tests {
     }
 
     #[test]
-    fn test_vec_ser_deser() {
-        let pus_tc = base_ping_tc_simple_ctor();
-        let mut test_vec = Vec::new();
-        let size = pus_tc
-            .append_to_vec(&mut test_vec)
-            .expect("Error writing TC to vector");
-        assert_eq!(size, 13);
-        verify_test_tc_raw(&test_vec.as_slice());
-        verify_crc_no_app_data(&test_vec.as_slice());
+    fn test_header_view() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        pus_tc
+            .write_to_bytes(test_buf.as_mut_slice())
+            .expect("Error writing TC to buffer");
+        let view = PusTcHeaderView::parse(&test_buf).expect("Parsing header view failed");
+        assert_eq!(view.service(), 17);
+        assert_eq!(view.subservice(), 1);
+        assert_eq!(view.apid(), 0x02);
+        assert_eq!(view.sp_header(), pus_tc.sp_header());
+        let (tc_from_view, size) = view.into_full().expect("Full parsing failed");
+        assert_eq!(size, 16);
+        assert_eq!(tc_from_view, pus_tc);
+    }
+
+    #[test]
+    fn test_header_view_does_not_check_crc() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let mut test_buf: [u8; 32] = [0; 32];
+        pus_tc
+            .write_to_bytes(test_buf.as_mut_slice())
+            .expect("Error writing TC to buffer");
+        test_buf[12] = 0;
+        let view = PusTcHeaderView::parse(&test_buf[0..11]).expect("Parsing header view failed");
+        assert_eq!(view.service(), 17);
+        let view = PusTcHeaderView::parse(&test_buf).expect("Parsing header view failed");
+        let res = view.into_full();
+        assert!(res.is_err());
+        assert!(matches!(res.unwrap_err(), PusError::IncorrectCrc { .. }));
+    }
+
+    #[test]
+    fn test_vec_ser_deser() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let mut test_vec = Vec::new();
+        let size = pus_tc
+            .append_to_vec(&mut test_vec)
+            .expect("Error writing TC to vector");
+        assert_eq!(size, 13);
+        verify_test_tc_raw(&test_vec.as_slice());
+        verify_crc_no_app_data(&test_vec.as_slice());
+    }
+
+    #[test]
+    fn test_vec_ser_returning_crc() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let mut test_vec = Vec::new();
+        let (size, crc16) = pus_tc
+            .append_to_vec_returning_crc(&mut test_vec)
+            .expect("Error writing TC to vector");
+        assert_eq!(size, 13);
+        assert_eq!(crc16, 0xee63);
+        verify_crc_no_app_data(&test_vec.as_slice());
+    }
+
+    #[test]
+    fn test_refresh_from_buffer() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let (tc_from_raw, _) = PusTc::from_bytes(&buf).unwrap();
+        assert_eq!(tc_from_raw.user_data().unwrap(), &[1, 2, 3]);
+        // Modify the application data and the subservice in-place.
+        buf[11] = 4;
+        buf[8] = 2;
+        assert!(PusTc::from_bytes(&buf).is_err());
+        let (refreshed_tc, refreshed_size) =
+            PusTc::refresh_from_buffer(&mut buf, &PusTcParseConfig::default())
+                .expect("Refreshing TC failed");
+        assert_eq!(refreshed_size, size);
+        assert_eq!(refreshed_tc.user_data().unwrap(), &[4, 2, 3]);
+        assert_eq!(PusPacket::subservice(&refreshed_tc), 2);
+        let crc16 = refreshed_tc.crc16().unwrap();
+        assert_eq!(u16::from_be_bytes([buf[size - 2], buf[size - 1]]), crc16);
+        let (tc_from_raw, _) =
+            PusTc::from_bytes(&buf).expect("Creating PUS TC struct from raw buffer failed");
+        assert_eq!(tc_from_raw.crc16(), Some(crc16));
+        assert_eq!(tc_from_raw.user_data().unwrap(), &[4, 2, 3]);
+    }
+
+    #[test]
+    fn test_refresh_from_buffer_invalid_packet() {
+        let mut buf: [u8; 32] = [0; 32];
+        // A zeroed buffer does not contain a telecommand and must not be modified.
+        assert_eq!(
+            PusTc::refresh_from_buffer(&mut buf, &PusTcParseConfig::default()).unwrap_err(),
+            PusError::RawDataTooShort(32)
+        );
+        assert_eq!(buf, [0; 32]);
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let config = PusTcParseConfig {
+            max_len: Some(size - 1),
+            ..Default::default()
+        };
+        buf[11] = 4;
+        let raw_tc = buf;
+        assert!(matches!(
+            PusTc::refresh_from_buffer(&mut buf, &config).unwrap_err(),
+            PusError::PacketTooLarge { .. }
+        ));
+        assert_eq!(buf, raw_tc);
+    }
+
+    #[test]
+    fn test_flat_representation() {
+        let app_data = [1, 2, 3];
+        let mut pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let flat = pus_tc.to_flat();
+        assert_eq!(flat.apid, 0x02);
+        assert_eq!(flat.seq_count, 0x34);
+        assert_eq!(flat.service, 17);
+        assert_eq!(flat.subservice, 1);
+        assert_eq!(flat.source_id, 0);
+        assert_eq!(flat.ack, ACK_ALL.bits());
+        assert!(!flat.crc16_valid);
+        let flat_app_data =
+            unsafe { core::slice::from_raw_parts(flat.app_data_ptr, flat.app_data_len) };
+        assert_eq!(flat_app_data, app_data);
+        pus_tc.calc_own_crc16().unwrap();
+        let flat = pus_tc.to_flat();
+        assert!(flat.crc16_valid);
+        assert_eq!(flat.crc16, pus_tc.crc16().unwrap());
+        let flat = base_ping_tc_simple_ctor().to_flat();
+        assert!(flat.app_data_ptr.is_null());
+        assert_eq!(flat.app_data_len, 0);
+    }
+
+    #[test]
+    fn test_iter_packets_with_ranges() {
+        let mut test_buf: [u8; 64] = [0; 64];
+        let first = base_ping_tc_simple_ctor();
+        let second = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut cursor = first.write_to_bytes_at(&mut test_buf, 0).unwrap();
+        cursor = second.write_to_bytes_at(&mut test_buf, cursor).unwrap();
+        let mut iter = PusTc::iter_packets_with_ranges(&test_buf[0..cursor]);
+        let (pus_tc, range) = iter.next().unwrap();
+        assert_eq!(pus_tc, first);
+        assert_eq!(range, 0..13);
+        let (pus_tc, range) = iter.next().unwrap();
+        assert_eq!(pus_tc, second);
+        assert_eq!(range, 13..29);
+        assert_eq!(pus_tc.raw_bytes().unwrap(), &test_buf[range]);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.offset(), cursor);
+    }
+
+    #[test]
+    fn test_iter_packets_with_ranges_trailing_data() {
+        let mut test_buf: [u8; 64] = [0; 64];
+        let pus_tc = base_ping_tc_simple_ctor();
+        let cursor = pus_tc.write_to_bytes_at(&mut test_buf, 0).unwrap();
+        let mut iter = PusTc::iter_packets_with_ranges(&test_buf[0..cursor + 4]);
+        assert_eq!(iter.next().unwrap().1, 0..13);
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+        assert_eq!(iter.offset(), cursor);
+    }
+
+    #[test]
+    fn test_sp_header_owned() {
+        let sp_header = {
+            let mut test_buf: [u8; 32] = [0; 32];
+            base_ping_tc_simple_ctor()
+                .write_to_bytes(test_buf.as_mut_slice())
+                .unwrap();
+            let (pus_tc, _) = PusTc::from_bytes(&test_buf).unwrap();
+            assert_eq!(pus_tc.sp_header_owned(), *pus_tc.sp_header());
+            pus_tc.sp_header_owned()
+        };
+        assert_eq!(sp_header.apid(), 0x02);
+        assert_eq!(sp_header.seq_count(), 0x34);
+    }
+
+    #[test]
+    fn test_write_to_bytes_at() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let mut test_buf: [u8; 32] = [0; 32];
+        let mut cursor = pus_tc.write_to_bytes_at(&mut test_buf, 2).unwrap();
+        assert_eq!(cursor, 2 + pus_tc.len_packed());
+        cursor = pus_tc.write_to_bytes_at(&mut test_buf, cursor).unwrap();
+        assert_eq!(cursor, 2 + 2 * pus_tc.len_packed());
+        assert_eq!(test_buf[0..2], [0, 0]);
+        let (first, first_len) = PusTc::from_bytes(&test_buf[2..]).unwrap();
+        assert_eq!(first, pus_tc);
+        let (second, _) = PusTc::from_bytes(&test_buf[2 + first_len..]).unwrap();
+        assert_eq!(second, pus_tc);
+    }
+
+    #[test]
+    fn test_write_to_bytes_at_invalid_offset() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let mut test_buf: [u8; 16] = [0; 16];
+        for offset in [4, 17] {
+            let res = pus_tc.write_to_bytes_at(&mut test_buf, offset);
+            assert!(res.is_err());
+            if let PusError::ByteConversionError(ByteConversionError::ToSliceTooSmall(missmatch)) =
+                res.unwrap_err()
+            {
+                assert_eq!(missmatch.expected, offset + pus_tc.len_packed());
+            } else {
+                panic!("unexpected error type");
+            }
+        }
+        assert_eq!(
+            pus_tc
+                .write_to_bytes_at(&mut test_buf, usize::MAX)
+                .unwrap_err(),
+            PusError::ByteConversionError(ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                found: test_buf.len(),
+                expected: usize::MAX,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_offsets() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(test_buf.as_mut_slice()).unwrap();
+        let offsets = PusTc::parse_offsets(&test_buf).unwrap();
+        assert_eq!(offsets.sec_header_start, 6);
+        assert_eq!(offsets.app_data, 11..14);
+        assert_eq!(offsets.crc_pos, 14);
+        assert_eq!(offsets.total_len(), size);
+        assert_eq!(offsets.service(&test_buf), 17);
+        assert_eq!(offsets.subservice(&test_buf), 1);
+        assert_eq!(offsets.source_id(&test_buf), 0);
+        assert_eq!(offsets.app_data(&test_buf), &[1, 2, 3]);
+        assert_eq!(
+            offsets.crc16(&test_buf),
+            calc_pus_crc16(&test_buf[0..size - 2])
+        );
+    }
+
+    #[test]
+    fn test_parse_offsets_no_app_data() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let mut test_buf: [u8; 32] = [0; 32];
+        pus_tc.write_to_bytes(test_buf.as_mut_slice()).unwrap();
+        let offsets = PusTc::parse_offsets(&test_buf).unwrap();
+        assert!(offsets.app_data.is_empty());
+        assert!(offsets.app_data(&test_buf).is_empty());
+        assert_eq!(offsets.total_len(), PUS_TC_MIN_LEN_WITHOUT_APP_DATA);
+    }
+
+    #[test]
+    fn test_parse_offsets_too_short() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(test_buf.as_mut_slice()).unwrap();
+        let res = PusTc::parse_offsets(&test_buf[0..size - 1]);
+        assert!(matches!(res.unwrap_err(), PusError::RawDataTooShort(15)));
+        let res = PusTc::parse_offsets(&test_buf[0..8]);
+        assert!(matches!(res.unwrap_err(), PusError::RawDataTooShort(8)));
+    }
+
+    #[test]
+    fn test_tm_reply_header_for_tc() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let reply_header = SpHeader::tm_reply_for(&pus_tc, 5).unwrap();
+        assert_eq!(reply_header.ptype(), PacketType::Tm);
+        assert_eq!(reply_header.apid(), pus_tc.apid());
+        assert_eq!(reply_header.seq_count(), 5);
+    }
+
+    #[test]
+    fn test_sec_header_round_trip() {
+        let sec_header =
+            PusTcSecondaryHeader::new(17, 1, AckFlags::ACCEPTANCE | AckFlags::PROGRESS, 0x1234);
+        let mut buf: [u8; 8] = [0; 8];
+        let written_len = sec_header.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written_len, PUC_TC_SECONDARY_HEADER_LEN);
+        assert_eq!(buf[0], 0x2a);
+        assert_eq!(buf[1], 17);
+        assert_eq!(buf[2], 1);
+        assert_eq!(u16::from_be_bytes(buf[3..5].try_into().unwrap()), 0x1234);
+        let (read_back, read_len) = PusTcSecondaryHeader::from_bytes(&buf).unwrap();
+        assert_eq!(read_len, PUC_TC_SECONDARY_HEADER_LEN);
+        assert_eq!(read_back, sec_header);
+    }
+
+    #[test]
+    fn test_sec_header_buf_too_small() {
+        let sec_header = PusTcSecondaryHeader::new_simple(17, 1);
+        let mut buf: [u8; 4] = [0; 4];
+        let res = sec_header.write_to_bytes(&mut buf);
+        assert!(res.is_err());
+        if let PusError::ByteConversionError(ByteConversionError::ToSliceTooSmall(missmatch)) =
+            res.unwrap_err()
+        {
+            assert_eq!(missmatch.found, 4);
+            assert_eq!(missmatch.expected, PUC_TC_SECONDARY_HEADER_LEN);
+        } else {
+            panic!("unexpected error type");
+        }
+        let res = PusTcSecondaryHeader::from_bytes(&buf);
+        assert!(matches!(
+            res.unwrap_err(),
+            PusError::ByteConversionError(ByteConversionError::FromSliceTooSmall(_))
+        ));
+    }
+
+    #[test]
+    fn test_sec_header_write_invalid_version() {
+        let mut sec_header = PusTcSecondaryHeader::new_simple(17, 1);
+        sec_header.version = PusVersion::PusA;
+        let mut buf: [u8; 8] = [0; 8];
+        let res = sec_header.write_to_bytes(&mut buf);
+        assert!(matches!(
+            res.unwrap_err(),
+            PusError::VersionNotSupported(PusVersion::PusA)
+        ));
+    }
+
+    #[test]
+    fn test_has_app_data_from_raw() {
+        let mut test_buf: [u8; 32] = [0; 32];
+        base_ping_tc_simple_ctor()
+            .write_to_bytes(test_buf.as_mut_slice())
+            .unwrap();
+        assert!(!pus_tc_has_app_data(&test_buf).unwrap());
+        base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3])
+            .write_to_bytes(test_buf.as_mut_slice())
+            .unwrap();
+        assert!(pus_tc_has_app_data(&test_buf).unwrap());
+        let res = pus_tc_has_app_data(&test_buf[0..4]);
+        assert!(res.is_err());
+        assert!(matches!(
+            res.unwrap_err(),
+            PusError::ByteConversionError(ByteConversionError::FromSliceTooSmall(_))
+        ));
     }
 
     #[test]
@@ -612,7 +2576,7 @@ mod tests {
         let mut pus_tc = base_ping_tc_simple_ctor();
         pus_tc.calc_crc_on_serialization = false;
         let mut test_buf: [u8; 32] = [0; 32];
-        pus_tc.calc_own_crc16();
+        pus_tc.calc_own_crc16().unwrap();
         pus_tc
             .write_to_bytes(test_buf.as_mut_slice())
             .expect("Error writing TC to buffer");
@@ -620,6 +2584,28 @@ mod tests {
         verify_crc_no_app_data(&test_buf);
     }
 
+    #[test]
+    fn test_set_service_and_subservice() {
+        let mut pus_tc = base_ping_tc_simple_ctor();
+        pus_tc.calc_crc_on_serialization = false;
+        pus_tc.calc_own_crc16().unwrap();
+        pus_tc.set_service(200);
+        pus_tc.set_subservice(5);
+        assert_eq!(PusPacket::service(&pus_tc), 200);
+        assert_eq!(PusPacket::subservice(&pus_tc), 5);
+        assert_eq!(pus_tc.crc16(), None);
+        let mut test_buf: [u8; 32] = [0; 32];
+        assert_eq!(
+            pus_tc.write_to_bytes(test_buf.as_mut_slice()).unwrap_err(),
+            PusError::CrcCalculationMissing
+        );
+        pus_tc.calc_own_crc16().unwrap();
+        let size = pus_tc.write_to_bytes(test_buf.as_mut_slice()).unwrap();
+        let (read_tc, _) = PusTc::from_bytes(&test_buf[0..size]).unwrap();
+        assert_eq!(PusPacket::service(&read_tc), 200);
+        assert_eq!(PusPacket::subservice(&read_tc), 5);
+    }
+
     #[test]
     fn test_manual_crc_calculation_no_calc_call() {
         let mut pus_tc = base_ping_tc_simple_ctor();
@@ -684,15 +2670,15 @@ mod tests {
         let mut test_buf: [u8; 32] = [0; 32];
         pus_tc.set_apid(0x7ff);
         pus_tc.set_seq_count(0x3fff);
-        pus_tc.set_ack_field(0b11);
+        pus_tc.set_ack_field(AckFlags::PROGRESS | AckFlags::COMPLETION);
         pus_tc.set_source_id(0xffff);
         pus_tc.set_seq_flags(SequenceFlags::Unsegmented);
         assert_eq!(pus_tc.source_id(), 0xffff);
         assert_eq!(pus_tc.seq_count(), 0x3fff);
-        assert_eq!(pus_tc.ack_flags(), 0b11);
+        assert_eq!(pus_tc.ack_flags().bits(), 0b11);
         assert_eq!(pus_tc.apid(), 0x7ff);
         assert_eq!(pus_tc.sequence_flags(), SequenceFlags::Unsegmented);
-        pus_tc.calc_own_crc16();
+        pus_tc.calc_own_crc16().unwrap();
         pus_tc
             .write_to_bytes(test_buf.as_mut_slice())
             .expect("Error writing TC to buffer");
@@ -773,4 +2759,1149 @@ mod tests {
         pus_tc.write_to_bytes(&mut buf).unwrap();
         assert_eq!(pus_tc, PusTc::from_bytes(&buf).unwrap().0);
     }
+
+    #[test]
+    fn strict_eq_pus_tc() {
+        let mut pus_tc = base_ping_tc_simple_ctor();
+        let mut buf = [0; 32];
+        pus_tc.write_to_bytes(&mut buf).unwrap();
+        let tc_from_raw = PusTc::from_bytes(&buf).unwrap().0;
+        assert!(!pus_tc.strict_eq(&tc_from_raw));
+        pus_tc.calc_own_crc16().unwrap();
+        assert!(pus_tc.strict_eq(&tc_from_raw));
+        let mut other_tc = base_ping_tc_full_ctor();
+        other_tc.calc_own_crc16().unwrap();
+        assert!(pus_tc.strict_eq(&other_tc));
+    }
+
+    #[test]
+    fn test_sec_header_from_typed_subservice() {
+        let sec_header = PusTcSecondaryHeader::new_for(test::Subservice::TcPing, ACK_ALL, 0);
+        assert_eq!(sec_header, PusTcSecondaryHeader::new_simple(17, 1));
+        let sec_header = PusTcSecondaryHeader::new_for((200, 5), ACK_ALL, 0x1234);
+        assert_eq!(sec_header.service, 200);
+        assert_eq!(sec_header.subservice, 5);
+        assert_eq!(sec_header.source_id, 0x1234);
+    }
+
+    #[test]
+    fn test_deserialization_detailed() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        pus_tc
+            .write_to_bytes(test_buf.as_mut_slice())
+            .expect("Error writing TC to buffer");
+        let parsed = PusTc::from_bytes_detailed(&test_buf)
+            .expect("Creating PUS TC struct from raw buffer failed");
+        assert_eq!(parsed.packet, pus_tc);
+        assert_eq!(parsed.consumed, 16);
+        assert_eq!(parsed.trailing, 16);
+    }
+
+    #[test]
+    fn test_zc_sec_header() {
+        let zc_header = zc::PusTcSecondaryHeader::new(17, 1, AckFlags::ACCEPTANCE, 0x1234);
+        assert_eq!(zc_header.pus_version(), PusC);
+        assert_eq!(zc_header.ack_flags(), AckFlags::ACCEPTANCE);
+        assert_eq!(zc_header.service(), 17);
+        assert_eq!(zc_header.subservice(), 1);
+        assert_eq!(zc_header.source_id(), 0x1234);
+        let sec_header = PusTcSecondaryHeader::new(17, 1, AckFlags::ACCEPTANCE, 0x1234);
+        assert_eq!(
+            zc::PusTcSecondaryHeader::try_from(sec_header).unwrap(),
+            zc_header
+        );
+        let mut buf: [u8; PUC_TC_SECONDARY_HEADER_LEN] = [0; PUC_TC_SECONDARY_HEADER_LEN];
+        sec_header.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(zc_header.as_bytes(), buf);
+        let mut zc_buf: [u8; 8] = [0; 8];
+        zc_header.write_to_bytes(&mut zc_buf[1..6]).unwrap();
+        assert_eq!(&zc_buf[1..6], buf);
+        assert!(zc_header.write_to_bytes(&mut zc_buf[0..4]).is_none());
+        assert_eq!(
+            zc::PusTcSecondaryHeader::from_bytes(&zc_buf[1..6]).unwrap(),
+            zc_header
+        );
+        assert!(zc::PusTcSecondaryHeader::from_bytes(&zc_buf[0..4]).is_none());
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut buf: [u8; 32] = [0; 32];
+        pus_tc.write_to_bytes(&mut buf).unwrap();
+        let read_tc = PusTc::try_from(buf.as_slice()).unwrap();
+        assert_eq!(read_tc, pus_tc);
+        let read_tc: PusTc = buf.as_slice().try_into().unwrap();
+        assert_eq!(read_tc.user_data(), Some([1, 2, 3].as_slice()));
+        assert_eq!(
+            PusTc::try_from(&buf[0..4]).unwrap_err(),
+            PusError::RawDataTooShort(4)
+        );
+    }
+
+    #[test]
+    fn test_min_len() {
+        const MIN_LEN: usize = PusTc::min_len();
+        let buf = [0; MIN_LEN];
+        assert_eq!(buf.len(), 13);
+        assert_eq!(base_ping_tc_simple_ctor().len_packed(), MIN_LEN);
+        assert_eq!(
+            base_ping_tc_simple_ctor_with_app_data(&[1, 2]).len_packed(),
+            MIN_LEN + 2
+        );
+    }
+
+    #[test]
+    fn test_deserialization_partial() {
+        let first = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let second = base_ping_tc_simple_ctor();
+        let mut test_buf: [u8; 32] = [0; 32];
+        let first_len = first.write_to_bytes(&mut test_buf).unwrap();
+        let second_len = second.write_to_bytes(&mut test_buf[first_len..]).unwrap();
+        let total_len = first_len + second_len;
+
+        let mut remainder = &test_buf[0..total_len];
+        let mut packets = Vec::new();
+        while !remainder.is_empty() {
+            let (packet, rest) = PusTc::from_bytes_partial(remainder).unwrap();
+            packets.push(packet);
+            remainder = rest;
+        }
+        assert_eq!(packets, [first, second]);
+
+        let (packet, rest) = PusTc::from_bytes_partial(&test_buf).unwrap();
+        assert_eq!(packet, first);
+        assert_eq!(rest.len(), 32 - first_len);
+        assert!(PusTc::from_bytes_partial(&test_buf[first_len..first_len + 4]).is_err());
+    }
+
+    #[test]
+    fn test_calc_crc_invalid_version() {
+        let mut pus_tc = base_ping_tc_simple_ctor();
+        pus_tc.calc_crc_on_serialization = false;
+        pus_tc.sec_header.version = PusVersion::PusA;
+        assert_eq!(
+            pus_tc.calc_own_crc16().unwrap_err(),
+            PusError::VersionNotSupported(PusVersion::PusA)
+        );
+        assert_eq!(
+            pus_tc.update_packet_fields().unwrap_err(),
+            PusError::VersionNotSupported(PusVersion::PusA)
+        );
+        assert!(pus_tc.crc16().is_none());
+    }
+
+    #[test]
+    fn test_invalid_version_round_trip() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        let mut test_buf: [u8; 32] = [0; 32];
+        let size = pus_tc
+            .write_to_bytes(test_buf.as_mut_slice())
+            .expect("Error writing TC to buffer");
+        test_buf[6] = (0b0111 << 4) | ACK_ALL.bits();
+        let crc16 = calc_pus_crc16(&test_buf[0..size - 2]);
+        test_buf[size - 2..size].copy_from_slice(&crc16.to_be_bytes());
+        let (tc_from_raw, _) =
+            PusTc::from_bytes(&test_buf).expect("Creating PUS TC struct from raw buffer failed");
+        assert_eq!(
+            PusPacket::pus_version(&tc_from_raw),
+            PusVersion::Invalid(0b0111)
+        );
+        let mut other_buf: [u8; 32] = [0; 32];
+        let res = tc_from_raw.write_to_bytes(&mut other_buf);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err(),
+            PusError::VersionNotSupported(PusVersion::Invalid(0b0111))
+        );
+    }
+
+    #[test]
+    fn test_invalid_version_no_panic() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut test_buf).unwrap();
+        test_buf[6] = (PusVersion::PusA.raw() << 4) | ACK_ALL.bits();
+        let crc16 = calc_pus_crc16(&test_buf[0..size - 2]);
+        test_buf[size - 2..size].copy_from_slice(&crc16.to_be_bytes());
+
+        let view = PusTcHeaderView::parse(&test_buf).unwrap();
+        assert_eq!(view.sec_header().version, PusVersion::PusA);
+        let reader = PusTcReader::new(&test_buf).unwrap();
+        assert_eq!(reader.pus_version(), PusVersion::PusA);
+        let (mut tc_from_raw, _) = view.into_full().unwrap();
+        assert_eq!(PusPacket::pus_version(&tc_from_raw), PusVersion::PusA);
+
+        let expected_err = PusError::VersionNotSupported(PusVersion::PusA);
+        assert_eq!(
+            tc_from_raw.write_to_bytes(&mut [0; 32]).unwrap_err(),
+            expected_err
+        );
+        assert_eq!(tc_from_raw.to_vec().unwrap_err(), expected_err);
+        let mut writer = Vec::new();
+        match tc_from_raw.write_to_writer(&mut writer).unwrap_err() {
+            StdPusError::PusError(e) => assert_eq!(e, expected_err),
+            e => panic!("unexpected error {e:?}"),
+        }
+        assert!(writer.is_empty());
+        assert_eq!(tc_from_raw.calc_own_crc16().unwrap_err(), expected_err);
+        assert_eq!(tc_from_raw.validate().unwrap_err(), expected_err);
+    }
+
+    #[test]
+    fn test_deserialization_capped() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        pus_tc
+            .write_to_bytes(test_buf.as_mut_slice())
+            .expect("Error writing TC to buffer");
+        let (tc_from_raw, size) = PusTc::from_bytes_capped(&test_buf, 16)
+            .expect("Creating PUS TC struct from raw buffer failed");
+        assert_eq!(size, 16);
+        assert_eq!(tc_from_raw, pus_tc);
+        // Corrupted length field advertising a huge packet
+        test_buf[4] = 0xff;
+        test_buf[5] = 0xff;
+        let res = PusTc::from_bytes_capped(&test_buf, 1024);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: 0xffff + 7,
+                max: 1024
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialization_with_known_len() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        let size = pus_tc
+            .write_to_bytes(test_buf.as_mut_slice())
+            .expect("Error writing TC to buffer");
+        let (tc_from_raw, read_len) = PusTc::from_bytes_with_known_len(&test_buf, size, true)
+            .expect("Creating PUS TC struct from raw buffer failed");
+        assert_eq!(read_len, 16);
+        verify_test_tc(&tc_from_raw, true, 16);
+        assert_eq!(tc_from_raw.user_data().unwrap(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_deserialization_with_known_len_ignores_len_field() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut test_buf: [u8; 32] = [0; 32];
+        let size = pus_tc
+            .write_to_bytes(test_buf.as_mut_slice())
+            .expect("Error writing TC to buffer");
+        // Corrupt the CCSDS data length field and recalculate the CRC16 of the packet.
+        test_buf[5] = 0x20;
+        let crc16 = calc_pus_crc16(&test_buf[0..size - 2]);
+        test_buf[size - 2..size].copy_from_slice(&crc16.to_be_bytes());
+        let (tc_from_raw, read_len) = PusTc::from_bytes_with_known_len(&test_buf, size, false)
+            .expect("Creating PUS TC struct from raw buffer failed");
+        assert_eq!(read_len, 16);
+        assert_eq!(tc_from_raw.user_data().unwrap(), &[1, 2, 3]);
+        let res = PusTc::from_bytes_with_known_len(&test_buf, size, true);
+        assert!(res.is_err());
+        let err = res.unwrap_err();
+        match err {
+            PusError::LengthFieldMismatch(missmatch) => {
+                assert_eq!(missmatch.found, 0x20 + 7);
+                assert_eq!(missmatch.expected, 16);
+            }
+            _ => panic!("unexpected error {err}"),
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let app_data = [1, 2, 3];
+        let pus_tc = PusTcBuilder::new()
+            .apid(0x02)
+            .seq_count(5)
+            .service(17)
+            .subservice(1)
+            .ack(AckFlags::START | AckFlags::COMPLETION)
+            .source_id(3)
+            .app_data(&app_data)
+            .build()
+            .unwrap();
+        let mut sph = SpHeader::tc_unseg(0x02, 5, 0).unwrap();
+        let expected = PusTc::new(
+            &mut sph,
+            PusTcSecondaryHeader::new(17, 1, AckFlags::START | AckFlags::COMPLETION, 3),
+            Some(&app_data),
+            true,
+        );
+        assert_eq!(pus_tc, expected);
+        assert_eq!(pus_tc.data_len(), expected.ccsds_data_len_value());
+        assert_eq!(pus_tc.sequence_flags(), SequenceFlags::Unsegmented);
+        let default_tc = PusTcBuilder::default().build().unwrap();
+        assert_eq!(default_tc.ack_flags(), ACK_ALL);
+        assert_eq!(default_tc.user_data(), None);
+    }
+
+    #[test]
+    fn test_builder_invalid_fields() {
+        assert_eq!(
+            PusTcBuilder::new().apid(0x800).build().unwrap_err(),
+            PusError::SpHeaderError(SpHeaderError::ApidTooLarge(0x800))
+        );
+        let error = PusTcBuilder::new().seq_count(0x4000).build().unwrap_err();
+        assert_eq!(
+            error,
+            PusError::SpHeaderError(SpHeaderError::SeqCountTooLarge(0x4000))
+        );
+        let error = SpHeaderError::SeqCountTooLarge(0x4000);
+        assert_eq!(
+            error.to_string(),
+            "sequence count 16384 exceeds the maximum value 16383"
+        );
+        let app_data = vec![0; PUS_TC_MAX_LEN];
+        assert_eq!(
+            PusTcBuilder::new().app_data(&app_data).build().unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: PUS_TC_MAX_LEN + 13,
+                max: PUS_TC_MAX_LEN
+            }
+        );
+    }
+
+    #[test]
+    fn test_spare_bytes() {
+        let app_data = [1, 2, 3];
+        let spare_bytes = [0, 0];
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let mut pus_tc = PusTc::new_simple(&mut sph, 17, 1, Some(&app_data), true);
+        pus_tc.set_spare_bytes(Some(&spare_bytes));
+        pus_tc.update_ccsds_data_len();
+        assert_eq!(pus_tc.len_packed(), PUS_TC_MIN_LEN_WITHOUT_APP_DATA + 5);
+        let mut buf = [0; 32];
+        let written = pus_tc.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written, pus_tc.len_packed());
+        assert_eq!(buf[11..14], app_data);
+        assert_eq!(buf[14..16], spare_bytes);
+        assert_eq!(calc_pus_crc16(&buf[0..written]), 0);
+        let mut vec = Vec::new();
+        pus_tc.append_to_vec(&mut vec).unwrap();
+        assert_eq!(vec, buf[0..written]);
+
+        let (read_tc, read_len) = PusTc::from_bytes_with_spare_bytes(&buf, 2).unwrap();
+        assert_eq!(read_len, written);
+        assert_eq!(read_tc.user_data(), Some(app_data.as_slice()));
+        assert_eq!(read_tc.spare_bytes(), Some(spare_bytes.as_slice()));
+        assert_eq!(read_tc, pus_tc);
+        // Without the spare byte count, the spare bytes are part of the application data.
+        let (read_tc, _) = PusTc::from_bytes(&buf).unwrap();
+        assert_eq!(read_tc.user_data(), Some(&buf[11..16]));
+        assert_eq!(read_tc.spare_bytes(), None);
+        assert_eq!(
+            PusTc::from_bytes_with_spare_bytes(&buf, 6).unwrap_err(),
+            PusError::RawDataTooShort(32)
+        );
+    }
+
+    fn create_owned_tc() -> PusTcOwned {
+        let app_data = vec![1, 2, 3];
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let pus_tc = PusTc::new_simple(&mut sph, 17, 1, Some(&app_data), true);
+        pus_tc.to_owned()
+    }
+
+    #[test]
+    fn test_owned() {
+        let owned_tc = create_owned_tc();
+        assert_eq!(owned_tc.app_data(), Some([1, 2, 3].as_slice()));
+        assert_eq!(owned_tc.as_pus_tc().apid(), 0x02);
+        let mut buf = [0; 32];
+        let written = owned_tc.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written, owned_tc.len_packed());
+        let mut vec = Vec::new();
+        assert_eq!(owned_tc.append_to_vec(&mut vec).unwrap(), written);
+        assert_eq!(vec, buf[0..written]);
+        let (read_tc, _) = PusTc::from_bytes(&buf).unwrap();
+        assert_eq!(read_tc, owned_tc.as_pus_tc());
+        assert_eq!(read_tc.to_owned(), owned_tc);
+    }
+
+    #[test]
+    fn test_owned_new_and_setters() {
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let mut owned_tc = PusTcOwned::new(&mut sph, PusTcSecondaryHeader::new_simple(17, 1), None);
+        assert_eq!(owned_tc.len_packed(), PUS_TC_MIN_LEN_WITHOUT_APP_DATA);
+        assert_eq!(
+            owned_tc.sp_header().data_len(),
+            owned_tc.as_pus_tc().ccsds_data_len_value()
+        );
+        owned_tc.set_app_data(Some(vec![1, 2]));
+        owned_tc.set_spare_bytes(Some(vec![0]));
+        assert_eq!(owned_tc.spare_bytes(), Some([0].as_slice()));
+        assert_eq!(owned_tc.len_packed(), PUS_TC_MIN_LEN_WITHOUT_APP_DATA + 3);
+        let mut buf = [0; 32];
+        owned_tc.write_to_bytes(&mut buf).unwrap();
+        let (read_tc, _) = PusTc::from_bytes_with_spare_bytes(&buf, 1).unwrap();
+        assert_eq!(read_tc.to_owned(), owned_tc);
+    }
+
+    #[test]
+    fn test_owned_custom_crc_kind() {
+        let mut pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let crc_kind = PusCrcKind::Custom(&CRC_16_XMODEM);
+        pus_tc.set_crc_kind(crc_kind);
+        let mut owned_tc = pus_tc.to_owned();
+        assert_eq!(owned_tc.crc_kind(), crc_kind);
+        assert_eq!(owned_tc.as_pus_tc().crc_kind(), crc_kind);
+        assert_eq!(
+            owned_tc.as_pus_tc().to_vec().unwrap(),
+            pus_tc.to_vec().unwrap()
+        );
+        let mut vec = Vec::new();
+        owned_tc.append_to_vec(&mut vec).unwrap();
+        let (read_tc, _) = PusTc::from_bytes_with_crc_kind(&vec, crc_kind).unwrap();
+        assert_eq!(read_tc.to_owned(), owned_tc);
+        owned_tc.set_crc_kind(PusCrcKind::CcittFalse);
+        assert_ne!(owned_tc.as_pus_tc().to_vec().unwrap(), vec);
+    }
+
+    #[test]
+    fn test_reader() {
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let mut pus_tc = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 3]), true);
+        pus_tc.sec_header.source_id = 0x1234;
+        let mut buf = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let reader = PusTcReader::new(&buf).unwrap();
+        assert_eq!(reader.raw_bytes(), &buf[0..size]);
+        assert_eq!(reader.ccsds_version(), 0);
+        assert_eq!(reader.apid(), 0x02);
+        assert_eq!(reader.seq_count(), 0x34);
+        assert_eq!(reader.ptype(), PacketType::Tc);
+        assert!(reader.sec_header_flag());
+        assert_eq!(reader.data_len(), pus_tc.sp_header.data_len());
+        assert_eq!(reader.pus_version(), PusC);
+        assert_eq!(reader.service(), 17);
+        assert_eq!(reader.subservice(), 1);
+        assert_eq!(reader.source_id(), 0x1234);
+        assert_eq!(reader.ack_flags(), ACK_ALL);
+        assert_eq!(reader.app_data(), Some([1, 2, 3].as_slice()));
+        assert_eq!(
+            reader.crc16(),
+            u16::from_be_bytes([buf[size - 2], buf[size - 1]])
+        );
+        assert!(reader.verify_crc().is_ok());
+        let (read_tc, _) = PusTc::from_bytes(&buf).unwrap();
+        assert_eq!(reader.into_pus_tc().unwrap(), read_tc);
+
+        let ping_tc = base_ping_tc_simple_ctor();
+        let size = ping_tc.write_to_bytes(&mut buf).unwrap();
+        let reader = PusTcReader::new(&buf[0..size]).unwrap();
+        assert_eq!(reader.app_data(), None);
+    }
+
+    #[test]
+    fn test_reader_invalid() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut buf = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let res = PusTcReader::new(&buf[0..size - 1]);
+        assert_eq!(res.unwrap_err(), PusError::RawDataTooShort(size - 1));
+        let res = PusTcReader::new(&buf[0..PUS_TC_MIN_LEN_WITHOUT_APP_DATA - 1]);
+        assert!(res.is_err());
+        buf[size - 3] ^= 0xff;
+        let reader = PusTcReader::new(&buf).unwrap();
+        let crc16 = reader.crc16();
+        assert_eq!(
+            reader.verify_crc().unwrap_err(),
+            PusError::IncorrectCrc(crc16)
+        );
+        assert!(reader.into_pus_tc().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        assert_eq!(
+            pus_tc.to_string(),
+            "PUS TC[apid=0x02, seq=52, 17/1, ack=0xF, app_data=3B]"
+        );
+        let mut sph = SpHeader::tc_unseg(0x7ff, 0, 0).unwrap();
+        let sec_header = PusTcSecondaryHeader::new(200, 5, AckFlags::empty(), 0);
+        let pus_tc = PusTc::new(&mut sph, sec_header, None, true);
+        assert_eq!(
+            pus_tc.to_string(),
+            "PUS TC[apid=0x7ff, seq=0, 200/5, ack=0x0, app_data=0B]"
+        );
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let mut pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let vec = pus_tc.to_vec().unwrap();
+        assert_eq!(vec.len(), pus_tc.len_packed());
+        assert_eq!(vec.capacity(), pus_tc.len_packed());
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(vec, buf[0..size]);
+        pus_tc.calc_crc_on_serialization = false;
+        assert_eq!(
+            pus_tc.to_vec().unwrap_err(),
+            PusError::CrcCalculationMissing
+        );
+        pus_tc.calc_own_crc16().unwrap();
+        assert_eq!(pus_tc.to_vec().unwrap(), vec);
+    }
+
+    #[test]
+    fn test_dedup_key_and_hash() {
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let pus_tc_0 = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 3]), true);
+        let mut sph = SpHeader::tc_unseg(0x02, 0x35, 0).unwrap();
+        let pus_tc_1 = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 3]), true);
+        let mut sph = SpHeader::tc_unseg(0x02, 0x36, 0).unwrap();
+        let pus_tc_2 = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 4]), true);
+        assert_ne!(pus_tc_0, pus_tc_1);
+        assert_eq!(pus_tc_0.dedup_key(), pus_tc_1.dedup_key());
+        assert_ne!(pus_tc_0.dedup_key(), pus_tc_2.dedup_key());
+        let key = pus_tc_0.dedup_key();
+        assert_eq!(key.apid, 0x02);
+        assert_eq!(key.service, 17);
+        assert_eq!(key.subservice, 1);
+        assert_eq!(key.ack, ACK_ALL);
+        assert_eq!(key.source_id, 0);
+        assert_eq!(key.destination_id, None);
+        assert_eq!(key.app_data, Some([1, 2, 3].as_slice()));
+
+        let hash = |pus_tc: &PusTc| {
+            let mut hasher = DefaultHasher::new();
+            pus_tc.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&pus_tc_0), hash(&pus_tc_1));
+        let mut keys = HashSet::new();
+        assert!(keys.insert(pus_tc_0.dedup_key()));
+        assert!(!keys.insert(pus_tc_1.dedup_key()));
+        assert!(keys.insert(pus_tc_2.dedup_key()));
+
+        let builder = PusTcBuilder::new()
+            .service(17)
+            .subservice(1)
+            .app_data(&[1, 2]);
+        let pus_tc_dest_1 = builder.destination_id(1).build().unwrap();
+        let pus_tc_dest_2 = builder.destination_id(2).build().unwrap();
+        assert_ne!(pus_tc_dest_1, pus_tc_dest_2);
+        assert_eq!(pus_tc_dest_1.dedup_key().destination_id, Some(1));
+        assert_ne!(pus_tc_dest_1.dedup_key(), pus_tc_dest_2.dedup_key());
+        assert_ne!(hash(&pus_tc_dest_1), hash(&pus_tc_dest_2));
+        assert!(keys.insert(pus_tc_dest_1.dedup_key()));
+        assert!(keys.insert(pus_tc_dest_2.dedup_key()));
+    }
+
+    #[test]
+    fn test_custom_crc_kind() {
+        let mut pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        assert_eq!(pus_tc.crc_kind(), PusCrcKind::CcittFalse);
+        pus_tc.calc_own_crc16().unwrap();
+        let default_crc16 = pus_tc.crc16().unwrap();
+        let crc_kind = PusCrcKind::Custom(&CRC_16_XMODEM);
+        pus_tc.set_crc_kind(crc_kind);
+        assert_eq!(pus_tc.crc16(), None);
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let crc16 = u16::from_be_bytes([buf[size - 2], buf[size - 1]]);
+        assert_ne!(crc16, default_crc16);
+        assert_eq!(
+            crc16,
+            Crc::<u16>::new(&CRC_16_XMODEM).checksum(&buf[0..size - 2])
+        );
+        assert_eq!(pus_tc.to_vec().unwrap(), buf[0..size]);
+        pus_tc.calc_own_crc16().unwrap();
+        assert_eq!(pus_tc.crc16(), Some(crc16));
+
+        assert_eq!(
+            PusTc::from_bytes(&buf).unwrap_err(),
+            PusError::IncorrectCrc(crc16)
+        );
+        let (read_tc, read_size) = PusTc::from_bytes_with_crc_kind(&buf, crc_kind).unwrap();
+        assert_eq!(read_size, size);
+        assert_eq!(read_tc, pus_tc);
+        assert_eq!(read_tc.crc_kind(), crc_kind);
+        assert_eq!(read_tc.crc16(), Some(crc16));
+    }
+
+    #[test]
+    fn test_from_bytes_with_config() {
+        let app_data = [1, 2, 3];
+        let spare_bytes = [0, 0];
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let mut sec_header = PusTcSecondaryHeader::new(17, 1, ACK_ALL, 0x12);
+        sec_header.source_id_width = SourceIdWidth::OneByte;
+        sec_header.destination_id = Some(0x0506);
+        let crc_kind = PusCrcKind::Custom(&CRC_16_XMODEM);
+        let mut pus_tc = PusTc::new(&mut sph, sec_header, Some(&app_data), true);
+        pus_tc.set_spare_bytes(Some(&spare_bytes));
+        pus_tc.set_crc_kind(crc_kind);
+        pus_tc.update_ccsds_data_len();
+        let mut buf = [0; 32];
+        let written = pus_tc.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(
+            Crc::<u16>::new(&CRC_16_XMODEM).checksum(&buf[0..written]),
+            0
+        );
+
+        let config = PusTcParseConfig {
+            layout: sec_header.layout(),
+            crc_kind,
+            num_spare_bytes: spare_bytes.len(),
+            max_len: Some(written),
+        };
+        let (read_tc, read_len) = PusTc::from_bytes_with_config(&buf, &config).unwrap();
+        assert_eq!(read_len, written);
+        assert_eq!(read_tc, pus_tc);
+        assert_eq!(read_tc.source_id(), 0x12);
+        assert_eq!(read_tc.destination_id(), Some(0x0506));
+        assert_eq!(read_tc.user_data(), Some(app_data.as_slice()));
+        assert_eq!(read_tc.spare_bytes(), Some(spare_bytes.as_slice()));
+        assert_eq!(read_tc.crc_kind(), crc_kind);
+
+        let capped_config = PusTcParseConfig {
+            max_len: Some(written - 1),
+            ..config
+        };
+        assert_eq!(
+            PusTc::from_bytes_with_config(&buf, &capped_config).unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: written,
+                max: written - 1
+            }
+        );
+        let default_crc_config = PusTcParseConfig {
+            crc_kind: PusCrcKind::default(),
+            ..config
+        };
+        assert!(matches!(
+            PusTc::from_bytes_with_config(&buf, &default_crc_config).unwrap_err(),
+            PusError::IncorrectCrc(_)
+        ));
+    }
+
+    #[test]
+    fn test_source_id_width() {
+        for (width, len) in [
+            (SourceIdWidth::None, 3),
+            (SourceIdWidth::OneByte, 4),
+            (SourceIdWidth::TwoBytes, PUC_TC_SECONDARY_HEADER_LEN),
+        ] {
+            assert_eq!(width.num_bytes(), len - 3);
+            assert_eq!(width.sec_header_len(), len);
+            let mut sec_header = PusTcSecondaryHeader::new(17, 1, ACK_ALL, 0x1234);
+            sec_header.source_id_width = width;
+            assert_eq!(sec_header.len_packed(), len);
+            let mut buf: [u8; 8] = [0; 8];
+            assert_eq!(sec_header.write_to_bytes(&mut buf).unwrap(), len);
+            let (read_back, read_len) =
+                PusTcSecondaryHeader::from_bytes_with_source_id_width(&buf, width).unwrap();
+            assert_eq!(read_len, len);
+            assert_eq!(read_back.source_id(), sec_header.source_id());
+
+            let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+            let pus_tc = PusTc::new(&mut sph, sec_header, Some(&[1, 2, 3]), true);
+            assert_eq!(pus_tc.len_packed(), CCSDS_HEADER_LEN + len + 3 + 2);
+            let mut buf: [u8; 32] = [0; 32];
+            let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+            assert_eq!(size, pus_tc.len_packed());
+            assert_eq!(pus_tc.to_vec().unwrap(), buf[0..size]);
+            assert_eq!(
+                &buf[CCSDS_HEADER_LEN + len..CCSDS_HEADER_LEN + len + 3],
+                &[1, 2, 3]
+            );
+            let (read_tc, read_size) = PusTc::from_bytes_with_source_id_width(&buf, width).unwrap();
+            assert_eq!(read_size, size);
+            assert_eq!(read_tc.user_data(), Some([1, 2, 3].as_slice()));
+            assert_eq!(read_tc.source_id(), pus_tc.source_id());
+        }
+        let mut sec_header = PusTcSecondaryHeader::new(17, 1, ACK_ALL, 0x1234);
+        sec_header.source_id_width = SourceIdWidth::None;
+        assert_eq!(sec_header.source_id(), 0);
+        sec_header.source_id_width = SourceIdWidth::OneByte;
+        assert_eq!(sec_header.source_id(), 0x34);
+    }
+
+    #[test]
+    fn test_source_id_exceeding_width() {
+        let mut sec_header = PusTcSecondaryHeader::new(17, 1, ACK_ALL, 0x1234);
+        sec_header.source_id_width = SourceIdWidth::OneByte;
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let pus_tc = PusTc::new(&mut sph, sec_header, Some(&[1, 2]), true);
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let (read_tc, _) =
+            PusTc::from_bytes_with_source_id_width(&buf[0..size], SourceIdWidth::OneByte).unwrap();
+        assert_eq!(read_tc.sec_header.source_id, 0x34);
+        assert_eq!(read_tc, pus_tc);
+        assert_eq!(read_tc.dedup_key(), pus_tc.dedup_key());
+        assert_eq!(pus_tc.dedup_key().source_id, 0x34);
+        assert_eq!(pus_tc.to_flat().source_id, 0x34);
+        let mut other_header = sec_header;
+        other_header.source_id = 0x5634;
+        assert_eq!(other_header, sec_header);
+        other_header.source_id = 0x1235;
+        assert_ne!(other_header, sec_header);
+    }
+
+    #[test]
+    fn test_destination_id() {
+        let pus_tc = base_ping_tc_simple_ctor();
+        assert_eq!(pus_tc.destination_id(), None);
+        assert_eq!(pus_tc.len_packed(), PUS_TC_MIN_LEN_WITHOUT_APP_DATA);
+
+        let pus_tc = PusTcBuilder::new()
+            .apid(0x02)
+            .service(17)
+            .subservice(1)
+            .source_id(0x1234)
+            .destination_id(0x5678)
+            .app_data(&[1, 2, 3])
+            .build()
+            .unwrap();
+        assert_eq!(pus_tc.destination_id(), Some(0x5678));
+        let layout = pus_tc.sec_header.layout();
+        assert!(layout.destination_id);
+        assert_eq!(layout.sec_header_len(), PUC_TC_SECONDARY_HEADER_LEN + 2);
+        assert_eq!(
+            pus_tc.len_packed(),
+            PUS_TC_MIN_LEN_WITHOUT_APP_DATA + PUS_TC_DESTINATION_ID_LEN + 3
+        );
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(size, pus_tc.len_packed());
+        assert_eq!(pus_tc.to_vec().unwrap(), buf[0..size]);
+        assert_eq!(&buf[9..11], &[0x12, 0x34]);
+        assert_eq!(&buf[11..13], &[0x56, 0x78]);
+        assert_eq!(&buf[13..16], &[1, 2, 3]);
+
+        let (read_tc, read_size) = PusTc::from_bytes_with_layout(&buf, layout).unwrap();
+        assert_eq!(read_size, size);
+        assert_eq!(read_tc, pus_tc);
+        assert_eq!(read_tc.source_id(), 0x1234);
+        assert_eq!(read_tc.destination_id(), Some(0x5678));
+        assert_eq!(read_tc.user_data(), Some([1, 2, 3].as_slice()));
+        // Parsed with the default layout, the destination ID is part of the application data.
+        let (read_tc, _) = PusTc::from_bytes(&buf).unwrap();
+        assert_eq!(read_tc.destination_id(), None);
+        assert_eq!(read_tc.user_data(), Some([0x56, 0x78, 1, 2, 3].as_slice()));
+
+        let mut sec_header = PusTcSecondaryHeader::new(17, 1, ACK_ALL, 0x34);
+        sec_header.source_id_width = SourceIdWidth::OneByte;
+        sec_header.destination_id = Some(0x0102);
+        let mut buf: [u8; 8] = [0; 8];
+        assert_eq!(sec_header.write_to_bytes(&mut buf).unwrap(), 6);
+        assert_eq!(&buf[3..6], &[0x34, 0x01, 0x02]);
+        let (read_back, read_len) =
+            PusTcSecondaryHeader::from_bytes_with_layout(&buf, sec_header.layout()).unwrap();
+        assert_eq!(read_len, 6);
+        assert_eq!(read_back, sec_header);
+        assert!(sec_header.write_to_bytes(&mut buf[0..5]).is_err());
+    }
+
+    #[test]
+    fn test_from_bytes_unchecked() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        assert_eq!(pus_tc.verify_crc().unwrap_err(), PusError::NoRawData);
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let (read_tc, read_size) = PusTc::from_bytes_unchecked(&buf).unwrap();
+        assert_eq!(read_size, size);
+        assert!(read_tc.verify_crc().is_ok());
+
+        buf[size - 1] ^= 0xff;
+        let crc16 = u16::from_be_bytes([buf[size - 2], buf[size - 1]]);
+        assert_eq!(
+            PusTc::from_bytes(&buf).unwrap_err(),
+            PusError::IncorrectCrc(crc16)
+        );
+        let (read_tc, read_size) = PusTc::from_bytes_unchecked(&buf).unwrap();
+        assert_eq!(read_size, size);
+        assert_eq!(read_tc, pus_tc);
+        assert_eq!(read_tc.crc16(), Some(crc16));
+        assert_eq!(read_tc.user_data(), Some([1, 2, 3].as_slice()));
+        assert_eq!(
+            read_tc.verify_crc().unwrap_err(),
+            PusError::IncorrectCrc(crc16)
+        );
+        assert_eq!(
+            PusTc::from_bytes_unchecked(&buf[0..size - 1]).unwrap_err(),
+            PusError::RawDataTooShort(size - 1)
+        );
+    }
+
+    #[test]
+    fn test_write_to_writer() {
+        let mut pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut vec = Vec::new();
+        let written = pus_tc.write_to_writer(&mut vec).unwrap();
+        assert_eq!(written, pus_tc.len_packed());
+        assert_eq!(vec, pus_tc.to_vec().unwrap());
+
+        let mut buf: [u8; 8] = [0; 8];
+        let mut too_small: &mut [u8] = &mut buf;
+        let err = pus_tc.write_to_writer(&mut too_small).unwrap_err();
+        assert!(matches!(err, StdPusError::IoError(_)));
+
+        pus_tc.calc_crc_on_serialization = false;
+        let mut vec = Vec::new();
+        let err = pus_tc.write_to_writer(&mut vec).unwrap_err();
+        assert!(matches!(
+            err,
+            StdPusError::PusError(PusError::CrcCalculationMissing)
+        ));
+        assert!(vec.is_empty());
+        pus_tc.calc_own_crc16().unwrap();
+        pus_tc.write_to_writer(&mut vec).unwrap();
+        assert_eq!(vec, pus_tc.to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_validate() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        assert!(pus_tc.validate().is_ok());
+
+        let mut invalid_tc = pus_tc;
+        invalid_tc.sec_header.version = PusVersion::PusA;
+        assert_eq!(
+            invalid_tc.validate().unwrap_err(),
+            PusError::VersionNotSupported(PusVersion::PusA)
+        );
+
+        let app_data = vec![0; u16::MAX as usize];
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let large_tc = PusTc::new_simple(&mut sph, 17, 1, Some(&app_data), false);
+        let max_len = PUS_TC_MAX_LEN;
+        assert_eq!(
+            large_tc.validate().unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: large_tc.len_packed(),
+                max: max_len
+            }
+        );
+        assert_eq!(
+            PusError::PacketTooLarge {
+                advertised: 65548,
+                max: max_len
+            }
+            .to_string(),
+            "packet length 65548 exceeds maximum length 65542"
+        );
+        let sp_header_error = PusError::from(SpHeaderError::ApidTooLarge(0x800));
+        assert_eq!(
+            sp_header_error,
+            PusError::SpHeaderError(SpHeaderError::ApidTooLarge(0x800))
+        );
+        assert!(sp_header_error.source().is_some());
+    }
+
+    #[test]
+    fn test_verify_length_consistency() {
+        let mut pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        assert!(pus_tc.verify_length_consistency().is_ok());
+        pus_tc.set_spare_bytes(Some(&[0, 0]));
+        assert_eq!(
+            pus_tc.verify_length_consistency().unwrap_err(),
+            PusError::LengthFieldMismatch(SizeMissmatch {
+                found: 16,
+                expected: 18
+            })
+        );
+        pus_tc.update_ccsds_data_len();
+        assert!(pus_tc.verify_length_consistency().is_ok());
+
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let pus_tc = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 3]), false);
+        assert_eq!(
+            pus_tc.verify_length_consistency().unwrap_err(),
+            PusError::LengthFieldMismatch(SizeMissmatch {
+                found: CCSDS_HEADER_LEN + 1,
+                expected: 16
+            })
+        );
+        let mut buf: [u8; 32] = [0; 32];
+        base_ping_tc_simple_ctor().write_to_bytes(&mut buf).unwrap();
+        let (read_tc, _) = PusTc::from_bytes(&buf).unwrap();
+        assert!(read_tc.verify_length_consistency().is_ok());
+    }
+
+    #[test]
+    fn test_append_two_tcs_to_vec() {
+        let pus_tc_0 = base_ping_tc_simple_ctor();
+        let mut sph = SpHeader::tc_unseg(0x02, 0x35, 0).unwrap();
+        let pus_tc_1 = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 3]), true);
+        let mut vec = Vec::new();
+        let len_0 = pus_tc_0.append_to_vec(&mut vec).unwrap();
+        let (len_1, crc16) = pus_tc_1.append_to_vec_returning_crc(&mut vec).unwrap();
+        assert_eq!(vec.len(), len_0 + len_1);
+        assert_eq!(crc16, calc_pus_crc16(&vec[len_0..len_0 + len_1 - 2]));
+        let (read_tc_0, read_len_0) = PusTc::from_bytes(&vec).unwrap();
+        assert_eq!(read_len_0, len_0);
+        assert_eq!(read_tc_0, pus_tc_0);
+        let (read_tc_1, read_len_1) = PusTc::from_bytes(&vec[len_0..]).unwrap();
+        assert_eq!(read_len_1, len_1);
+        assert_eq!(read_tc_1, pus_tc_1);
+        assert_eq!(read_tc_1.crc16(), Some(crc16));
+    }
+
+    #[test]
+    fn test_data_len_overflow() {
+        let mut sph = SpHeader::tc_unseg(0x02, 0x34, 0).unwrap();
+        let sec_header = PusTcSecondaryHeader::new_simple(17, 1);
+        let app_data = vec![0; PUS_TC_MAX_LEN - PUS_TC_MIN_LEN_WITHOUT_APP_DATA];
+        let mut pus_tc = PusTc::try_new(&mut sph, sec_header, Some(&app_data)).unwrap();
+        assert_eq!(pus_tc.len_packed(), PUS_TC_MAX_LEN);
+        assert_eq!(pus_tc.sp_header().data_len(), u16::MAX);
+        assert!(pus_tc.try_update_ccsds_data_len().is_ok());
+
+        let app_data = vec![0; PUS_TC_MAX_LEN - PUS_TC_MIN_LEN_WITHOUT_APP_DATA + 1];
+        assert_eq!(
+            PusTc::try_new(&mut sph, sec_header, Some(&app_data)).unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: PUS_TC_MAX_LEN + 1,
+                max: PUS_TC_MAX_LEN
+            }
+        );
+        let mut pus_tc = PusTc::new(&mut sph, sec_header, Some(&app_data), false);
+        assert_eq!(
+            pus_tc.try_update_ccsds_data_len().unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: PUS_TC_MAX_LEN + 1,
+                max: PUS_TC_MAX_LEN
+            }
+        );
+        assert_eq!(pus_tc.sp_header().data_len(), 0);
+        // The unchecked variant truncates the value instead of panicking.
+        pus_tc.update_ccsds_data_len();
+        assert_eq!(pus_tc.sp_header().data_len(), 0);
+    }
+
+    #[test]
+    fn test_segmentation() {
+        let app_data: Vec<u8> = (0..40).collect();
+        let sec_header = PusTcSecondaryHeader::new_simple(6, 1);
+        let max_packet_len = 24;
+        let segments: Vec<Vec<u8>> = PusTcSegmentIter::new(
+            0x02,
+            MAX_SEQ_COUNT - 1,
+            sec_header,
+            &app_data,
+            max_packet_len,
+            PusCrcKind::CcittFalse,
+        )
+        .unwrap()
+        .collect();
+        assert_eq!(segments.len(), 3);
+        let mut reassembled = Vec::new();
+        for (idx, segment) in segments.iter().enumerate() {
+            assert!(segment.len() <= max_packet_len);
+            let (sp_header, _) = SpHeader::from_be_bytes(segment).unwrap();
+            assert_eq!(sp_header.total_len(), segment.len());
+            assert_eq!(sp_header.ptype(), PacketType::Tc);
+            assert_eq!(sp_header.apid(), 0x02);
+            assert_eq!(sp_header.sec_header_flag(), idx == 0);
+            assert_eq!(
+                sp_header.seq_count(),
+                (MAX_SEQ_COUNT - 1 + idx as u16) & MAX_SEQ_COUNT
+            );
+            assert_eq!(calc_pus_crc16(segment), 0);
+            let data_start = match idx {
+                0 => CCSDS_HEADER_LEN + PUC_TC_SECONDARY_HEADER_LEN,
+                _ => CCSDS_HEADER_LEN,
+            };
+            reassembled.extend_from_slice(&segment[data_start..segment.len() - 2]);
+        }
+        assert_eq!(reassembled, app_data);
+        assert_eq!(
+            SpHeader::from_be_bytes(&segments[0])
+                .unwrap()
+                .0
+                .sequence_flags(),
+            SequenceFlags::FirstSegment
+        );
+        assert_eq!(
+            SpHeader::from_be_bytes(&segments[1])
+                .unwrap()
+                .0
+                .sequence_flags(),
+            SequenceFlags::ContinuationSegment
+        );
+        assert_eq!(
+            SpHeader::from_be_bytes(&segments[2])
+                .unwrap()
+                .0
+                .sequence_flags(),
+            SequenceFlags::LastSegment
+        );
+        let (first_tc, _) = PusTc::from_bytes(&segments[0]).unwrap();
+        assert_eq!(PusPacket::service(&first_tc), 6);
+        assert_eq!(first_tc.user_data(), Some(&app_data[0..11]));
+
+        let mut single = PusTcSegmentIter::new(
+            0x02,
+            0,
+            sec_header,
+            &app_data[0..3],
+            24,
+            PusCrcKind::CcittFalse,
+        )
+        .unwrap();
+        let single_packet = single.next().unwrap();
+        let (single_tc, _) = PusTc::from_bytes(&single_packet).unwrap();
+        assert_eq!(single_tc.sequence_flags(), SequenceFlags::Unsegmented);
+        assert_eq!(single_tc.user_data(), Some(&app_data[0..3]));
+        assert!(single.next().is_none());
+    }
+
+    #[test]
+    fn test_segmentation_custom_crc_and_layout() {
+        let app_data: Vec<u8> = (0..20).collect();
+        let mut sec_header = PusTcSecondaryHeader::new(6, 1, ACK_ALL, 0x34);
+        sec_header.source_id_width = SourceIdWidth::OneByte;
+        sec_header.destination_id = Some(0x0102);
+        let crc_kind = PusCrcKind::Custom(&CRC_16_XMODEM);
+        let segments: Vec<Vec<u8>> =
+            PusTcSegmentIter::new(0x02, 0, sec_header, &app_data, 24, crc_kind)
+                .unwrap()
+                .collect();
+        assert_eq!(segments.len(), 2);
+        let crc = Crc::<u16>::new(&CRC_16_XMODEM);
+        for segment in &segments {
+            assert_eq!(crc.checksum(segment), 0);
+        }
+        let (first_tc, _) = PusTc::from_bytes_with_config(
+            &segments[0],
+            &PusTcParseConfig {
+                layout: sec_header.layout(),
+                crc_kind,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(first_tc.sec_header, sec_header);
+        let first_len = 24 - CCSDS_HEADER_LEN - sec_header.len_packed() - 2;
+        assert_eq!(first_tc.user_data(), Some(&app_data[0..first_len]));
+        assert_eq!(
+            &segments[1][CCSDS_HEADER_LEN..segments[1].len() - 2],
+            &app_data[first_len..]
+        );
+    }
+
+    #[test]
+    fn test_segmentation_invalid_params() {
+        let sec_header = PusTcSecondaryHeader::new_simple(6, 1);
+        let crc_kind = PusCrcKind::CcittFalse;
+        assert_eq!(
+            PusTcSegmentIter::new(0x800, 0, sec_header, &[], 24, crc_kind).unwrap_err(),
+            PusError::SpHeaderError(SpHeaderError::ApidTooLarge(0x800))
+        );
+        assert_eq!(
+            PusTcSegmentIter::new(0x02, 0, sec_header, &[], PUS_TC_MAX_LEN + 1, crc_kind)
+                .unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: PUS_TC_MAX_LEN + 1,
+                max: PUS_TC_MAX_LEN
+            }
+        );
+        assert_eq!(
+            PusTcSegmentIter::new(
+                0x02,
+                0,
+                sec_header,
+                &[],
+                PUS_TC_MIN_LEN_WITHOUT_APP_DATA,
+                crc_kind,
+            )
+            .unwrap_err(),
+            PusError::ByteConversionError(ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                found: PUS_TC_MIN_LEN_WITHOUT_APP_DATA,
+                expected: PUS_TC_MIN_LEN_WITHOUT_APP_DATA + 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_raw_forwarding() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let serialized = pus_tc.as_raw_or_serialize().unwrap();
+        assert!(matches!(serialized, Cow::Owned(_)));
+        assert_eq!(serialized.as_ref(), pus_tc.to_vec().unwrap().as_slice());
+
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tc.write_to_bytes(&mut buf).unwrap();
+        let (read_tc, _) = PusTc::from_bytes(&buf).unwrap();
+        assert_eq!(read_tc.raw_bytes(), Some(&buf[0..size]));
+        let raw = read_tc.as_raw_or_serialize().unwrap();
+        assert!(matches!(raw, Cow::Borrowed(_)));
+        assert_eq!(raw.as_ref(), &buf[0..size]);
+    }
+
+    #[test]
+    fn test_raw_forwarding_after_modification() {
+        let pus_tc = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut buf: [u8; 32] = [0; 32];
+        pus_tc.write_to_bytes(&mut buf).unwrap();
+        let (read_tc, _) = PusTc::from_bytes(&buf).unwrap();
+        let modifications: [fn(&mut PusTc); 9] = [
+            |tc| tc.set_service(5),
+            |tc| tc.set_subservice(2),
+            |tc| tc.set_ack_field(AckFlags::empty()),
+            |tc| tc.set_source_id(5),
+            |tc| tc.set_crc_kind(PusCrcKind::Custom(&CRC_16_XMODEM)),
+            |tc| tc.set_spare_bytes(Some(&[0, 0])),
+            |tc| assert!(tc.set_apid(0x03)),
+            |tc| assert!(tc.set_seq_count(0x35)),
+            |tc| tc.set_seq_flags(SequenceFlags::FirstSegment),
+        ];
+        for modify in modifications {
+            let mut modified_tc = read_tc;
+            modify(&mut modified_tc);
+            modified_tc.update_packet_fields().unwrap();
+            assert_eq!(modified_tc.raw_bytes(), None);
+            let forwarded = modified_tc.as_raw_or_serialize().unwrap();
+            assert!(matches!(forwarded, Cow::Owned(_)));
+            assert_ne!(forwarded.as_ref(), &buf[0..pus_tc.len_packed()]);
+            assert_eq!(forwarded.as_ref(), modified_tc.to_vec().unwrap().as_slice());
+        }
+    }
+
+    #[test]
+    fn test_eq_ignoring_seq_count() {
+        let pus_tc_0 = base_ping_tc_simple_ctor_with_app_data(&[1, 2, 3]);
+        let mut pus_tc_1 = pus_tc_0;
+        pus_tc_1.set_seq_count(0x35);
+        pus_tc_1.calc_own_crc16().unwrap();
+        assert_ne!(pus_tc_0, pus_tc_1);
+        assert!(pus_tc_0.eq_ignoring_seq_count(&pus_tc_1));
+        assert!(pus_tc_1.eq_ignoring_seq_count(&pus_tc_0));
+        pus_tc_1.set_apid(0x03);
+        assert!(!pus_tc_0.eq_ignoring_seq_count(&pus_tc_1));
+        let mut sph = SpHeader::tc_unseg(0x02, 0x36, 0).unwrap();
+        let pus_tc_2 = PusTc::new_simple(&mut sph, 17, 1, Some(&[1, 2, 4]), true);
+        assert!(!pus_tc_0.eq_ignoring_seq_count(&pus_tc_2));
+    }
+
+    #[test]
+    fn test_ack_flags() {
+        let ack = AckFlags::ACCEPTANCE | AckFlags::COMPLETION;
+        assert_eq!(u8::from(ack), 0b1001);
+        assert!(ack.contains(AckFlags::ACCEPTANCE));
+        assert!(!ack.contains(AckFlags::START));
+        assert_eq!(ack & AckFlags::COMPLETION, AckFlags::COMPLETION);
+        assert_eq!(AckFlags::try_from(0b1001), Ok(ack));
+        assert_eq!(AckFlags::try_from(0b10000), Err(0b10000));
+        assert_eq!(AckFlags::from_bits_truncate(0xf1), AckFlags::COMPLETION);
+        let mut all = AckFlags::empty();
+        assert!(all.is_empty());
+        all |= AckFlags::ACCEPTANCE | AckFlags::START;
+        all |= AckFlags::PROGRESS | AckFlags::COMPLETION;
+        assert_eq!(all, ACK_ALL);
+        assert_eq!(AckFlags::default(), AckFlags::empty());
+    }
 }
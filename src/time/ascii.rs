@@ -66,7 +66,7 @@ pub fn generate_time_code_b_terminated(
     date.format(FMT_STR_CODE_B_TERMINATED_WITH_SIZE.0)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use chrono::Utc;
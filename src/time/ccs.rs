@@ -0,0 +1,600 @@
+//! Module to generate or read CCSDS Calendar Segmented (CCS) timestamps as specified in
+//! [CCSDS 301.0-B-4](https://public.ccsds.org/Pubs/301x0b4e1.pdf) section 3.4 .
+//!
+//! All calendar fields of the T-field are encoded as binary coded decimals (BCD) as specified
+//! in the standard. The core data structure to do this is the [TimeProvider] struct.
+use super::*;
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDate, Timelike};
+
+/// Base value for the preamble field for a time field parser to determine the time field type.
+pub const P_FIELD_BASE: u8 = (CcsdsTimeCodes::Ccs as u8) << 4;
+/// Length of the p-field and the calendar fields without the subsecond octets.
+pub const MIN_CCS_LEN: usize = 8;
+/// Maximum number of subsecond octets. Each octet contains two decimal digits.
+pub const MAX_SUBSEC_OCTETS: u8 = 6;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CcsError {
+    /// The year, month and day combination is not a valid calendar date.
+    InvalidDate(u16, u8, u8),
+    /// The hour, minute and second combination is not a valid time of day.
+    InvalidTimeOfDay(u8, u8, u8),
+    /// The number of subsecond octets exceeds [MAX_SUBSEC_OCTETS] or the subsecond value does
+    /// not fit into the number of octets.
+    InvalidSubseconds(u8, u64),
+    /// The year and day of year combination is not valid.
+    InvalidDayOfYear(u16, u16),
+    /// A raw byte is not a valid BCD value.
+    InvalidBcd(u8),
+}
+
+impl Display for CcsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CcsError::InvalidDate(year, month, day) => {
+                write!(f, "invalid ccs date {year:04}-{month:02}-{day:02}")
+            }
+            CcsError::InvalidTimeOfDay(hour, minute, second) => {
+                write!(
+                    f,
+                    "invalid ccs time of day {hour:02}:{minute:02}:{second:02}"
+                )
+            }
+            CcsError::InvalidSubseconds(octets, value) => {
+                write!(f, "invalid ccs subseconds {value} for {octets} octets")
+            }
+            CcsError::InvalidBcd(raw) => {
+                write!(f, "invalid bcd value {raw:#04x}")
+            }
+            CcsError::InvalidDayOfYear(year, day_of_year) => {
+                write!(f, "invalid ccs day of year {day_of_year} for year {year}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CcsError {}
+
+#[inline]
+const fn to_bcd(val: u8) -> u8 {
+    ((val / 10) << 4) | (val % 10)
+}
+
+#[inline]
+fn from_bcd(raw: u8) -> Result<u8, CcsError> {
+    let (high, low) = (raw >> 4, raw & 0b1111);
+    if high > 9 || low > 9 {
+        return Err(CcsError::InvalidBcd(raw));
+    }
+    Ok(high * 10 + low)
+}
+
+/// CCS timestamp provider.
+///
+/// The calendar date can either be represented as month and day of month, or as day of year
+/// if the provider was created with [TimeProvider::new_doy]. The representation is selected
+/// with the calendar variation flag of the p-field.
+///
+/// The subseconds are stored as a decimal value with two digits per octet, so one subsecond
+/// octet has a resolution of 10 ms and six octets have a resolution of 1 ps.
+///
+/// # Example
+///
+/// ```
+/// use spacepackets::time::ccs::TimeProvider;
+/// use spacepackets::time::{CcsdsTimeCodes, CcsdsTimeProvider, TimeReader, TimeWriter};
+///
+/// let mut stamp = TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+/// // Millisecond resolution: two octets with two decimal digits each.
+/// stamp.set_subseconds(2, 1230).unwrap();
+/// let mut raw_stamp: [u8; 16] = [0; 16];
+/// let written = stamp.write_to_bytes(&mut raw_stamp).unwrap();
+/// assert_eq!(written, 10);
+/// assert_eq!((raw_stamp[0] >> 4) & 0b111, CcsdsTimeCodes::Ccs as u8);
+/// let read_back = TimeProvider::from_bytes(&raw_stamp).unwrap();
+/// assert_eq!(read_back, stamp);
+/// assert_eq!(read_back.subsecond_millis(), Some(123));
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimeProvider {
+    pfield: u8,
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    subsec_octets: u8,
+    subsec_value: u64,
+}
+
+impl TimeProvider {
+    /// Length of a timestamp without sub-second octets.
+    pub const MIN_LEN: usize = MIN_CCS_LEN;
+    /// Length of a timestamp with the maximum supported number of sub-second octets.
+    pub const MAX_LEN: usize = MIN_CCS_LEN + MAX_SUBSEC_OCTETS as usize;
+
+    /// Create a new timestamp without a subsecond part.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns [CcsError::InvalidDate] if the date is not a valid calendar date
+    /// between the years 0 and 9999 and [CcsError::InvalidTimeOfDay] if the hour, minute or
+    /// second value is invalid. Leap seconds are not supported.
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Self, CcsError> {
+        let valid_date = matches!(
+            days_in_month(year as i32, month as u32),
+            Some(days) if day >= 1 && day as u32 <= days
+        );
+        if year > 9999 || !valid_date {
+            return Err(CcsError::InvalidDate(year, month, day));
+        }
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(CcsError::InvalidTimeOfDay(hour, minute, second));
+        }
+        Ok(Self {
+            pfield: P_FIELD_BASE,
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            subsec_octets: 0,
+            subsec_value: 0,
+        })
+    }
+
+    /// Create a new timestamp using the day of year calendar variation, where the day of year
+    /// starts at 1 for January 1.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns [CcsError::InvalidDayOfYear] if the day of year is not valid for
+    /// the given year and [CcsError::InvalidTimeOfDay] if the hour, minute or second value is
+    /// invalid.
+    pub fn new_doy(
+        year: u16,
+        day_of_year: u16,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Self, CcsError> {
+        if year > 9999 || day_of_year == 0 || day_of_year as u32 > days_in_year(year as i32) {
+            return Err(CcsError::InvalidDayOfYear(year, day_of_year));
+        }
+        let (_, month, day) =
+            civil_from_unix_days(unix_days_from_civil(year as i32, 1, 1) + day_of_year as i64 - 1);
+        let mut provider = Self::new(year, month as u8, day as u8, hour, minute, second)?;
+        provider.pfield |= 1 << 3;
+        Ok(provider)
+    }
+
+    /// Create a timestamp from a [`DateTime<Utc>`] with the given number of subsecond octets.
+    /// The subsecond value is truncated to the resolution of the subsecond octets.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    pub fn from_date_time(dt: &DateTime<Utc>, subsec_octets: u8) -> Result<Self, TimestampError> {
+        if dt.year() < 0 || dt.year() > 9999 {
+            return Err(
+                CcsError::InvalidDate(dt.year() as u16, dt.month() as u8, dt.day() as u8).into(),
+            );
+        }
+        let mut provider = Self::new(
+            dt.year() as u16,
+            dt.month() as u8,
+            dt.day() as u8,
+            dt.hour() as u8,
+            dt.minute() as u8,
+            dt.second() as u8,
+        )?;
+        let subsec_value = subsec_value_from_nanos(dt.nanosecond() as u64, subsec_octets)?;
+        provider.set_subseconds(subsec_octets, subsec_value)?;
+        Ok(provider)
+    }
+
+    /// Like [Self::from_date_time] but with a [time::OffsetDateTime](::time::OffsetDateTime).
+    #[cfg(all(feature = "time", feature = "chrono"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "time", feature = "chrono"))))]
+    pub fn from_offset_date_time(
+        dt: &::time::OffsetDateTime,
+        subsec_octets: u8,
+    ) -> Result<Self, TimestampError> {
+        Self::from_date_time(&date_time_from_offset_date_time(dt), subsec_octets)
+    }
+
+    /// Set the subsecond part of the timestamp. Each octet contains two decimal digits of the
+    /// subsecond value, so the value needs to be smaller than 100 to the power of the number of
+    /// octets. For example, `set_subseconds(2, 1230)` sets the subseconds to 0.123 seconds.
+    pub fn set_subseconds(&mut self, octets: u8, value: u64) -> Result<(), CcsError> {
+        if octets > MAX_SUBSEC_OCTETS || value >= 100_u64.pow(octets as u32) {
+            return Err(CcsError::InvalidSubseconds(octets, value));
+        }
+        self.subsec_octets = octets;
+        self.subsec_value = value;
+        self.pfield = (self.pfield & !0b111) | octets;
+        Ok(())
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Day of year, starting at 1 for January 1.
+    pub fn day_of_year(&self) -> u16 {
+        (unix_days_from_civil(self.year as i32, self.month as u32, self.day as u32)
+            - unix_days_from_civil(self.year as i32, 1, 1)
+            + 1) as u16
+    }
+
+    /// Returns [true] if the day of year calendar variation is used.
+    pub fn is_day_of_year_variant(&self) -> bool {
+        (self.pfield >> 3) & 0b1 == 1
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Returns a tuple containing the number of subsecond octets and the subsecond value.
+    pub fn subseconds(&self) -> (u8, u64) {
+        (self.subsec_octets, self.subsec_value)
+    }
+
+    fn subsec_nanos(&self) -> u32 {
+        let digits = 2 * self.subsec_octets as u32;
+        if digits <= 9 {
+            return (self.subsec_value * 10_u64.pow(9 - digits)) as u32;
+        }
+        (self.subsec_value / 10_u64.pow(digits - 9)) as u32
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn subsec_value_from_nanos(nanos: u64, octets: u8) -> Result<u64, CcsError> {
+    if octets > MAX_SUBSEC_OCTETS {
+        return Err(CcsError::InvalidSubseconds(octets, 0));
+    }
+    let digits = 2 * octets as u32;
+    if digits <= 9 {
+        return Ok(nanos / 10_u64.pow(9 - digits));
+    }
+    Ok(nanos * 10_u64.pow(digits - 9))
+}
+
+impl TimeWriter for TimeProvider {
+    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
+        if bytes.len() < self.len_as_bytes() {
+            return Err(TimestampError::ByteConversionError(
+                ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                    found: bytes.len(),
+                    expected: self.len_as_bytes(),
+                }),
+            ));
+        }
+        bytes[0] = self.pfield;
+        bytes[1] = to_bcd((self.year / 100) as u8);
+        bytes[2] = to_bcd((self.year % 100) as u8);
+        if self.is_day_of_year_variant() {
+            let day_of_year = self.day_of_year();
+            bytes[3] = to_bcd((day_of_year / 100) as u8);
+            bytes[4] = to_bcd((day_of_year % 100) as u8);
+        } else {
+            bytes[3] = to_bcd(self.month);
+            bytes[4] = to_bcd(self.day);
+        }
+        bytes[5] = to_bcd(self.hour);
+        bytes[6] = to_bcd(self.minute);
+        bytes[7] = to_bcd(self.second);
+        let mut subsec_value = self.subsec_value;
+        for idx in (0..self.subsec_octets as usize).rev() {
+            bytes[MIN_CCS_LEN + idx] = to_bcd((subsec_value % 100) as u8);
+            subsec_value /= 100;
+        }
+        Ok(self.len_as_bytes())
+    }
+}
+
+impl TimeReader for TimeProvider {
+    fn from_bytes(buf: &[u8]) -> Result<Self, TimestampError>
+    where
+        Self: Sized,
+    {
+        if buf.len() < MIN_CCS_LEN {
+            return Err(TimestampError::ByteConversionError(
+                ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                    expected: MIN_CCS_LEN,
+                    found: buf.len(),
+                }),
+            ));
+        }
+        match ccsds_time_code_from_p_field(buf[0]) {
+            Ok(CcsdsTimeCodes::Ccs) => (),
+            Ok(code) => {
+                return Err(TimestampError::InvalidTimeCode(
+                    CcsdsTimeCodes::Ccs,
+                    code as u8,
+                ))
+            }
+            Err(raw) => return Err(TimestampError::InvalidTimeCode(CcsdsTimeCodes::Ccs, raw)),
+        }
+        let subsec_octets = buf[0] & 0b111;
+        if subsec_octets > MAX_SUBSEC_OCTETS {
+            return Err(CcsError::InvalidSubseconds(subsec_octets, 0).into());
+        }
+        let total_len = MIN_CCS_LEN + subsec_octets as usize;
+        if buf.len() < total_len {
+            return Err(TimestampError::ByteConversionError(
+                ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                    expected: total_len,
+                    found: buf.len(),
+                }),
+            ));
+        }
+        let year = from_bcd(buf[1])? as u16 * 100 + from_bcd(buf[2])? as u16;
+        let (hour, minute, second) = (from_bcd(buf[5])?, from_bcd(buf[6])?, from_bcd(buf[7])?);
+        let mut provider = if (buf[0] >> 3) & 0b1 == 1 {
+            let day_of_year = from_bcd(buf[3])? as u16 * 100 + from_bcd(buf[4])? as u16;
+            Self::new_doy(year, day_of_year, hour, minute, second)?
+        } else {
+            Self::new(
+                year,
+                from_bcd(buf[3])?,
+                from_bcd(buf[4])?,
+                hour,
+                minute,
+                second,
+            )?
+        };
+        let mut subsec_value: u64 = 0;
+        for raw in &buf[MIN_CCS_LEN..total_len] {
+            subsec_value = subsec_value * 100 + from_bcd(*raw)? as u64;
+        }
+        provider.set_subseconds(subsec_octets, subsec_value)?;
+        Ok(provider)
+    }
+}
+
+impl CcsdsTimeProvider for TimeProvider {
+    fn len_as_bytes(&self) -> usize {
+        MIN_CCS_LEN + self.subsec_octets as usize
+    }
+
+    fn p_field(&self) -> (usize, [u8; 2]) {
+        (1, [self.pfield, 0])
+    }
+
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
+        CcsdsTimeCodes::Ccs
+    }
+
+    fn unix_seconds(&self) -> i64 {
+        unix_days_from_civil(self.year as i32, self.month as u32, self.day as u32)
+            * SECONDS_PER_DAY as i64
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64
+    }
+
+    fn subsecond_millis(&self) -> Option<u16> {
+        if self.subsec_octets == 0 {
+            return None;
+        }
+        Some((self.subsec_nanos() / 10_u32.pow(6)) as u16)
+    }
+
+    fn unix_nanos(&self) -> i128 {
+        self.unix_seconds() as i128 * 10_i128.pow(9) + self.subsec_nanos() as i128
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        let naive = NaiveDate::from_ymd_opt(self.year as i32, self.month as u32, self.day as u32)?
+            .and_hms_nano_opt(
+                self.hour as u32,
+                self.minute as u32,
+                self.second as u32,
+                self.subsec_nanos(),
+            )?;
+        Some(Utc.from_utc_datetime(&naive))
+    }
+
+    #[cfg(feature = "chrono")]
+    fn iso8601_seconds_format(&self) -> SecondsFormat {
+        match self.subsec_octets {
+            0 => SecondsFormat::Secs,
+            1 => SecondsFormat::Millis,
+            2 | 3 => SecondsFormat::Micros,
+            _ => SecondsFormat::Nanos,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_basic() {
+        let stamp = TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        assert_eq!(stamp.len_as_bytes(), 8);
+        assert_eq!(stamp.ccsds_time_code(), CcsdsTimeCodes::Ccs);
+        assert_eq!(stamp.p_field(), (1, [0b0101_0000, 0]));
+        assert_eq!(stamp.unix_seconds(), 1673714970);
+        assert!(stamp.subsecond_millis().is_none());
+        let dt = stamp.date_time().unwrap();
+        assert_eq!(dt.year(), 2023);
+        assert_eq!(dt.month(), 1);
+        assert_eq!(dt.day(), 14);
+        assert_eq!(dt.hour(), 16);
+        assert_eq!(dt.minute(), 49);
+        assert_eq!(dt.second(), 30);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_write_bcd() {
+        let mut stamp = TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        stamp.set_subseconds(3, 123456).unwrap();
+        let mut buf: [u8; 16] = [0; 16];
+        let written = stamp.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written, 11);
+        assert_eq!(buf[0], 0b0101_0011);
+        assert_eq!(
+            buf[1..11],
+            [0x20, 0x23, 0x01, 0x14, 0x16, 0x49, 0x30, 0x12, 0x34, 0x56]
+        );
+        let read_back = TimeProvider::from_bytes(&buf).unwrap();
+        assert_eq!(read_back, stamp);
+        assert_eq!(read_back.subseconds(), (3, 123456));
+        assert_eq!(read_back.subsecond_millis(), Some(123));
+        assert_eq!(read_back.date_time().unwrap().nanosecond(), 123_456_000);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_date_time() {
+        let dt = Utc.timestamp_opt(1673714970, 987_654_321).unwrap();
+        let stamp = TimeProvider::from_date_time(&dt, 6).unwrap();
+        assert_eq!(stamp.subseconds(), (6, 987_654_321_000));
+        assert_eq!(stamp.date_time().unwrap(), dt);
+        let stamp = TimeProvider::from_date_time(&dt, 1).unwrap();
+        assert_eq!(stamp.subseconds(), (1, 98));
+        assert_eq!(stamp.subsecond_millis(), Some(980));
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        assert_eq!(
+            TimeProvider::new(2023, 2, 29, 0, 0, 0).unwrap_err(),
+            CcsError::InvalidDate(2023, 2, 29)
+        );
+        assert!(TimeProvider::new(2024, 2, 29, 0, 0, 0).is_ok());
+        assert_eq!(
+            TimeProvider::new(2023, 1, 1, 24, 0, 0).unwrap_err(),
+            CcsError::InvalidTimeOfDay(24, 0, 0)
+        );
+        let mut stamp = TimeProvider::new(2023, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            stamp.set_subseconds(1, 100).unwrap_err(),
+            CcsError::InvalidSubseconds(1, 100)
+        );
+        assert_eq!(
+            stamp.set_subseconds(7, 0).unwrap_err(),
+            CcsError::InvalidSubseconds(7, 0)
+        );
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let stamp = TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        let mut buf: [u8; 16] = [0; 16];
+        stamp.write_to_bytes(&mut buf).unwrap();
+        let res = TimeProvider::from_bytes(&buf[0..7]);
+        assert!(matches!(
+            res.unwrap_err(),
+            TimestampError::ByteConversionError(ByteConversionError::FromSliceTooSmall(_))
+        ));
+        let mut invalid_bcd = buf;
+        invalid_bcd[3] = 0x1a;
+        assert_eq!(
+            TimeProvider::from_bytes(&invalid_bcd).unwrap_err(),
+            TimestampError::CcsError(CcsError::InvalidBcd(0x1a))
+        );
+        let mut invalid_code = buf;
+        invalid_code[0] = (CcsdsTimeCodes::Cds as u8) << 4;
+        assert_eq!(
+            TimeProvider::from_bytes(&invalid_code).unwrap_err(),
+            TimestampError::InvalidTimeCode(CcsdsTimeCodes::Ccs, CcsdsTimeCodes::Cds as u8)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_day_of_year() {
+        let mut stamp = TimeProvider::new_doy(2024, 366, 23, 59, 59).unwrap();
+        assert!(stamp.is_day_of_year_variant());
+        assert_eq!(stamp.p_field(), (1, [0b0101_1000, 0]));
+        assert_eq!(stamp.day_of_year(), 366);
+        assert_eq!(stamp.month(), 12);
+        assert_eq!(stamp.day(), 31);
+        stamp.set_subseconds(1, 50).unwrap();
+        let dt = stamp.date_time().unwrap();
+        assert_eq!(dt.ordinal(), 366);
+        assert_eq!(dt.month(), 12);
+        assert_eq!(dt.day(), 31);
+        assert_eq!(dt.nanosecond(), 500_000_000);
+
+        let mut buf: [u8; 16] = [0; 16];
+        let written = stamp.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written, 9);
+        assert_eq!(buf[0], 0b0101_1001);
+        assert_eq!(buf[1..9], [0x20, 0x24, 0x03, 0x66, 0x23, 0x59, 0x59, 0x50]);
+        let read_back = TimeProvider::from_bytes(&buf).unwrap();
+        assert_eq!(read_back, stamp);
+        assert!(read_back.is_day_of_year_variant());
+        assert_eq!(read_back.day_of_year(), 366);
+    }
+
+    #[test]
+    fn test_invalid_day_of_year() {
+        assert_eq!(
+            TimeProvider::new_doy(2023, 366, 0, 0, 0).unwrap_err(),
+            CcsError::InvalidDayOfYear(2023, 366)
+        );
+        assert_eq!(
+            TimeProvider::new_doy(2023, 0, 0, 0, 0).unwrap_err(),
+            CcsError::InvalidDayOfYear(2023, 0)
+        );
+    }
+
+    #[test]
+    fn test_write_buf_too_small() {
+        let stamp = TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        let mut buf: [u8; 7] = [0; 7];
+        let res = stamp.write_to_bytes(&mut buf);
+        assert!(matches!(
+            res.unwrap_err(),
+            TimestampError::ByteConversionError(ByteConversionError::ToSliceTooSmall(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialization() {
+        let mut stamp = TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        stamp.set_subseconds(2, 0x1234).unwrap();
+        let val = postcard::to_allocvec(&stamp).expect("Serializing timestamp failed");
+        let stamp_deser: TimeProvider =
+            postcard::from_bytes(&val).expect("Stamp deserialization failed");
+        assert_eq!(stamp_deser, stamp);
+    }
+}
@@ -4,17 +4,24 @@
 //! The core data structure to do this is the [TimeProvider] struct and the
 //! [get_dyn_time_provider_from_bytes] function to retrieve correct instances of the
 //! struct from a bytestream.
+//!
+//! The width of the days field is selected with the generic parameter of the [TimeProvider].
+//! The 16 bit days field of [DaysLen16Bits] rolls over in 2137, so missions which need a
+//! larger range can use the 24 bit days field of [DaysLen24Bits], which increases the
+//! timestamp length to 8 bytes.
 use super::*;
 use crate::private::Sealed;
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "chrono")]
 use chrono::Datelike;
 #[cfg(feature = "alloc")]
 use core::any::Any;
 use core::cmp::Ordering;
 use core::fmt::Debug;
-use core::ops::{Add, AddAssign};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
 use core::time::Duration;
+#[cfg(any(feature = "std", feature = "chrono"))]
 use delegate::delegate;
 
 /// Base value for the preamble field for a time field parser to determine the time field type.
@@ -78,6 +85,11 @@ pub enum CdsError {
     /// There are distinct constructors depending on the days field width detected in the preamble
     /// field. This error will be returned if there is a missmatch.
     InvalidCtorForDaysOfLenInPreamble(LengthOfDaySegment),
+    /// Milliseconds of day value read from a raw timestamp is equal to or larger than the
+    /// number of milliseconds in a day.
+    InvalidMsOfDay(u32),
+    /// Sub-millisecond precision value is equal to or larger than one millisecond.
+    InvalidSubmillisPrecision(SubmillisPrecision),
 }
 
 impl Display for CdsError {
@@ -92,6 +104,15 @@ impl Display for CdsError {
                     "wrong constructor for length of day {length_of_day:?} detected in preamble",
                 )
             }
+            CdsError::InvalidMsOfDay(ms_of_day) => {
+                write!(f, "invalid milliseconds of day {ms_of_day}")
+            }
+            CdsError::InvalidSubmillisPrecision(prec) => {
+                write!(
+                    f,
+                    "sub-millisecond precision {prec:?} exceeds one millisecond"
+                )
+            }
         }
     }
 }
@@ -175,7 +196,9 @@ pub struct TimeProvider<DaysLen: ProvidesDaysLength = DaysLen16Bits> {
 /// Also exists to encapsulate properties used by private converters.
 pub trait CdsCommon {
     fn submillis_precision(&self) -> Option<SubmillisPrecision>;
+    /// Raw milliseconds of day field.
     fn ms_of_day(&self) -> u32;
+    /// Raw CCSDS days field, converted to a [u32] for both days field widths.
     fn ccsds_days_as_u32(&self) -> u32;
 }
 
@@ -202,13 +225,9 @@ impl ConversionFromUnix {
         let (unix_days, secs_of_day) = calc_unix_days_and_secs_of_day(unix_seconds);
         let ccsds_days = unix_to_ccsds_days(unix_days);
         if ccsds_days == 0 && (secs_of_day > 0 || subsec_millis > 0) || ccsds_days < 0 {
-            let millis = if unix_seconds < 0 {
-                unix_seconds * 1000 - subsec_millis as i64
-            } else {
-                unix_seconds * 1000 + subsec_millis as i64
-            };
+            // The subsecond milliseconds of a leap second can exceed 999.
             return Err(TimestampError::DateBeforeCcsdsEpoch(
-                Utc.timestamp_millis_opt(millis).unwrap(),
+                UnixTimestamp::const_new(unix_seconds, subsec_millis.min(999) as u16),
             ));
         }
         Ok(Self {
@@ -239,11 +258,13 @@ impl CdsConverter for ConversionFromUnix {
     }
 }
 /// Helper struct which generates fields for the CDS time provider from a datetime.
+#[cfg(feature = "chrono")]
 struct ConversionFromDatetime {
     unix_conversion: ConversionFromUnix,
     submillis_prec: Option<SubmillisPrecision>,
 }
 
+#[cfg(feature = "chrono")]
 impl CdsCommon for ConversionFromDatetime {
     fn submillis_precision(&self) -> Option<SubmillisPrecision> {
         self.submillis_prec
@@ -257,6 +278,7 @@ impl CdsCommon for ConversionFromDatetime {
     }
 }
 
+#[cfg(feature = "chrono")]
 impl CdsConverter for ConversionFromDatetime {
     delegate! {to self.unix_conversion { fn unix_days_seconds(&self) -> i64; }}
 }
@@ -274,6 +296,7 @@ fn calc_unix_days_and_secs_of_day(unix_seconds: i64) -> (i64, u32) {
     (unix_days, secs_of_day as u32)
 }
 
+#[cfg(feature = "chrono")]
 impl ConversionFromDatetime {
     fn new(dt: &DateTime<Utc>) -> Result<Self, TimestampError> {
         Self::new_generic(dt, None)
@@ -293,7 +316,9 @@ impl ConversionFromDatetime {
     ) -> Result<Self, TimestampError> {
         // The CDS timestamp does not support timestamps before the CCSDS epoch.
         if dt.year() < 1958 {
-            return Err(TimestampError::DateBeforeCcsdsEpoch(*dt));
+            return Err(TimestampError::DateBeforeCcsdsEpoch(UnixTimestamp::from(
+                *dt,
+            )));
         }
         // The contained values in the conversion should be all positive now
         let unix_conversion =
@@ -478,15 +503,107 @@ impl<ProvidesDaysLen: ProvidesDaysLength> TimeProvider<ProvidesDaysLen> {
         }
     }
 
+    /// Like [Self::set_submillis_precision], but returns [CdsError::InvalidSubmillisPrecision]
+    /// if the microseconds or picoseconds value is not smaller than one millisecond.
+    pub fn try_set_submillis_precision(
+        &mut self,
+        prec: SubmillisPrecision,
+    ) -> Result<(), CdsError> {
+        match prec {
+            SubmillisPrecision::Microseconds(us) if us >= 1000 => {
+                return Err(CdsError::InvalidSubmillisPrecision(prec));
+            }
+            SubmillisPrecision::Picoseconds(ps) if ps >= 10_u32.pow(9) => {
+                return Err(CdsError::InvalidSubmillisPrecision(prec));
+            }
+            _ => (),
+        }
+        self.set_submillis_precision(prec);
+        Ok(())
+    }
+
     pub fn clear_submillis_precision(&mut self) {
         self.pfield &= !(0b11);
         self.submillis_precision = None;
     }
 
+    /// Raw CCSDS days field, which is a [u16] for [DaysLen16Bits] and a [u32] for
+    /// [DaysLen24Bits]. The raw milliseconds of day field is returned by [CdsCommon::ms_of_day].
     pub fn ccsds_days(&self) -> ProvidesDaysLen::FieldType {
         self.ccsds_days
     }
 
+    /// Calendar breakdown of the timestamp as a (year, month, day, hour, minute, second,
+    /// millisecond) tuple. Unlike [CcsdsTimeProvider::date_time], this does not use [chrono]
+    /// for the calculation.
+    pub fn to_ymd_hms(&self) -> (i32, u32, u32, u32, u32, u32, u32) {
+        let (year, month, day) =
+            civil_from_unix_days(ccsds_to_unix_days(self.ccsds_days_as_u32() as i64));
+        let seconds_of_day = self.ms_of_day / 1000;
+        (
+            year,
+            month,
+            day,
+            seconds_of_day / 3600,
+            (seconds_of_day / 60) % 60,
+            seconds_of_day % 60,
+            self.ms_of_day % 1000,
+        )
+    }
+
+    /// Fallible variant of [CcsdsTimeProvider::date_time] which returns the reason why the
+    /// fields of the timestamp can not be converted to a [`DateTime<Utc>`].
+    ///
+    /// The timestamp fields are not validated by all constructors, so this should be used for
+    /// timestamps which are read from untrusted sources.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    pub fn try_date_time(&self) -> Result<DateTime<Utc>, CdsError> {
+        if self.ms_of_day >= MS_PER_DAY {
+            return Err(CdsError::InvalidMsOfDay(self.ms_of_day));
+        }
+        let mut ns_since_last_sec = (self.ms_of_day % 1000) * 10_u32.pow(6);
+        if let Some(precision) = self.submillis_precision {
+            match precision {
+                SubmillisPrecision::Microseconds(us) if us < 1000 => {
+                    ns_since_last_sec += us as u32 * 1000;
+                }
+                SubmillisPrecision::Picoseconds(ps) if ps < 10_u32.pow(9) => {
+                    ns_since_last_sec += ps / 1000;
+                }
+                SubmillisPrecision::Microseconds(_) | SubmillisPrecision::Picoseconds(_) => {
+                    return Err(CdsError::InvalidSubmillisPrecision(precision));
+                }
+                _ => (),
+            }
+        }
+        if let LocalResult::Single(val) =
+            Utc.timestamp_opt(self.unix_stamp.unix_seconds, ns_since_last_sec)
+        {
+            return Ok(val);
+        }
+        Err(CdsError::InvalidCcsdsDays(self.ccsds_days_as_u32() as i64))
+    }
+
+    /// Elapsed time since the other timestamp, or [None] if the other timestamp is later.
+    ///
+    /// The calculation is performed on the raw days and milliseconds of day fields, including the
+    /// sub-millisecond precision with nanosecond resolution, and works across day boundaries.
+    pub fn duration_since(&self, other: &Self) -> Option<Duration> {
+        const NS_PER_SEC: u128 = 10_u128.pow(9);
+        // The nanoseconds since the epoch of a 24 bit days field do not fit into an u64.
+        let nanos_since_epoch = |stamp: &Self| {
+            (stamp.ccsds_days_as_u32() as u128 * MS_PER_DAY as u128 + stamp.ms_of_day as u128)
+                * 10_u128.pow(6)
+                + stamp.precision_as_ns().unwrap_or(0) as u128
+        };
+        let diff_nanos = nanos_since_epoch(self).checked_sub(nanos_since_epoch(other))?;
+        Some(Duration::new(
+            (diff_nanos / NS_PER_SEC) as u64,
+            (diff_nanos % NS_PER_SEC) as u32,
+        ))
+    }
+
     /// Maps the submillisecond precision to a nanosecond value. This will reduce precision when
     /// using picosecond resolution, but significantly simplifies comparison of timestamps.
     pub fn precision_as_ns(&self) -> Option<u32> {
@@ -555,7 +672,7 @@ impl<ProvidesDaysLen: ProvidesDaysLength> TimeProvider<ProvidesDaysLen> {
         Ok(precision_from_pfield(pfield))
     }
 
-    fn calc_stamp_len(pfield: u8) -> usize {
+    pub(crate) fn calc_stamp_len(pfield: u8) -> usize {
         let mut init_len = 7;
         if length_of_day_segment_from_pfield(pfield) == LengthOfDaySegment::Long24Bits {
             init_len += 1
@@ -587,19 +704,6 @@ impl<ProvidesDaysLen: ProvidesDaysLength> TimeProvider<ProvidesDaysLen> {
         self.unix_stamp = UnixTimestamp::const_new(unix_days_seconds, (ms_of_day % 1000) as u16);
     }
 
-    fn calc_date_time(&self, ns_since_last_second: u32) -> Option<DateTime<Utc>> {
-        assert!(
-            ns_since_last_second < 10_u32.pow(9),
-            "Invalid MS since last second"
-        );
-        if let LocalResult::Single(val) =
-            Utc.timestamp_opt(self.unix_stamp.unix_seconds, ns_since_last_second)
-        {
-            return Some(val);
-        }
-        None
-    }
-
     fn length_check(&self, buf: &[u8], len_as_bytes: usize) -> Result<(), TimestampError> {
         if buf.len() < len_as_bytes {
             return Err(TimestampError::ByteConversionError(
@@ -632,6 +736,7 @@ impl<ProvidesDaysLen: ProvidesDaysLength> TimeProvider<ProvidesDaysLen> {
         Ok(provider)
     }
 
+    #[cfg(feature = "chrono")]
     fn from_dt_generic(
         dt: &DateTime<Utc>,
         days_len: LengthOfDaySegment,
@@ -640,6 +745,7 @@ impl<ProvidesDaysLen: ProvidesDaysLength> TimeProvider<ProvidesDaysLen> {
         Self::generic_from_conversion(days_len, conv_from_dt)
     }
 
+    #[cfg(feature = "chrono")]
     fn from_dt_generic_us_prec(
         dt: &DateTime<Utc>,
         days_len: LengthOfDaySegment,
@@ -648,6 +754,7 @@ impl<ProvidesDaysLen: ProvidesDaysLength> TimeProvider<ProvidesDaysLen> {
         Self::generic_from_conversion(days_len, conv_from_dt)
     }
 
+    #[cfg(feature = "chrono")]
     fn from_dt_generic_ps_prec(
         dt: &DateTime<Utc>,
         days_len: LengthOfDaySegment,
@@ -768,15 +875,40 @@ impl<ProvidesDaysLen: ProvidesDaysLength> TimeProvider<ProvidesDaysLen> {
 }
 
 impl TimeProvider<DaysLen24Bits> {
-    /// Generate a new timestamp provider with the days field width set to 24 bits
+    /// Length of a timestamp with a 24 bit days field and without sub-millisecond precision.
+    pub const MIN_LEN: usize = MIN_CDS_FIELD_LEN + 1;
+    /// Length of a timestamp with a 24 bit days field and picosecond precision.
+    pub const MAX_LEN: usize = Self::MIN_LEN + 4;
+
+    /// Generate a new timestamp provider with the days field width set to 24 bits.
+    ///
+    /// A milliseconds of day value which is equal to or larger than the number of milliseconds in
+    /// a day is normalized by incrementing the days. [CdsError::InvalidCcsdsDays] is returned if
+    /// the resulting days do not fit into 24 bits. See [Self::try_new_with_u24_days] for a
+    /// variant which rejects such milliseconds of day values instead.
     pub fn new_with_u24_days(ccsds_days: u32, ms_of_day: u32) -> Result<Self, CdsError> {
-        if ccsds_days > MAX_DAYS_24_BITS {
-            return Err(CdsError::InvalidCcsdsDays(ccsds_days.into()));
+        let (ccsds_days, ms_of_day) = normalize_ms_of_day(ccsds_days, ms_of_day);
+        if ccsds_days > MAX_DAYS_24_BITS as u64 {
+            return Err(CdsError::InvalidCcsdsDays(ccsds_days as i64));
         }
-        Self::generic_new(LengthOfDaySegment::Long24Bits, ccsds_days, ms_of_day)
+        Self::generic_new(LengthOfDaySegment::Long24Bits, ccsds_days as u32, ms_of_day)
+    }
+
+    /// Like [Self::new_with_u24_days] but returns [CdsError::InvalidMsOfDay] if the milliseconds
+    /// of day value is equal to or larger than the number of milliseconds in a day.
+    pub fn try_new_with_u24_days(ccsds_days: u32, ms_of_day: u32) -> Result<Self, CdsError> {
+        if ms_of_day >= MS_PER_DAY {
+            return Err(CdsError::InvalidMsOfDay(ms_of_day));
+        }
+        Self::new_with_u24_days(ccsds_days, ms_of_day)
     }
 
     /// Generate a time stamp from the current time using the system clock.
+    ///
+    /// The system time is truncated to milliseconds. Use
+    /// [Self::from_now_with_u24_days_us_precision] or [Self::from_now_with_u24_days_ps_precision]
+    /// to also store the sub-millisecond part, which is truncated to microseconds or to the
+    /// nanosecond resolution of the system clock respectively.
     #[cfg(feature = "std")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
     pub fn from_now_with_u24_days() -> Result<Self, StdTimestampError> {
@@ -790,6 +922,8 @@ impl TimeProvider<DaysLen24Bits> {
     /// This function will return [TimestampError::DateBeforeCcsdsEpoch] or
     /// [TimestampError::CdsError] if the time is before the CCSDS epoch (1958-01-01T00:00:00+00:00)
     /// or the CCSDS days value exceeds the allowed bit width (24 bits).
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn from_dt_with_u24_days(dt: &DateTime<Utc>) -> Result<Self, TimestampError> {
         Self::from_dt_generic(dt, LengthOfDaySegment::Long24Bits)
     }
@@ -808,11 +942,15 @@ impl TimeProvider<DaysLen24Bits> {
     }
 
     /// Like [Self::from_dt_with_u24_days] but with microsecond sub-millisecond precision.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn from_dt_with_u24_days_us_precision(dt: &DateTime<Utc>) -> Result<Self, TimestampError> {
         Self::from_dt_generic_us_prec(dt, LengthOfDaySegment::Long24Bits)
     }
 
     /// Like [Self::from_dt_with_u24_days] but with picoseconds sub-millisecond precision.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn from_dt_with_u24_days_ps_precision(dt: &DateTime<Utc>) -> Result<Self, TimestampError> {
         Self::from_dt_generic_ps_prec(dt, LengthOfDaySegment::Long24Bits)
     }
@@ -828,7 +966,7 @@ impl TimeProvider<DaysLen24Bits> {
     #[cfg(feature = "std")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
     pub fn from_now_with_u24_days_ps_precision() -> Result<Self, StdTimestampError> {
-        Self::from_now_generic_us_prec(LengthOfDaySegment::Long24Bits)
+        Self::from_now_generic_ps_prec(LengthOfDaySegment::Long24Bits)
     }
 
     pub fn from_bytes_with_u24_days(buf: &[u8]) -> Result<Self, TimestampError> {
@@ -838,16 +976,19 @@ impl TimeProvider<DaysLen24Bits> {
         temp_buf[1..4].copy_from_slice(&buf[1..4]);
         let cccsds_days: u32 = u32::from_be_bytes(temp_buf);
         let ms_of_day: u32 = u32::from_be_bytes(buf[4..8].try_into().unwrap());
+        if ms_of_day >= MS_PER_DAY {
+            return Err(CdsError::InvalidMsOfDay(ms_of_day).into());
+        }
         let mut provider = Self::new_with_u24_days(cccsds_days, ms_of_day)?;
         match submillis_precision {
             SubmillisPrecision::Microseconds(_) => {
-                provider.set_submillis_precision(SubmillisPrecision::Microseconds(
+                provider.try_set_submillis_precision(SubmillisPrecision::Microseconds(
                     u16::from_be_bytes(buf[8..10].try_into().unwrap()),
-                ))
+                ))?
             }
-            SubmillisPrecision::Picoseconds(_) => provider.set_submillis_precision(
+            SubmillisPrecision::Picoseconds(_) => provider.try_set_submillis_precision(
                 SubmillisPrecision::Picoseconds(u32::from_be_bytes(buf[8..12].try_into().unwrap())),
-            ),
+            )?,
             _ => (),
         }
         Ok(provider)
@@ -855,22 +996,53 @@ impl TimeProvider<DaysLen24Bits> {
 }
 
 impl TimeProvider<DaysLen16Bits> {
-    /// Generate a new timestamp provider with the days field width set to 16 bits
+    /// Length of a timestamp with a 16 bit days field and without sub-millisecond precision.
+    pub const MIN_LEN: usize = MIN_CDS_FIELD_LEN;
+    /// Length of a timestamp with a 16 bit days field and picosecond precision.
+    pub const MAX_LEN: usize = Self::MIN_LEN + 4;
+
+    /// Generate a new timestamp provider with the days field width set to 16 bits.
+    ///
+    /// A milliseconds of day value which is equal to or larger than the number of milliseconds in
+    /// a day is normalized by incrementing the days. If the resulting days exceed the maximum
+    /// value of the 16 bit days field, the timestamp saturates at the last millisecond of the
+    /// last representable day. See [Self::try_new_with_u16_days] for a variant which rejects such
+    /// milliseconds of day values.
     pub fn new_with_u16_days(ccsds_days: u16, ms_of_day: u32) -> Self {
+        let (ccsds_days, ms_of_day) = match normalize_ms_of_day(ccsds_days.into(), ms_of_day) {
+            (days, _) if days > u16::MAX as u64 => (u16::MAX, MS_PER_DAY - 1),
+            (days, ms_of_day) => (days as u16, ms_of_day),
+        };
         // This should never fail, type system ensures CCSDS can not be negative or too large
         Self::generic_new(LengthOfDaySegment::Short16Bits, ccsds_days, ms_of_day).unwrap()
     }
 
+    /// Like [Self::new_with_u16_days] but returns [CdsError::InvalidMsOfDay] if the milliseconds
+    /// of day value is equal to or larger than the number of milliseconds in a day.
+    pub fn try_new_with_u16_days(ccsds_days: u16, ms_of_day: u32) -> Result<Self, CdsError> {
+        if ms_of_day >= MS_PER_DAY {
+            return Err(CdsError::InvalidMsOfDay(ms_of_day));
+        }
+        Ok(Self::new_with_u16_days(ccsds_days, ms_of_day))
+    }
+
     /// Create a provider from a [`DateTime<Utc>`] struct.
     ///
     /// This function will return a [TimestampError::DateBeforeCcsdsEpoch] or a
     /// [TimestampError::CdsError] if the time is before the CCSDS epoch (01-01-1958 00:00:00) or
     /// the CCSDS days value exceeds the allowed bit width (16 bits).
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn from_dt_with_u16_days(dt: &DateTime<Utc>) -> Result<Self, TimestampError> {
         Self::from_dt_generic(dt, LengthOfDaySegment::Short16Bits)
     }
 
     /// Generate a time stamp from the current time using the system clock.
+    ///
+    /// The system time is truncated to milliseconds. Use
+    /// [Self::from_now_with_u16_days_us_precision] or [Self::from_now_with_u16_days_ps_precision]
+    /// to also store the sub-millisecond part, which is truncated to microseconds or to the
+    /// nanosecond resolution of the system clock respectively.
     #[cfg(feature = "std")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
     pub fn from_now_with_u16_days() -> Result<Self, StdTimestampError> {
@@ -890,12 +1062,33 @@ impl TimeProvider<DaysLen16Bits> {
         Self::from_unix_generic(unix_stamp, LengthOfDaySegment::Short16Bits)
     }
 
+    /// Create a provider from an ISO-8601 date time string like `2023-01-14T16:49:30.123Z`.
+    /// Date times with a UTC offset are converted to UTC. Sub-millisecond digits are truncated.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return [TimestampError::InvalidDateTimeString] if the string can not be
+    /// parsed. Like [Self::from_dt_with_u16_days], it will return
+    /// [TimestampError::DateBeforeCcsdsEpoch] or [TimestampError::CdsError] if the time can not
+    /// be represented with a 16 bit days field.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    pub fn from_iso8601(date_time: &str) -> Result<Self, TimestampError> {
+        let dt = DateTime::parse_from_rfc3339(date_time)
+            .map_err(|_| TimestampError::InvalidDateTimeString)?;
+        Self::from_dt_with_u16_days(&dt.with_timezone(&Utc))
+    }
+
     /// Like [Self::from_dt_with_u16_days] but with microsecond sub-millisecond precision.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn from_dt_with_u16_days_us_precision(dt: &DateTime<Utc>) -> Result<Self, TimestampError> {
         Self::from_dt_generic_us_prec(dt, LengthOfDaySegment::Short16Bits)
     }
 
     /// Like [Self::from_dt_with_u16_days] but with picoseconds sub-millisecond precision.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn from_dt_with_u16_days_ps_precision(dt: &DateTime<Utc>) -> Result<Self, TimestampError> {
         Self::from_dt_generic_ps_prec(dt, LengthOfDaySegment::Short16Bits)
     }
@@ -919,21 +1112,32 @@ impl TimeProvider<DaysLen16Bits> {
             Self::generic_raw_read_checks(buf, LengthOfDaySegment::Short16Bits)?;
         let ccsds_days: u16 = u16::from_be_bytes(buf[1..3].try_into().unwrap());
         let ms_of_day: u32 = u32::from_be_bytes(buf[3..7].try_into().unwrap());
+        if ms_of_day >= MS_PER_DAY {
+            return Err(CdsError::InvalidMsOfDay(ms_of_day).into());
+        }
         let mut provider = Self::new_with_u16_days(ccsds_days, ms_of_day);
         provider.pfield = buf[0];
         match submillis_precision {
-            SubmillisPrecision::Microseconds(_) => provider.set_submillis_precision(
+            SubmillisPrecision::Microseconds(_) => provider.try_set_submillis_precision(
                 SubmillisPrecision::Microseconds(u16::from_be_bytes(buf[7..9].try_into().unwrap())),
-            ),
-            SubmillisPrecision::Picoseconds(_) => provider.set_submillis_precision(
+            )?,
+            SubmillisPrecision::Picoseconds(_) => provider.try_set_submillis_precision(
                 SubmillisPrecision::Picoseconds(u32::from_be_bytes(buf[7..11].try_into().unwrap())),
-            ),
+            )?,
             _ => (),
         }
         Ok(provider)
     }
 }
 
+/// Move full days contained in the milliseconds of day value into the days value.
+fn normalize_ms_of_day(ccsds_days: u32, ms_of_day: u32) -> (u64, u32) {
+    (
+        ccsds_days as u64 + (ms_of_day / MS_PER_DAY) as u64,
+        ms_of_day % MS_PER_DAY,
+    )
+}
+
 fn add_for_max_ccsds_days_val<T: ProvidesDaysLength>(
     time_provider: &TimeProvider<T>,
     max_days_val: u32,
@@ -1013,6 +1217,50 @@ fn add_for_max_ccsds_days_val<T: ProvidesDaysLength>(
     (next_ccsds_days, next_ms_of_day, precision)
 }
 
+fn sub_for_max_ccsds_days_val<T: ProvidesDaysLength>(
+    time_provider: &TimeProvider<T>,
+    max_days_val: u32,
+    duration: Duration,
+) -> (u32, u32, Option<SubmillisPrecision>) {
+    const PS_PER_MS: u128 = 10_u128.pow(9);
+    let ps_per_day = MS_PER_DAY as u128 * PS_PER_MS;
+    let days_modulus = max_days_val as u128 + 1;
+    // The duration is truncated to the precision of the time provider, mirroring the addition.
+    let (submilli_ps, duration_ps) = match time_provider.submillis_precision {
+        Some(SubmillisPrecision::Microseconds(us)) => (
+            us as u128 * 10_u128.pow(6),
+            duration.as_micros() * 10_u128.pow(6),
+        ),
+        Some(SubmillisPrecision::Picoseconds(ps)) => {
+            (ps as u128, duration.as_nanos() * 10_u128.pow(3))
+        }
+        _ => (0, duration.as_millis() * PS_PER_MS),
+    };
+    let ps_of_day = time_provider.ms_of_day as u128 * PS_PER_MS + submilli_ps;
+    // Borrow as many days as required so that the new time of day is not negative.
+    let mut days_decrement = 0;
+    if duration_ps > ps_of_day {
+        days_decrement = (duration_ps - ps_of_day + ps_per_day - 1) / ps_per_day;
+    }
+    let next_ps_of_day = ps_of_day + days_decrement * ps_per_day - duration_ps;
+    // The CCSDS days roll over to the maximum value when they underflow.
+    let next_ccsds_days = ((time_provider.ccsds_days_as_u32() as u128 + days_modulus
+        - days_decrement % days_modulus)
+        % days_modulus) as u32;
+    let next_ms_of_day = (next_ps_of_day / PS_PER_MS) as u32;
+    let submilli_ps = (next_ps_of_day % PS_PER_MS) as u32;
+    let precision = match time_provider.submillis_precision {
+        Some(SubmillisPrecision::Microseconds(_)) => Some(SubmillisPrecision::Microseconds(
+            (submilli_ps / 10_u32.pow(6)) as u16,
+        )),
+        Some(SubmillisPrecision::Picoseconds(_)) => {
+            Some(SubmillisPrecision::Picoseconds(submilli_ps))
+        }
+        _ => None,
+    };
+    (next_ccsds_days, next_ms_of_day, precision)
+}
+
 impl CdsTimestamp for TimeProvider<DaysLen16Bits> {
     fn len_of_day_seg(&self) -> LengthOfDaySegment {
         LengthOfDaySegment::Short16Bits
@@ -1092,11 +1340,7 @@ impl Add<Duration> for &TimeProvider<DaysLen24Bits> {
 /// days overflow when this is a possibility and might be a problem.
 impl AddAssign<Duration> for TimeProvider<DaysLen16Bits> {
     fn add_assign(&mut self, duration: Duration) {
-        let (next_ccsds_days, next_ms_of_day, precision) =
-            add_for_max_ccsds_days_val(self, u16::MAX as u32, duration);
-        self.ccsds_days = next_ccsds_days as u16;
-        self.ms_of_day = next_ms_of_day;
-        self.submillis_precision = precision;
+        *self = *self + duration;
     }
 }
 
@@ -1105,14 +1349,75 @@ impl AddAssign<Duration> for TimeProvider<DaysLen16Bits> {
 /// days overflow when this is a possibility and might be a problem.
 impl AddAssign<Duration> for TimeProvider<DaysLen24Bits> {
     fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+/// Allows subtracting a duration in form of an offset. Please note that the CCSDS days will roll
+/// over to the maximum value when they underflow, because subtraction needs to be infallible.
+/// The user needs to check for a days underflow when this is a possibility and might be a problem.
+impl Sub<Duration> for TimeProvider<DaysLen16Bits> {
+    type Output = Self;
+
+    fn sub(self, duration: Duration) -> Self::Output {
+        Sub::sub(&self, duration)
+    }
+}
+
+impl Sub<Duration> for &TimeProvider<DaysLen16Bits> {
+    type Output = TimeProvider<DaysLen16Bits>;
+
+    fn sub(self, duration: Duration) -> Self::Output {
         let (next_ccsds_days, next_ms_of_day, precision) =
-            add_for_max_ccsds_days_val(self, MAX_DAYS_24_BITS, duration);
-        self.ccsds_days = next_ccsds_days;
-        self.ms_of_day = next_ms_of_day;
-        self.submillis_precision = precision;
+            sub_for_max_ccsds_days_val(self, u16::MAX as u32, duration);
+        let mut provider = Self::Output::new_with_u16_days(next_ccsds_days as u16, next_ms_of_day);
+        if let Some(prec) = precision {
+            provider.set_submillis_precision(prec);
+        }
+        provider
+    }
+}
+
+/// Allows subtracting a duration in form of an offset. Please note that the CCSDS days will roll
+/// over to the maximum value when they underflow, because subtraction needs to be infallible.
+/// The user needs to check for a days underflow when this is a possibility and might be a problem.
+impl Sub<Duration> for TimeProvider<DaysLen24Bits> {
+    type Output = Self;
+
+    fn sub(self, duration: Duration) -> Self::Output {
+        Sub::sub(&self, duration)
+    }
+}
+
+impl Sub<Duration> for &TimeProvider<DaysLen24Bits> {
+    type Output = TimeProvider<DaysLen24Bits>;
+
+    fn sub(self, duration: Duration) -> Self::Output {
+        let (next_ccsds_days, next_ms_of_day, precision) =
+            sub_for_max_ccsds_days_val(self, MAX_DAYS_24_BITS, duration);
+        let mut provider =
+            Self::Output::new_with_u24_days(next_ccsds_days, next_ms_of_day).unwrap();
+        if let Some(prec) = precision {
+            provider.set_submillis_precision(prec);
+        }
+        provider
+    }
+}
+
+impl SubAssign<Duration> for TimeProvider<DaysLen16Bits> {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+impl SubAssign<Duration> for TimeProvider<DaysLen24Bits> {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
     }
 }
 
+#[cfg(feature = "chrono")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
 impl TryFrom<DateTime<Utc>> for TimeProvider<DaysLen16Bits> {
     type Error = TimestampError;
 
@@ -1122,6 +1427,85 @@ impl TryFrom<DateTime<Utc>> for TimeProvider<DaysLen16Bits> {
     }
 }
 
+/// Create a provider from a [SystemTime], truncated to milliseconds. Times before the UNIX epoch
+/// are rejected with [StdTimestampError::SystemTimeError].
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl TryFrom<SystemTime> for TimeProvider<DaysLen16Bits> {
+    type Error = StdTimestampError;
+
+    fn try_from(system_time: SystemTime) -> Result<Self, Self::Error> {
+        let unix_duration = system_time.duration_since(SystemTime::UNIX_EPOCH)?;
+        let conversion = ConversionFromUnix::new(
+            unix_duration.as_secs() as i64,
+            unix_duration.subsec_millis(),
+        )?;
+        Ok(Self::generic_from_conversion(
+            LengthOfDaySegment::Short16Bits,
+            conversion,
+        )?)
+    }
+}
+
+/// Create a provider from a [SystemTime], truncated to milliseconds. Times before the UNIX epoch
+/// are rejected with [StdTimestampError::SystemTimeError].
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl TryFrom<SystemTime> for TimeProvider<DaysLen24Bits> {
+    type Error = StdTimestampError;
+
+    fn try_from(system_time: SystemTime) -> Result<Self, Self::Error> {
+        let unix_duration = system_time.duration_since(SystemTime::UNIX_EPOCH)?;
+        let conversion = ConversionFromUnix::new(
+            unix_duration.as_secs() as i64,
+            unix_duration.subsec_millis(),
+        )?;
+        Ok(Self::generic_from_conversion(
+            LengthOfDaySegment::Long24Bits,
+            conversion,
+        )?)
+    }
+}
+
+/// Convert the timestamp into a [SystemTime], including the sub-millisecond precision.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl<ProvidesDaysLen: ProvidesDaysLength> From<TimeProvider<ProvidesDaysLen>> for SystemTime {
+    fn from(provider: TimeProvider<ProvidesDaysLen>) -> Self {
+        let unix_nanos = provider.unix_nanos();
+        let unix_seconds = unix_nanos.div_euclid(10_i128.pow(9));
+        let subsec_nanos = Duration::from_nanos(unix_nanos.rem_euclid(10_i128.pow(9)) as u64);
+        if unix_seconds >= 0 {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(unix_seconds as u64) + subsec_nanos
+        } else {
+            SystemTime::UNIX_EPOCH - Duration::from_secs(unix_seconds.unsigned_abs() as u64)
+                + subsec_nanos
+        }
+    }
+}
+
+#[cfg(all(feature = "time", feature = "chrono"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "time", feature = "chrono"))))]
+impl TryFrom<::time::OffsetDateTime> for TimeProvider<DaysLen16Bits> {
+    type Error = TimestampError;
+
+    fn try_from(dt: ::time::OffsetDateTime) -> Result<Self, Self::Error> {
+        Self::try_from(date_time_from_offset_date_time(&dt))
+    }
+}
+
+#[cfg(all(feature = "time", feature = "chrono"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "time", feature = "chrono"))))]
+impl TryFrom<::time::OffsetDateTime> for TimeProvider<DaysLen24Bits> {
+    type Error = TimestampError;
+
+    fn try_from(dt: ::time::OffsetDateTime) -> Result<Self, Self::Error> {
+        Self::try_from(date_time_from_offset_date_time(&dt))
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
 impl TryFrom<DateTime<Utc>> for TimeProvider<DaysLen24Bits> {
     type Error = TimestampError;
 
@@ -1140,7 +1524,7 @@ impl<ProvidesDaysLen: ProvidesDaysLength> CcsdsTimeProvider for TimeProvider<Pro
         (1, [self.pfield, 0])
     }
 
-    fn ccdsd_time_code(&self) -> CcsdsTimeCodes {
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
         CcsdsTimeCodes::Cds
     }
 
@@ -1158,20 +1542,24 @@ impl<ProvidesDaysLen: ProvidesDaysLength> CcsdsTimeProvider for TimeProvider<Pro
         self.unix_stamp
     }
 
+    fn unix_nanos(&self) -> i128 {
+        self.unix_stamp.unix_seconds as i128 * 10_i128.pow(9)
+            + (self.ms_of_day % 1000) as i128 * 10_i128.pow(6)
+            + self.precision_as_ns().unwrap_or(0) as i128
+    }
+
+    #[cfg(feature = "chrono")]
     fn date_time(&self) -> Option<DateTime<Utc>> {
-        let mut ns_since_last_sec = (self.ms_of_day % 1000) * 10_u32.pow(6);
-        if let Some(precision) = self.submillis_precision {
-            match precision {
-                SubmillisPrecision::Microseconds(us) => {
-                    ns_since_last_sec += us as u32 * 1000;
-                }
-                SubmillisPrecision::Picoseconds(ps) => {
-                    ns_since_last_sec += ps / 1000;
-                }
-                _ => (),
-            }
+        self.try_date_time().ok()
+    }
+
+    #[cfg(feature = "chrono")]
+    fn iso8601_seconds_format(&self) -> SecondsFormat {
+        match self.submillis_precision() {
+            Some(SubmillisPrecision::Microseconds(_)) => SecondsFormat::Micros,
+            Some(SubmillisPrecision::Picoseconds(_)) => SecondsFormat::Nanos,
+            _ => SecondsFormat::Millis,
         }
-        self.calc_date_time(ns_since_last_sec)
     }
 }
 
@@ -1304,11 +1692,13 @@ mod tests {
     use super::*;
     use crate::time::TimestampError::{ByteConversionError, InvalidTimeCode};
     use crate::ByteConversionError::{FromSliceTooSmall, ToSliceTooSmall};
+    #[cfg(feature = "chrono")]
     use chrono::{Datelike, NaiveDate, Timelike};
     #[cfg(feature = "serde")]
     use postcard::{from_bytes, to_allocvec};
     use std::format;
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_time_stamp_zero_args() {
         let time_stamper = TimeProvider::new_with_u16_days(0, 0);
@@ -1321,7 +1711,7 @@ mod tests {
         assert!(subsecond_millis.is_none());
         assert_eq!(time_stamper.submillis_precision(), None);
         assert!(time_stamper.subsecond_millis().is_none());
-        assert_eq!(time_stamper.ccdsd_time_code(), CcsdsTimeCodes::Cds);
+        assert_eq!(time_stamper.ccsds_time_code(), CcsdsTimeCodes::Cds);
         assert_eq!(
             time_stamper.p_field(),
             (1, [(CcsdsTimeCodes::Cds as u8) << 4, 0])
@@ -1335,6 +1725,7 @@ mod tests {
         assert_eq!(date_time.second(), 0);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_time_stamp_unix_epoch() {
         let time_stamper = TimeProvider::new_with_u16_days((-DAYS_CCSDS_TO_UNIX) as u16, 0);
@@ -1355,9 +1746,27 @@ mod tests {
         assert_eq!(time_stamper.subsecond_millis().unwrap(), 40);
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_ymd_hms_breakdown() {
+        let time_stamper = TimeProvider::new_with_u16_days(0, 0);
+        assert_eq!(time_stamper.to_ymd_hms(), (1958, 1, 1, 0, 0, 0, 0));
+        let time_stamper = TimeProvider::new_with_u16_days((-DAYS_CCSDS_TO_UNIX) as u16, 1040);
+        assert_eq!(time_stamper.to_ymd_hms(), (1970, 1, 1, 0, 0, 1, 40));
+        // 2023-01-14T16:49:30.123+00:00
+        let time_stamper = TimeProvider::new_with_u16_days(23754, 60570123);
+        assert_eq!(time_stamper.to_ymd_hms(), (2023, 1, 14, 16, 49, 30, 123));
+        // Leap day 2024-02-29
+        let time_stamper = TimeProvider::new_with_u16_days((-DAYS_CCSDS_TO_UNIX) as u16 + 19782, 0);
+        let date_time = time_stamper.date_time().unwrap();
+        assert_eq!(date_time.month(), 2);
+        assert_eq!(date_time.day(), 29);
+        assert_eq!(time_stamper.to_ymd_hms(), (2024, 2, 29, 0, 0, 0, 0));
+    }
+
     #[test]
     fn test_large_days_field_write() {
-        let time_stamper = TimeProvider::new_with_u24_days(0x108020_u32, 0x10203040);
+        let time_stamper = TimeProvider::new_with_u24_days(0x108020_u32, 0x01020304);
         assert!(time_stamper.is_ok());
         let time_stamper = time_stamper.unwrap();
         assert_eq!(time_stamper.len_as_bytes(), 8);
@@ -1370,7 +1779,7 @@ mod tests {
         assert_eq!(buf[2], 0x80);
         assert_eq!(buf[3], 0x20);
         let ms = u32::from_be_bytes(buf[4..8].try_into().unwrap());
-        assert_eq!(ms, 0x10203040);
+        assert_eq!(ms, 0x01020304);
         assert_eq!((buf[0] >> 2) & 0b1, 1);
     }
 
@@ -1424,7 +1833,7 @@ mod tests {
             u32::from_be_bytes(buf[3..7].try_into().expect("Byte conversion failed")),
             0
         );
-        let time_stamper_1 = TimeProvider::new_with_u16_days(u16::MAX - 1, u32::MAX - 1);
+        let time_stamper_1 = TimeProvider::new_with_u16_days(u16::MAX - 1, MS_PER_DAY - 1);
         res = time_stamper_1.write_to_bytes(&mut buf);
         assert!(res.is_ok());
         assert_eq!(buf[0], (CcsdsTimeCodes::Cds as u8) << 4);
@@ -1434,10 +1843,21 @@ mod tests {
         );
         assert_eq!(
             u32::from_be_bytes(buf[3..7].try_into().expect("Byte conversion failed")),
-            u32::MAX - 1
+            MS_PER_DAY - 1
         );
     }
 
+    #[test]
+    fn test_write_exact_layout() {
+        let mut buf = [0; 7];
+        let time_stamper = TimeProvider::new_with_u16_days(0x1234, 0x0102_0304);
+        assert_eq!(time_stamper.write_to_bytes(&mut buf).unwrap(), 7);
+        // P-field, then the 16 bit days field, then the 32 bit milliseconds of day field.
+        assert_eq!(buf, [0x40, 0x12, 0x34, 0x01, 0x02, 0x03, 0x04]);
+        let read_back = TimeProvider::from_bytes_with_u16_days(&buf).unwrap();
+        assert_eq!(read_back, time_stamper);
+    }
+
     #[test]
     fn test_faulty_write_buf_too_small() {
         let mut buf = [0; 7];
@@ -1502,7 +1922,7 @@ mod tests {
     #[test]
     fn test_reading() {
         let mut buf = [0; 16];
-        let time_stamper = TimeProvider::new_with_u16_days(u16::MAX - 1, u32::MAX - 1);
+        let time_stamper = TimeProvider::new_with_u16_days(u16::MAX - 1, MS_PER_DAY - 1);
         let res = time_stamper.write_to_bytes(&mut buf);
         assert!(res.is_ok());
         assert_eq!(buf[0], (CcsdsTimeCodes::Cds as u8) << 4);
@@ -1512,15 +1932,41 @@ mod tests {
         );
         assert_eq!(
             u32::from_be_bytes(buf[3..7].try_into().expect("Byte conversion failed")),
-            u32::MAX - 1
+            MS_PER_DAY - 1
         );
 
         let read_stamp: TimeProvider<DaysLen16Bits> =
             TimeProvider::from_bytes(&buf).expect("Reading timestamp failed");
         assert_eq!(read_stamp.ccsds_days(), u16::MAX - 1);
-        assert_eq!(read_stamp.ms_of_day(), u32::MAX - 1);
+        assert_eq!(read_stamp.ms_of_day(), MS_PER_DAY - 1);
+    }
+
+    #[test]
+    fn test_reading_invalid_ms_of_day() {
+        let mut buf = [0; 16];
+        let time_stamper = TimeProvider::new_with_u16_days(u16::MAX - 1, 0);
+        time_stamper.write_to_bytes(&mut buf).unwrap();
+        buf[3..7].copy_from_slice(&MS_PER_DAY.to_be_bytes());
+        let res = TimeProvider::<DaysLen16Bits>::from_bytes(&buf);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err(),
+            TimestampError::CdsError(CdsError::InvalidMsOfDay(MS_PER_DAY))
+        );
+
+        let time_stamper = TimeProvider::new_with_u24_days(0x108020, 0).unwrap();
+        time_stamper.write_to_bytes(&mut buf).unwrap();
+        buf[4..8].copy_from_slice(&(u32::MAX - 1).to_be_bytes());
+        let res = TimeProvider::<DaysLen24Bits>::from_bytes(&buf);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err(),
+            TimestampError::CdsError(CdsError::InvalidMsOfDay(u32::MAX - 1))
+        );
     }
 
+    #[cfg(feature = "chrono")]
+    #[cfg(feature = "std")]
     fn generic_now_test<T: ProvidesDaysLength>(
         timestamp_now: TimeProvider<T>,
         compare_stamp: DateTime<Utc>,
@@ -1546,41 +1992,72 @@ mod tests {
         generic_dt_property_equality_check(dt.minute(), compare_stamp.minute(), 0, 59);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
+    #[cfg(feature = "std")]
     fn test_time_now() {
         let timestamp_now = TimeProvider::from_now_with_u16_days().unwrap();
         let compare_stamp = Utc::now();
         generic_now_test(timestamp_now, compare_stamp);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
+    #[cfg(feature = "std")]
     fn test_time_now_us_prec() {
         let timestamp_now = TimeProvider::from_now_with_u16_days_us_precision().unwrap();
         let compare_stamp = Utc::now();
         generic_now_test(timestamp_now, compare_stamp);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
+    #[cfg(feature = "std")]
     fn test_time_now_ps_prec() {
         let timestamp_now = TimeProvider::from_now_with_u16_days_ps_precision().unwrap();
         let compare_stamp = Utc::now();
         generic_now_test(timestamp_now, compare_stamp);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
+    #[cfg(feature = "std")]
     fn test_time_now_ps_prec_u16_days() {
         let timestamp_now = TimeProvider::from_now_with_u16_days_ps_precision().unwrap();
         let compare_stamp = Utc::now();
         generic_now_test(timestamp_now, compare_stamp);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
+    #[cfg(feature = "std")]
     fn test_time_now_ps_prec_u24_days() {
         let timestamp_now = TimeProvider::from_now_with_u24_days_ps_precision().unwrap();
         let compare_stamp = Utc::now();
         generic_now_test(timestamp_now, compare_stamp);
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_time_now_submillis_accuracy() {
+        let before = Utc::now();
+        let stamp_us = TimeProvider::from_now_with_u16_days_us_precision().unwrap();
+        let stamp_ps = TimeProvider::from_now_with_u24_days_ps_precision().unwrap();
+        let after = Utc::now();
+        assert!(matches!(
+            stamp_ps.submillis_precision(),
+            Some(SubmillisPrecision::Picoseconds(_))
+        ));
+        let dt_us = stamp_us.date_time().unwrap();
+        let dt_ps = stamp_ps.date_time().unwrap();
+        // The microsecond stamp may be truncated below the first reference time.
+        assert!(dt_us + chrono::Duration::microseconds(1) >= before);
+        assert!(dt_us <= dt_ps);
+        assert!(dt_ps >= before);
+        assert!(dt_ps <= after);
+    }
+
     #[test]
     fn test_submillis_precision_micros() {
         let mut time_stamper = TimeProvider::new_with_u16_days(0, 0);
@@ -1602,6 +2079,18 @@ mod tests {
         assert_eq!(write_buf[7..9], cross_check.to_be_bytes());
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_submillis_precision_micros_date_time() {
+        let mut time_stamper = TimeProvider::new_with_u16_days(0, 250);
+        time_stamper.set_submillis_precision(SubmillisPrecision::Microseconds(500));
+        assert_eq!(time_stamper.p_field().1[0] & 0b11, 0b01);
+        assert_eq!(time_stamper.len_as_bytes(), 9);
+        let dt = time_stamper.date_time().unwrap();
+        assert_eq!(dt.timestamp(), time_stamper.unix_seconds());
+        assert_eq!(dt.timestamp_subsec_nanos(), 250_500_000);
+    }
+
     #[test]
     fn test_submillis_precision_picos() {
         let mut time_stamper = TimeProvider::new_with_u16_days(0, 0);
@@ -1643,6 +2132,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_invalid_submillis_precision() {
+        let mut time_stamper = TimeProvider::new_with_u16_days(0, 0);
+        let invalid_picos = SubmillisPrecision::Picoseconds(10_u32.pow(9));
+        assert_eq!(
+            time_stamper.try_set_submillis_precision(invalid_picos),
+            Err(CdsError::InvalidSubmillisPrecision(invalid_picos))
+        );
+        assert!(time_stamper.submillis_precision().is_none());
+        let invalid_micros = SubmillisPrecision::Microseconds(1000);
+        assert_eq!(
+            time_stamper.try_set_submillis_precision(invalid_micros),
+            Err(CdsError::InvalidSubmillisPrecision(invalid_micros))
+        );
+        assert!(time_stamper
+            .try_set_submillis_precision(SubmillisPrecision::Picoseconds(10_u32.pow(9) - 1))
+            .is_ok());
+        assert_eq!(time_stamper.len_as_bytes(), 11);
+    }
+
+    #[test]
+    fn read_stamp_with_invalid_ps_submillis_precision() {
+        let mut time_stamper = TimeProvider::new_with_u16_days(0, 0);
+        time_stamper.set_submillis_precision(SubmillisPrecision::Picoseconds(u32::MAX));
+        let mut write_buf: [u8; 16] = [0; 16];
+        let written = time_stamper.write_to_bytes(&mut write_buf).unwrap();
+        assert_eq!(written, 11);
+        let res = TimeProvider::<DaysLen16Bits>::from_bytes(&write_buf);
+        assert_eq!(
+            res.unwrap_err(),
+            TimestampError::CdsError(CdsError::InvalidSubmillisPrecision(
+                SubmillisPrecision::Picoseconds(u32::MAX)
+            ))
+        );
+
+        let mut time_stamper = TimeProvider::new_with_u24_days(0, 0).unwrap();
+        time_stamper.set_submillis_precision(SubmillisPrecision::Picoseconds(u32::MAX));
+        time_stamper.write_to_bytes(&mut write_buf).unwrap();
+        let res = TimeProvider::<DaysLen24Bits>::from_bytes(&write_buf);
+        assert!(matches!(
+            res.unwrap_err(),
+            TimestampError::CdsError(CdsError::InvalidSubmillisPrecision(_))
+        ));
+    }
+
     #[test]
     fn read_stamp_with_us_submillis_precision() {
         let mut time_stamper = TimeProvider::new_with_u16_days(0, 0);
@@ -1713,6 +2247,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "chrono")]
     fn generic_dt_case_0_no_prec(subsec_millis: u32) -> DateTime<Utc> {
         let naivedatetime_utc = NaiveDate::from_ymd_opt(2023, 01, 14)
             .unwrap()
@@ -1721,6 +2256,7 @@ mod tests {
         DateTime::<Utc>::from_utc(naivedatetime_utc, Utc)
     }
 
+    #[cfg(feature = "chrono")]
     fn generic_check_dt_case_0<DaysLen: ProvidesDaysLength>(
         time_provider: &TimeProvider<DaysLen>,
         subsec_millis: u32,
@@ -1736,6 +2272,7 @@ mod tests {
         assert_eq!(time_provider.date_time().unwrap(), datetime_utc);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_creation_from_dt_u16_days() {
         let subsec_millis = 250;
@@ -1747,6 +2284,7 @@ mod tests {
         // Test the TryInto trait impl
         assert_eq!(time_provider, time_provider_2);
     }
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_creation_from_dt_u24_days() {
         let subsec_millis = 250;
@@ -1759,6 +2297,19 @@ mod tests {
         assert_eq!(time_provider, time_provider_2);
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_dt_round_trip_through_bytes() {
+        let datetime_utc = generic_dt_case_0_no_prec(999);
+        let time_provider: TimeProvider<DaysLen16Bits> =
+            datetime_utc.try_into().expect("conversion failed");
+        let mut buf = [0; 7];
+        time_provider.write_to_bytes(&mut buf).unwrap();
+        let read_back = TimeProvider::from_bytes_with_u16_days(&buf).unwrap();
+        assert_eq!(read_back.date_time().unwrap(), datetime_utc);
+    }
+
+    #[cfg(feature = "chrono")]
     fn generic_dt_case_1_us_prec(subsec_millis: u32) -> DateTime<Utc> {
         // 250 ms + 500 us
         let subsec_micros = subsec_millis * 1000 + 500;
@@ -1769,6 +2320,7 @@ mod tests {
         DateTime::<Utc>::from_utc(naivedatetime_utc, Utc)
     }
 
+    #[cfg(feature = "chrono")]
     fn generic_check_dt_case_1_us_prec<DaysLen: ProvidesDaysLength>(
         time_provider: &TimeProvider<DaysLen>,
         subsec_millis: u32,
@@ -1791,6 +2343,7 @@ mod tests {
         assert_eq!(time_provider.date_time().unwrap(), datetime_utc);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_creation_from_dt_u16_days_us_prec() {
         let subsec_millis = 250;
@@ -1800,6 +2353,7 @@ mod tests {
         generic_check_dt_case_1_us_prec(&time_provider, subsec_millis, datetime_utc);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_creation_from_dt_u24_days_us_prec() {
         let subsec_millis = 250;
@@ -1809,6 +2363,7 @@ mod tests {
         generic_check_dt_case_1_us_prec(&time_provider, subsec_millis, datetime_utc);
     }
 
+    #[cfg(feature = "chrono")]
     fn generic_dt_case_2_ps_prec(subsec_millis: u32) -> (DateTime<Utc>, u32) {
         // 250 ms + 500 us
         let subsec_nanos = subsec_millis * 1000 * 1000 + 500 * 1000;
@@ -1823,6 +2378,7 @@ mod tests {
         )
     }
 
+    #[cfg(feature = "chrono")]
     fn generic_check_dt_case_2_ps_prec<DaysLen: ProvidesDaysLength>(
         time_provider: &TimeProvider<DaysLen>,
         subsec_millis: u32,
@@ -1846,6 +2402,7 @@ mod tests {
         assert_eq!(time_provider.date_time().unwrap(), datetime_utc);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_creation_from_dt_u16_days_ps_prec() {
         let subsec_millis = 250;
@@ -1860,6 +2417,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_creation_from_dt_u24_days_ps_prec() {
         let subsec_millis = 250;
@@ -1898,6 +2456,7 @@ mod tests {
         assert_eq!(time_provider.ccsds_days, (-DAYS_CCSDS_TO_UNIX) as u32)
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_creation_from_unix_stamp_1() {
         let subsec_millis = 250;
@@ -1955,6 +2514,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_invalid_creation_from_unix_stamp_before_ccsds_epoch() {
         // This is a unix stamp before the CCSDS epoch (01-01-1958 00:00:00), this should be
@@ -1969,7 +2529,9 @@ mod tests {
                 panic!("creation should not succeed")
             }
             Err(e) => {
-                if let TimestampError::DateBeforeCcsdsEpoch(dt) = e {
+                if let TimestampError::DateBeforeCcsdsEpoch(unix_stamp) = e {
+                    assert_eq!(unix_stamp.unix_seconds, unix_secs as i64);
+                    let dt = unix_stamp.as_date_time().unwrap();
                     assert_eq!(dt.year(), 1957);
                     assert_eq!(dt.month(), 12);
                     assert_eq!(dt.day(), 31);
@@ -2030,7 +2592,7 @@ mod tests {
         let dyn_provider = get_dyn_time_provider_from_bytes(&buf);
         assert!(dyn_provider.is_ok());
         let dyn_provider = dyn_provider.unwrap();
-        assert_eq!(dyn_provider.ccdsd_time_code(), CcsdsTimeCodes::Cds);
+        assert_eq!(dyn_provider.ccsds_time_code(), CcsdsTimeCodes::Cds);
         assert_eq!(dyn_provider.ccsds_days_as_u32(), u16::MAX as u32 + 1);
         assert_eq!(dyn_provider.ms_of_day(), 24);
         assert_eq!(dyn_provider.submillis_precision(), None);
@@ -2113,6 +2675,75 @@ mod tests {
         assert_eq!(new_stamp.ms_of_day, 1000);
     }
 
+    #[test]
+    fn test_addition_updates_unix_stamp() {
+        let mut provider = TimeProvider::new_with_u16_days(20000, 0);
+        let unix_seconds = provider.unix_seconds();
+        provider += Duration::from_millis(1500);
+        assert_eq!(provider.unix_seconds(), unix_seconds + 1);
+        assert_eq!(provider.subsecond_millis(), Some(500));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_subtraction_u16_days() {
+        let provider = TimeProvider::new_with_u16_days(20000, 10_000);
+        let new_stamp = provider - Duration::from_secs(5);
+        assert_eq!(new_stamp.ccsds_days_as_u32(), 20000);
+        assert_eq!(new_stamp.ms_of_day, 5000);
+        assert_eq!(new_stamp.unix_seconds(), provider.unix_seconds() - 5);
+        assert_eq!(
+            new_stamp.date_time().unwrap(),
+            provider.date_time().unwrap() - chrono::Duration::seconds(5)
+        );
+    }
+
+    #[test]
+    fn test_subtraction_day_decrement() {
+        let mut provider = TimeProvider::new_with_u24_days(2, 5000).unwrap();
+        provider -= Duration::from_secs(10);
+        assert_eq!(provider.ccsds_days_as_u32(), 1);
+        assert_eq!(provider.ms_of_day, MS_PER_DAY - 5000);
+        let provider_ref = &provider;
+        let new_stamp = provider_ref - Duration::from_secs(2 * 24 * 60 * 60);
+        assert_eq!(new_stamp.ccsds_days_as_u32(), MAX_DAYS_24_BITS);
+        assert_eq!(new_stamp.ms_of_day, MS_PER_DAY - 5000);
+    }
+
+    #[test]
+    fn test_subtraction_underflow_u16_days() {
+        let provider = TimeProvider::new_with_u16_days(0, 0);
+        let new_stamp = provider - Duration::from_millis(1);
+        assert_eq!(new_stamp.ccsds_days_as_u32(), u16::MAX as u32);
+        assert_eq!(new_stamp.ms_of_day, MS_PER_DAY - 1);
+        let mut buf = [0; 7];
+        new_stamp.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(
+            TimeProvider::from_bytes_with_u16_days(&buf).unwrap(),
+            new_stamp
+        );
+    }
+
+    #[test]
+    fn test_subtraction_with_us_precision_borrow() {
+        let mut provider = TimeProvider::new_with_u16_days(0, 1);
+        provider.set_submillis_precision(SubmillisPrecision::Microseconds(200));
+        provider -= Duration::from_micros(400);
+        assert_eq!(provider.ms_of_day, 0);
+        assert_eq!(
+            provider.submillis_precision(),
+            Some(SubmillisPrecision::Microseconds(800))
+        );
+    }
+
+    #[test]
+    fn test_subtraction_inverse_of_addition() {
+        let mut provider = TimeProvider::new_with_u16_days(20, 500);
+        provider.set_submillis_precision(SubmillisPrecision::Picoseconds(300_000));
+        let duration = Duration::from_nanos(3 * 24 * 60 * 60 * 10_u64.pow(9) + 1_700_001);
+        assert_eq!(provider + duration - duration, provider);
+    }
+
     fn check_ps_and_carryover(prec: SubmillisPrecision, ms_of_day: u32, val: u32) {
         if let SubmillisPrecision::Picoseconds(ps) = prec {
             assert_eq!(ps, val);
@@ -2155,7 +2786,7 @@ mod tests {
         let dyn_provider = get_dyn_time_provider_from_bytes(&buf);
         assert!(dyn_provider.is_ok());
         let dyn_provider = dyn_provider.unwrap();
-        assert_eq!(dyn_provider.ccdsd_time_code(), CcsdsTimeCodes::Cds);
+        assert_eq!(dyn_provider.ccsds_time_code(), CcsdsTimeCodes::Cds);
         assert_eq!(dyn_provider.ccsds_days_as_u32(), 24);
         assert_eq!(dyn_provider.ms_of_day(), 24);
         assert_eq!(
@@ -2180,6 +2811,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_from_dt_invalid_time() {
         // Date before CCSDS epoch
@@ -2190,8 +2822,8 @@ mod tests {
         let datetime_utc = DateTime::<Utc>::from_utc(naivedatetime_utc, Utc);
         let time_provider = TimeProvider::from_dt_with_u24_days(&datetime_utc);
         assert!(time_provider.is_err());
-        if let TimestampError::DateBeforeCcsdsEpoch(dt) = time_provider.unwrap_err() {
-            assert_eq!(dt, datetime_utc);
+        if let TimestampError::DateBeforeCcsdsEpoch(unix_stamp) = time_provider.unwrap_err() {
+            assert_eq!(unix_stamp, UnixTimestamp::from(datetime_utc));
         }
     }
 
@@ -2221,6 +2853,202 @@ mod tests {
         assert!(stamp3 > stamp2);
     }
 
+    #[test]
+    fn test_eq_different_constructors() {
+        let stamp0 = TimeProvider::new_with_u16_days(1, 500);
+        let unix_stamp = UnixTimestamp::const_new(
+            (DAYS_CCSDS_TO_UNIX as i64 + 1) * SECONDS_PER_DAY as i64,
+            500,
+        );
+        let stamp1 = TimeProvider::from_unix_secs_with_u16_days(&unix_stamp).unwrap();
+        assert_eq!(stamp0, stamp1);
+        assert_eq!(stamp0.cmp(&stamp1), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut stamps = [
+            TimeProvider::new_with_u16_days(1, 0),
+            TimeProvider::new_with_u16_days(0, 50000),
+            TimeProvider::new_with_u16_days(0, 0),
+        ];
+        stamps.sort();
+        assert_eq!(stamps[0], TimeProvider::new_with_u16_days(0, 0));
+        assert_eq!(stamps[1], TimeProvider::new_with_u16_days(0, 50000));
+        assert_eq!(stamps[2], TimeProvider::new_with_u16_days(1, 0));
+    }
+
+    #[test]
+    fn test_try_new() {
+        let stamp = TimeProvider::try_new_with_u16_days(0, MS_PER_DAY - 1).unwrap();
+        assert_eq!(stamp.ms_of_day(), MS_PER_DAY - 1);
+        assert_eq!(
+            TimeProvider::try_new_with_u16_days(0, 86_400_000).unwrap_err(),
+            CdsError::InvalidMsOfDay(86_400_000)
+        );
+        let stamp = TimeProvider::try_new_with_u24_days(u16::MAX as u32 + 1, 0).unwrap();
+        assert_eq!(stamp.ccsds_days_as_u32(), u16::MAX as u32 + 1);
+        assert_eq!(
+            TimeProvider::try_new_with_u24_days(0, 86_400_000).unwrap_err(),
+            CdsError::InvalidMsOfDay(86_400_000)
+        );
+        assert_eq!(
+            TimeProvider::try_new_with_u24_days(MAX_DAYS_24_BITS + 1, 0).unwrap_err(),
+            CdsError::InvalidCcsdsDays(MAX_DAYS_24_BITS as i64 + 1)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_iso8601() {
+        let stamp = TimeProvider::from_iso8601("1970-01-01T03:25:45.678Z").unwrap();
+        assert_eq!(stamp.ccsds_days(), 4383);
+        assert_eq!(stamp.ms_of_day(), 12_345_678);
+        assert_eq!(stamp.to_iso8601().unwrap(), "1970-01-01T03:25:45.678Z");
+        let stamp = TimeProvider::from_iso8601("1970-01-01T04:25:45.678+01:00").unwrap();
+        assert_eq!(stamp.ms_of_day(), 12_345_678);
+        assert_eq!(
+            TimeProvider::from_iso8601("1970-01-01 03:25").unwrap_err(),
+            TimestampError::InvalidDateTimeString
+        );
+        assert!(matches!(
+            TimeProvider::from_iso8601("1957-12-31T23:59:59Z").unwrap_err(),
+            TimestampError::DateBeforeCcsdsEpoch(_)
+        ));
+        assert!(matches!(
+            TimeProvider::from_iso8601("2200-01-01T00:00:00Z").unwrap_err(),
+            TimestampError::CdsError(CdsError::InvalidCcsdsDays(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_system_time_round_trip() {
+        let system_time = SystemTime::UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456);
+        let stamp = TimeProvider::<DaysLen16Bits>::try_from(system_time).unwrap();
+        assert_eq!(stamp.unix_seconds(), 1_700_000_000);
+        assert_eq!(stamp.subsecond_millis(), Some(123));
+        let round_trip = SystemTime::from(stamp);
+        assert_eq!(
+            round_trip.duration_since(SystemTime::UNIX_EPOCH).unwrap(),
+            Duration::from_millis(1_700_000_000_123)
+        );
+        let stamp_u24 = TimeProvider::<DaysLen24Bits>::try_from(system_time).unwrap();
+        assert_eq!(SystemTime::from(stamp_u24), round_trip);
+        let before_unix_epoch = SystemTime::UNIX_EPOCH - Duration::from_secs(1);
+        assert!(matches!(
+            TimeProvider::<DaysLen16Bits>::try_from(before_unix_epoch).unwrap_err(),
+            StdTimestampError::SystemTimeError(_)
+        ));
+        // Exceeds the 16 bit days field.
+        let too_late = SystemTime::UNIX_EPOCH + Duration::from_secs(200 * 365 * 86400);
+        assert!(matches!(
+            TimeProvider::<DaysLen16Bits>::try_from(too_late).unwrap_err(),
+            StdTimestampError::TimestampError(TimestampError::CdsError(
+                CdsError::InvalidCcsdsDays(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_ms_of_day_normalization() {
+        let stamp = TimeProvider::new_with_u16_days(20000, MS_PER_DAY - 1);
+        assert_eq!(stamp.ccsds_days(), 20000);
+        assert_eq!(stamp.ms_of_day(), MS_PER_DAY - 1);
+        let stamp = TimeProvider::new_with_u16_days(20000, MS_PER_DAY + 1);
+        assert_eq!(stamp.ccsds_days(), 20001);
+        assert_eq!(stamp.ms_of_day(), 1);
+        assert_eq!(
+            stamp.unix_seconds(),
+            TimeProvider::new_with_u16_days(20001, 1).unix_seconds()
+        );
+        let mut buf = [0; 16];
+        stamp.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(u32::from_be_bytes(buf[3..7].try_into().unwrap()), 1);
+        // The days field can not be incremented anymore, so the timestamp saturates instead of
+        // wrapping around to the CCSDS epoch.
+        let stamp = TimeProvider::new_with_u16_days(u16::MAX, MS_PER_DAY);
+        assert_eq!(stamp.ccsds_days(), u16::MAX);
+        assert_eq!(stamp.ms_of_day(), MS_PER_DAY - 1);
+        let stamp = TimeProvider::new_with_u16_days(u16::MAX - 1, u32::MAX);
+        assert_eq!(stamp.ccsds_days(), u16::MAX);
+        assert_eq!(stamp.ms_of_day(), MS_PER_DAY - 1);
+        let stamp = TimeProvider::new_with_u16_days(u16::MAX - 1, MS_PER_DAY + 1);
+        assert_eq!(stamp.ccsds_days(), u16::MAX);
+        assert_eq!(stamp.ms_of_day(), 1);
+        let stamp = TimeProvider::new_with_u24_days(20000, 3 * MS_PER_DAY + 5).unwrap();
+        assert_eq!(stamp.ccsds_days_as_u32(), 20003);
+        assert_eq!(stamp.ms_of_day(), 5);
+        assert_eq!(
+            TimeProvider::new_with_u24_days(MAX_DAYS_24_BITS, MS_PER_DAY).unwrap_err(),
+            CdsError::InvalidCcsdsDays(MAX_DAYS_24_BITS as i64 + 1)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_try_date_time() {
+        let stamp = TimeProvider::new_with_u16_days(0, MS_PER_DAY - 1);
+        assert_eq!(stamp.try_date_time().unwrap(), stamp.date_time().unwrap());
+        let mut stamp = TimeProvider::new_with_u16_days(0, 0);
+        stamp.ms_of_day = MS_PER_DAY;
+        assert_eq!(
+            stamp.try_date_time().unwrap_err(),
+            CdsError::InvalidMsOfDay(MS_PER_DAY)
+        );
+        assert!(stamp.date_time().is_none());
+        let mut stamp = TimeProvider::new_with_u16_days(0, MS_PER_DAY - 1);
+        stamp.set_submillis_precision(SubmillisPrecision::Microseconds(5000));
+        assert_eq!(
+            stamp.try_date_time().unwrap_err(),
+            CdsError::InvalidSubmillisPrecision(SubmillisPrecision::Microseconds(5000))
+        );
+        assert!(stamp.date_time().is_none());
+    }
+
+    #[test]
+    fn test_duration_since() {
+        let stamp0 = TimeProvider::new_with_u16_days(1, MS_PER_DAY - 500);
+        let mut stamp1 = TimeProvider::new_with_u16_days(2, 1000);
+        assert_eq!(
+            stamp1.duration_since(&stamp0),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(stamp0.duration_since(&stamp0), Some(Duration::ZERO));
+        assert!(stamp0.duration_since(&stamp1).is_none());
+        stamp1.set_submillis_precision(SubmillisPrecision::Microseconds(250));
+        assert_eq!(
+            stamp1.duration_since(&stamp0),
+            Some(Duration::from_micros(1_500_250))
+        );
+    }
+
+    #[test]
+    fn test_duration_since_u24_days() {
+        let stamp0 = TimeProvider::new_with_u24_days(0x108020, 1000).unwrap();
+        let mut stamp1 = TimeProvider::new_with_u24_days(0x108021, 500).unwrap();
+        assert_eq!(
+            stamp1.duration_since(&stamp0),
+            Some(Duration::from_millis(MS_PER_DAY as u64 - 500))
+        );
+        assert!(stamp0.duration_since(&stamp1).is_none());
+        stamp1.set_submillis_precision(SubmillisPrecision::Picoseconds(1_500_000));
+        assert_eq!(
+            stamp1.duration_since(&stamp0),
+            Some(Duration::from_nanos(
+                (MS_PER_DAY as u64 - 500) * 1_000_000 + 1500
+            ))
+        );
+        let epoch = TimeProvider::new_with_u24_days(0, 0).unwrap();
+        let max = TimeProvider::new_with_u24_days(MAX_DAYS_24_BITS, MS_PER_DAY - 1).unwrap();
+        assert_eq!(
+            max.duration_since(&epoch),
+            Some(Duration::from_millis(
+                MAX_DAYS_24_BITS as u64 * MS_PER_DAY as u64 + MS_PER_DAY as u64 - 1
+            ))
+        );
+    }
+
     #[test]
     fn test_conversion() {
         let mut stamp_small = TimeProvider::new_with_u16_days(u16::MAX, 500);
@@ -2233,7 +3061,7 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "serde")]
+    #[cfg(all(feature = "serde", feature = "std"))]
     fn test_serialization() {
         let stamp_now = TimeProvider::from_now_with_u16_days().expect("Error retrieving time");
         let val = to_allocvec(&stamp_now).expect("Serializing timestamp failed");
@@ -2242,6 +3070,19 @@ mod tests {
         assert_eq!(stamp_deser, stamp_now);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialization_u24_days() {
+        let stamp = TimeProvider::new_with_u24_days(u16::MAX as u32 + 1, 500).unwrap();
+        let val = to_allocvec(&stamp).expect("Serializing timestamp failed");
+        let stamp_deser: TimeProvider<DaysLen24Bits> =
+            from_bytes(&val).expect("Stamp deserialization failed");
+        assert_eq!(stamp_deser, stamp);
+        assert_eq!(stamp_deser.unix_stamp(), stamp.unix_stamp());
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg(feature = "chrono")]
     fn generic_dt_property_equality_check(first: u32, second: u32, start: u32, end: u32) {
         if second < first {
             assert_eq!(second, start);
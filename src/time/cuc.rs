@@ -1,8 +1,11 @@
 //! Module to generate or read CCSDS Unsegmented (CUC) timestamps as specified in
 //! [CCSDS 301.0-B-4](https://public.ccsds.org/Pubs/301x0b4e1.pdf) section 3.2 .
 //!
-//! The core data structure to do this is the [TimeProviderCcsdsEpoch] struct.
+//! The core data structure to do this is the [TimeProviderCcsdsEpoch] struct. Timestamps
+//! relative to an agency-defined epoch are supported by the [TimeProviderAgencyEpoch] struct,
+//! which requires the `chrono` feature.
 use super::*;
+#[cfg(feature = "chrono")]
 use chrono::Datelike;
 use core::fmt::Debug;
 use core::ops::{Add, AddAssign};
@@ -12,6 +15,8 @@ const MIN_CUC_LEN: usize = 2;
 
 /// Base value for the preamble field for a time field parser to determine the time field type.
 pub const P_FIELD_BASE: u8 = (CcsdsTimeCodes::CucCcsdsEpoch as u8) << 4;
+/// Base value for the preamble field of CUC timestamps using an agency-defined epoch.
+pub const P_FIELD_BASE_AGENCY_EPOCH: u8 = (CcsdsTimeCodes::CucAgencyEpoch as u8) << 4;
 /// Maximum length if the preamble field is not extended.
 pub const MAX_CUC_LEN_SMALL_PREAMBLE: usize = 8;
 
@@ -42,14 +47,15 @@ impl TryFrom<u8> for FractionalResolution {
     }
 }
 
-/// Please note that this function will panic if the fractional value is not smaller than
-/// the maximum number of fractions allowed for the particular resolution.
-/// (e.g. passing 270 when the resolution only allows 255 values).
+/// Convert the fractional part to nanoseconds. One fraction is 1/2^(8 * n) seconds for a
+/// resolution of n bytes, so the result is always smaller than one second. Fractional values
+/// which do not fit into the resolution are clamped to the largest valid value.
 #[inline]
 pub fn convert_fractional_part_to_ns(fractional_part: FractionalPart) -> u64 {
-    let div = fractional_res_to_div(fractional_part.0);
-    assert!(fractional_part.1 < div);
-    10_u64.pow(9) * fractional_part.1 as u64 / div as u64
+    let fractions = fractional_part
+        .1
+        .min(fractional_res_to_div(fractional_part.0)) as u64;
+    (10_u64.pow(9) * fractions) >> (8 * fractional_part.0 as u32)
 }
 
 #[inline(always)]
@@ -97,6 +103,9 @@ pub enum CucError {
     /// Invalid counter supplied.
     InvalidCounter(u8, u64),
     InvalidFractions(FractionalResolution, u64),
+    /// The second octet of an extended p-field specifies additional counter or fractional part
+    /// octets or a further extension, which is not supported. Contains the second octet.
+    UnsupportedPfieldExtension(u8),
 }
 
 impl Display for CucError {
@@ -114,6 +123,9 @@ impl Display for CucError {
             CucError::InvalidFractions(w, c) => {
                 write!(f, "invalid cuc fractional part {c} for width {w:?}")
             }
+            CucError::UnsupportedPfieldExtension(ext) => {
+                write!(f, "unsupported cuc p-field extension octet {ext:#010b}")
+            }
         }
     }
 }
@@ -121,12 +133,15 @@ impl Display for CucError {
 #[cfg(feature = "std")]
 impl Error for CucError {}
 
+/// Byte width of the counter, which can be 1 to 4 bytes, and the counter value.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct WidthCounterPair(u8, u32);
+pub struct WidthCounterPair(pub u8, pub u32);
+/// Resolution of the fractional part, which also determines its byte width, and the fractional
+/// value.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct FractionalPart(FractionalResolution, u32);
+pub struct FractionalPart(pub FractionalResolution, pub u32);
 
 /// This object is the abstraction for the CCSDS Unsegmented Time Code (CUC) using the CCSDS epoch
 /// and a small preamble field.
@@ -179,7 +194,22 @@ pub fn pfield_len(pfield: u8) -> usize {
     1
 }
 
+#[inline]
+fn set_p_field_extension(pfield: &mut u8, extended: bool) {
+    if extended {
+        *pfield |= 1 << 7;
+    } else {
+        *pfield &= !(1 << 7);
+    }
+}
+
 impl TimeProviderCcsdsEpoch {
+    /// Length of a timestamp with a one byte counter and no fractional part.
+    pub const MIN_LEN: usize = MIN_CUC_LEN;
+    /// Length of a timestamp with an extended p-field, a four byte counter and a three byte
+    /// fractional part.
+    pub const MAX_LEN: usize = MAX_CUC_LEN_SMALL_PREAMBLE + 1;
+
     /// Create a time provider with a four byte counter and no fractional part.
     pub fn new(counter: u32) -> Self {
         // These values are definitely valid, so it is okay to unwrap here.
@@ -263,21 +293,47 @@ impl TimeProviderCcsdsEpoch {
         Ok(())
     }
 
+    /// Create a time provider from a [`DateTime<Utc>`] struct. The counter is set to the seconds
+    /// elapsed since the CCSDS epoch (1958-01-01T00:00:00+00:00).
+    ///
+    /// ## Errors
+    ///
+    /// This function will return [TimestampError::DateBeforeCcsdsEpoch] if the time is before
+    /// the CCSDS epoch or a [TimestampError::CucError] if the elapsed seconds do not fit into the
+    /// four byte counter.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn from_date_time(
         dt: &DateTime<Utc>,
         res: FractionalResolution,
     ) -> Result<Self, TimestampError> {
         // Year before CCSDS epoch is invalid.
         if dt.year() < 1958 {
-            return Err(TimestampError::DateBeforeCcsdsEpoch(*dt));
+            return Err(TimestampError::DateBeforeCcsdsEpoch(UnixTimestamp::from(
+                *dt,
+            )));
+        }
+        let ccsds_epoch = unix_epoch_to_ccsds_epoch(dt.timestamp());
+        if ccsds_epoch > u32::MAX as i64 {
+            return Err(CucError::InvalidCounter(4, ccsds_epoch as u64).into());
         }
         Self::new_generic(
-            WidthCounterPair(4, dt.timestamp() as u32),
+            WidthCounterPair(4, ccsds_epoch as u32),
             fractional_part_from_subsec_ns(res, dt.timestamp_subsec_nanos() as u64),
         )
         .map_err(|e| e.into())
     }
 
+    /// Like [Self::from_date_time] but with a [time::OffsetDateTime](::time::OffsetDateTime).
+    #[cfg(all(feature = "time", feature = "chrono"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "time", feature = "chrono"))))]
+    pub fn from_offset_date_time(
+        dt: &::time::OffsetDateTime,
+        res: FractionalResolution,
+    ) -> Result<Self, TimestampError> {
+        Self::from_date_time(&date_time_from_offset_date_time(dt), res)
+    }
+
     pub fn from_unix_stamp(
         unix_stamp: &UnixTimestamp,
         res: FractionalResolution,
@@ -285,9 +341,7 @@ impl TimeProviderCcsdsEpoch {
         let ccsds_epoch = unix_epoch_to_ccsds_epoch(unix_stamp.unix_seconds);
         // Negative CCSDS epoch is invalid.
         if ccsds_epoch < 0 {
-            return Err(TimestampError::DateBeforeCcsdsEpoch(
-                unix_stamp.as_date_time().unwrap(),
-            ));
+            return Err(TimestampError::DateBeforeCcsdsEpoch(*unix_stamp));
         }
         if ccsds_epoch > u32::MAX as i64 {
             return Err(CucError::InvalidCounter(4, ccsds_epoch as u64).into());
@@ -299,6 +353,49 @@ impl TimeProviderCcsdsEpoch {
         Self::new_generic(WidthCounterPair(4, ccsds_epoch as u32), fractions).map_err(|e| e.into())
     }
 
+    /// Create a time provider from the time represented by a CDS timestamp. The counter width is
+    /// set to 4 bytes.
+    ///
+    /// Both time codes use the CCSDS epoch, so the seconds since the epoch and the sub-second
+    /// nanoseconds are calculated from the CDS days, milliseconds of day and sub-millisecond
+    /// fields. The sub-second part is truncated to the given fractional resolution.
+    ///
+    /// ## Errors
+    ///
+    /// This function will return a [TimestampError::CucError] if the elapsed seconds do not fit
+    /// into the four byte counter, which is possible for CDS timestamps after the year 2094, or a
+    /// [TimestampError::CdsError] for invalid CDS field values.
+    pub fn from_cds(
+        cds: &impl cds::CdsCommon,
+        res: FractionalResolution,
+    ) -> Result<Self, TimestampError> {
+        if cds.ms_of_day() >= MS_PER_DAY {
+            return Err(cds::CdsError::InvalidMsOfDay(cds.ms_of_day()).into());
+        }
+        let submilli_ns = match cds.submillis_precision() {
+            Some(cds::SubmillisPrecision::Microseconds(us)) if us < 1000 => us as u64 * 1000,
+            Some(cds::SubmillisPrecision::Picoseconds(ps)) if ps < 10_u32.pow(9) => {
+                ps as u64 / 1000
+            }
+            Some(
+                prec @ (cds::SubmillisPrecision::Microseconds(_)
+                | cds::SubmillisPrecision::Picoseconds(_)),
+            ) => return Err(cds::CdsError::InvalidSubmillisPrecision(prec).into()),
+            _ => 0,
+        };
+        let seconds_since_epoch = cds.ccsds_days_as_u32() as u64 * SECONDS_PER_DAY as u64
+            + (cds.ms_of_day() / 1000) as u64;
+        if seconds_since_epoch > u32::MAX as u64 {
+            return Err(CucError::InvalidCounter(4, seconds_since_epoch).into());
+        }
+        let subsec_ns = (cds.ms_of_day() % 1000) as u64 * 10_u64.pow(6) + submilli_ns;
+        Self::new_generic(
+            WidthCounterPair(4, seconds_since_epoch as u32),
+            fractional_part_from_subsec_ns(res, subsec_ns),
+        )
+        .map_err(|e| e.into())
+    }
+
     pub fn new_u16_counter(counter: u16) -> Self {
         // These values are definitely valid, so it is okay to unwrap here.
         Self::new_generic(WidthCounterPair(2, counter as u32), None).unwrap()
@@ -312,6 +409,17 @@ impl TimeProviderCcsdsEpoch {
         self.fractions
     }
 
+    /// Smallest representable time step in nanoseconds for the fractional resolution of the
+    /// timestamp. This is one second if there is no fractional part.
+    pub fn resolution_nanos(&self) -> u64 {
+        match self.fractions {
+            Some(fractions) if fractions.0 != FractionalResolution::Seconds => {
+                10_u64.pow(9) / fractional_res_to_div(fractions.0) as u64
+            }
+            _ => 10_u64.pow(9),
+        }
+    }
+
     pub fn set_fractions(&mut self, fractions: FractionalPart) -> Result<(), CucError> {
         Self::verify_fractions_width(fractions.0)?;
         Self::verify_fractions_value(fractions)?;
@@ -320,6 +428,13 @@ impl TimeProviderCcsdsEpoch {
         Ok(())
     }
 
+    /// Enable or disable the extension of the p-field by a second octet. The second octet does
+    /// not specify additional counter or fractional part octets, so only the timestamp length
+    /// changes.
+    pub fn set_p_field_extension(&mut self, extended: bool) {
+        set_p_field_extension(&mut self.pfield, extended);
+    }
+
     /// Set a fractional resolution. Please note that this function will reset the fractional value
     /// to 0 if the resolution changes.
     pub fn set_fractional_resolution(&mut self, res: FractionalResolution) {
@@ -337,6 +452,15 @@ impl TimeProviderCcsdsEpoch {
         }
     }
 
+    /// Create a time provider with a custom counter width and an optional fractional part. The
+    /// resulting timestamp length is one byte for the p-field plus the counter width plus the
+    /// width of the fractional part.
+    ///
+    /// ## Errors
+    ///
+    /// This function returns [CucError::InvalidCounterWidth] if the counter width is not in the
+    /// range 1 to 4 and [CucError::InvalidCounter] or [CucError::InvalidFractions] if the counter
+    /// or the fractional value do not fit into the respective width.
     pub fn new_generic(
         counter: WidthCounterPair,
         fractions: Option<FractionalPart>,
@@ -407,7 +531,7 @@ impl TimeProviderCcsdsEpoch {
     /// counter, the second value is the byte width of the fractional part, and the third
     /// components is the total size.
     pub fn len_components_and_total_from_pfield(pfield: u8) -> (u8, u8, usize) {
-        let base_len: usize = 1;
+        let base_len = pfield_len(pfield);
         let cntr_len = Self::len_cntr_from_pfield(pfield);
         let fractions_len = Self::len_fractions_from_pfield(pfield);
         (
@@ -418,7 +542,7 @@ impl TimeProviderCcsdsEpoch {
     }
 
     pub fn len_packed_from_pfield(pfield: u8) -> usize {
-        let mut base_len: usize = 1;
+        let mut base_len = pfield_len(pfield);
         base_len += Self::len_cntr_from_pfield(pfield) as usize;
         base_len += Self::len_fractions_from_pfield(pfield) as usize;
         base_len
@@ -447,135 +571,169 @@ impl TimeProviderCcsdsEpoch {
     }
 }
 
-impl TimeReader for TimeProviderCcsdsEpoch {
-    fn from_bytes(buf: &[u8]) -> Result<Self, TimestampError>
-    where
-        Self: Sized,
-    {
-        if buf.len() < MIN_CUC_LEN {
-            return Err(TimestampError::ByteConversionError(
-                ByteConversionError::FromSliceTooSmall(SizeMissmatch {
-                    expected: MIN_CUC_LEN,
-                    found: buf.len(),
-                }),
-            ));
-        }
-        match ccsds_time_code_from_p_field(buf[0]) {
-            Ok(code) => {
-                if code != CcsdsTimeCodes::CucCcsdsEpoch {
-                    return Err(TimestampError::InvalidTimeCode(
-                        CcsdsTimeCodes::CucCcsdsEpoch,
-                        code as u8,
-                    ));
-                }
-            }
-            Err(raw) => {
+/// Reads the counter and the fractional part of a CUC timestamp after checking the time code
+/// of the p-field against the expected time code.
+fn read_cuc_fields(
+    buf: &[u8],
+    expected_time_code: CcsdsTimeCodes,
+) -> Result<(WidthCounterPair, Option<FractionalPart>), TimestampError> {
+    if buf.len() < MIN_CUC_LEN {
+        return Err(TimestampError::ByteConversionError(
+            ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                expected: MIN_CUC_LEN,
+                found: buf.len(),
+            }),
+        ));
+    }
+    match ccsds_time_code_from_p_field(buf[0]) {
+        Ok(code) => {
+            if code != expected_time_code {
                 return Err(TimestampError::InvalidTimeCode(
-                    CcsdsTimeCodes::CucCcsdsEpoch,
-                    raw,
-                ))
+                    expected_time_code,
+                    code as u8,
+                ));
             }
         }
-        let (cntr_len, fractions_len, total_len) =
-            Self::len_components_and_total_from_pfield(buf[0]);
-        if buf.len() < total_len {
-            return Err(TimestampError::ByteConversionError(
-                ByteConversionError::FromSliceTooSmall(SizeMissmatch {
-                    expected: total_len,
-                    found: buf.len(),
-                }),
-            ));
+        Err(raw) => return Err(TimestampError::InvalidTimeCode(expected_time_code, raw)),
+    }
+    // Only the second octet of the p-field which does not specify any additional octets is
+    // supported. The reserved bits are ignored.
+    if pfield_len(buf[0]) == 2 && buf[1] & 0b1111_1100 != 0 {
+        return Err(CucError::UnsupportedPfieldExtension(buf[1]).into());
+    }
+    let (cntr_len, fractions_len, total_len) =
+        TimeProviderCcsdsEpoch::len_components_and_total_from_pfield(buf[0]);
+    if buf.len() < total_len {
+        return Err(TimestampError::ByteConversionError(
+            ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                expected: total_len,
+                found: buf.len(),
+            }),
+        ));
+    }
+    let mut current_idx = pfield_len(buf[0]);
+    let counter = match cntr_len {
+        1 => buf[current_idx] as u32,
+        2 => u16::from_be_bytes(buf[current_idx..current_idx + 2].try_into().unwrap()) as u32,
+        3 => {
+            let mut tmp_buf: [u8; 4] = [0; 4];
+            tmp_buf[1..4].copy_from_slice(&buf[current_idx..current_idx + 3]);
+            u32::from_be_bytes(tmp_buf)
         }
-        let mut current_idx = 1;
-        let counter = match cntr_len {
-            1 => buf[current_idx] as u32,
-            2 => u16::from_be_bytes(buf[current_idx..current_idx + 2].try_into().unwrap()) as u32,
+        4 => u32::from_be_bytes(buf[current_idx..current_idx + 4].try_into().unwrap()),
+        _ => panic!("unreachable match arm"),
+    };
+    current_idx += cntr_len as usize;
+    let mut fractions = None;
+    if fractions_len > 0 {
+        match fractions_len {
+            1 => {
+                fractions = Some(FractionalPart(
+                    fractions_len.try_into().unwrap(),
+                    buf[current_idx] as u32,
+                ))
+            }
+            2 => {
+                fractions = Some(FractionalPart(
+                    fractions_len.try_into().unwrap(),
+                    u16::from_be_bytes(buf[current_idx..current_idx + 2].try_into().unwrap())
+                        as u32,
+                ))
+            }
             3 => {
                 let mut tmp_buf: [u8; 4] = [0; 4];
                 tmp_buf[1..4].copy_from_slice(&buf[current_idx..current_idx + 3]);
-                u32::from_be_bytes(tmp_buf)
+                fractions = Some(FractionalPart(
+                    fractions_len.try_into().unwrap(),
+                    u32::from_be_bytes(tmp_buf),
+                ))
             }
-            4 => u32::from_be_bytes(buf[current_idx..current_idx + 4].try_into().unwrap()),
             _ => panic!("unreachable match arm"),
-        };
-        current_idx += cntr_len as usize;
-        let mut fractions = None;
-        if fractions_len > 0 {
-            match fractions_len {
-                1 => {
-                    fractions = Some(FractionalPart(
-                        fractions_len.try_into().unwrap(),
-                        buf[current_idx] as u32,
-                    ))
-                }
-                2 => {
-                    fractions = Some(FractionalPart(
-                        fractions_len.try_into().unwrap(),
-                        u16::from_be_bytes(buf[current_idx..current_idx + 2].try_into().unwrap())
-                            as u32,
-                    ))
-                }
-                3 => {
-                    let mut tmp_buf: [u8; 4] = [0; 4];
-                    tmp_buf[1..4].copy_from_slice(&buf[current_idx..current_idx + 3]);
-                    fractions = Some(FractionalPart(
-                        fractions_len.try_into().unwrap(),
-                        u32::from_be_bytes(tmp_buf),
-                    ))
-                }
-                _ => panic!("unreachable match arm"),
-            }
         }
-        let provider = Self::new_generic(WidthCounterPair(cntr_len, counter), fractions)?;
+    }
+    Ok((WidthCounterPair(cntr_len, counter), fractions))
+}
+
+impl TimeReader for TimeProviderCcsdsEpoch {
+    fn from_bytes(buf: &[u8]) -> Result<Self, TimestampError>
+    where
+        Self: Sized,
+    {
+        let (counter, fractions) = read_cuc_fields(buf, CcsdsTimeCodes::CucCcsdsEpoch)?;
+        let mut provider = Self::new_generic(counter, fractions)?;
+        provider.set_p_field_extension(pfield_len(buf[0]) == 2);
         Ok(provider)
     }
 }
 
-impl TimeWriter for TimeProviderCcsdsEpoch {
-    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
-        // Cross check the sizes of the counters against byte widths in the ctor
-        if bytes.len() < self.len_as_bytes() {
-            return Err(TimestampError::ByteConversionError(
-                ByteConversionError::ToSliceTooSmall(SizeMissmatch {
-                    found: bytes.len(),
-                    expected: self.len_as_bytes(),
-                }),
-            ));
+/// Writes a CUC timestamp consisting of the p-field, the counter and the optional fractional
+/// part.
+fn write_cuc_fields(
+    pfield: u8,
+    counter: WidthCounterPair,
+    fractions: Option<FractionalPart>,
+    bytes: &mut [u8],
+) -> Result<usize, TimestampError> {
+    let len_packed = TimeProviderCcsdsEpoch::len_packed_from_pfield(pfield);
+    // Cross check the sizes of the counters against byte widths in the ctor
+    if bytes.len() < len_packed {
+        return Err(TimestampError::ByteConversionError(
+            ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                found: bytes.len(),
+                expected: len_packed,
+            }),
+        ));
+    }
+    bytes[0] = pfield;
+    let mut current_idx = pfield_len(pfield);
+    if current_idx == 2 {
+        bytes[1] = 0;
+    }
+    match counter.0 {
+        1 => {
+            bytes[current_idx] = counter.1 as u8;
         }
-        bytes[0] = self.pfield;
-        let mut current_idx: usize = 1;
-        match self.counter.0 {
-            1 => {
-                bytes[current_idx] = self.counter.1 as u8;
-            }
-            2 => {
-                bytes[current_idx..current_idx + 2]
-                    .copy_from_slice(&(self.counter.1 as u16).to_be_bytes());
-            }
-            3 => {
-                bytes[current_idx..current_idx + 3]
-                    .copy_from_slice(&self.counter.1.to_be_bytes()[1..4]);
-            }
-            4 => {
-                bytes[current_idx..current_idx + 4].copy_from_slice(&self.counter.1.to_be_bytes());
-            }
-            // Should never happen
-            _ => panic!("invalid counter width value"),
+        2 => {
+            bytes[current_idx..current_idx + 2].copy_from_slice(&(counter.1 as u16).to_be_bytes());
         }
-        current_idx += self.counter.0 as usize;
-        if let Some(fractions) = self.fractions {
-            match fractions.0 {
-                FractionalResolution::FourMs => bytes[current_idx] = fractions.1 as u8,
-                FractionalResolution::FifteenUs => bytes[current_idx..current_idx + 2]
-                    .copy_from_slice(&(fractions.1 as u16).to_be_bytes()),
-                FractionalResolution::SixtyNs => bytes[current_idx..current_idx + 3]
-                    .copy_from_slice(&fractions.1.to_be_bytes()[1..4]),
-                // Should also never happen
-                _ => panic!("invalid fractions value"),
-            }
-            current_idx += fractions.0 as usize;
+        3 => {
+            bytes[current_idx..current_idx + 3].copy_from_slice(&counter.1.to_be_bytes()[1..4]);
         }
-        Ok(current_idx)
+        4 => {
+            bytes[current_idx..current_idx + 4].copy_from_slice(&counter.1.to_be_bytes());
+        }
+        // Should never happen
+        _ => panic!("invalid counter width value"),
+    }
+    current_idx += counter.0 as usize;
+    if let Some(fractions) = fractions {
+        match fractions.0 {
+            FractionalResolution::FourMs => bytes[current_idx] = fractions.1 as u8,
+            FractionalResolution::FifteenUs => bytes[current_idx..current_idx + 2]
+                .copy_from_slice(&(fractions.1 as u16).to_be_bytes()),
+            FractionalResolution::SixtyNs => bytes[current_idx..current_idx + 3]
+                .copy_from_slice(&fractions.1.to_be_bytes()[1..4]),
+            // Should also never happen
+            _ => panic!("invalid fractions value"),
+        }
+        current_idx += fractions.0 as usize;
+    }
+    Ok(current_idx)
+}
+
+impl TimeWriter for TimeProviderCcsdsEpoch {
+    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
+        write_cuc_fields(self.pfield, self.counter, self.fractions, bytes)
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn iso8601_seconds_format_for_fractions(fractions: Option<FractionalPart>) -> SecondsFormat {
+    match fractions.map(|fractions| fractions.0) {
+        None | Some(FractionalResolution::Seconds) => SecondsFormat::Secs,
+        Some(FractionalResolution::FourMs) => SecondsFormat::Millis,
+        Some(FractionalResolution::FifteenUs) => SecondsFormat::Micros,
+        Some(FractionalResolution::SixtyNs) => SecondsFormat::Nanos,
     }
 }
 
@@ -585,10 +743,10 @@ impl CcsdsTimeProvider for TimeProviderCcsdsEpoch {
     }
 
     fn p_field(&self) -> (usize, [u8; 2]) {
-        (1, [self.pfield, 0])
+        (pfield_len(self.pfield), [self.pfield, 0])
     }
 
-    fn ccdsd_time_code(&self) -> CcsdsTimeCodes {
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
         CcsdsTimeCodes::CucCcsdsEpoch
     }
 
@@ -607,6 +765,16 @@ impl CcsdsTimeProvider for TimeProviderCcsdsEpoch {
         None
     }
 
+    fn unix_nanos(&self) -> i128 {
+        let ns = if let Some(fractional_part) = self.fractions {
+            convert_fractional_part_to_ns(fractional_part)
+        } else {
+            0
+        };
+        self.unix_seconds() as i128 * 10_i128.pow(9) + ns as i128
+    }
+
+    #[cfg(feature = "chrono")]
     fn date_time(&self) -> Option<DateTime<Utc>> {
         let unix_seconds = self.unix_seconds();
         let ns = if let Some(fractional_part) = self.fractions {
@@ -619,6 +787,11 @@ impl CcsdsTimeProvider for TimeProviderCcsdsEpoch {
         }
         None
     }
+
+    #[cfg(feature = "chrono")]
+    fn iso8601_seconds_format(&self) -> SecondsFormat {
+        iso8601_seconds_format_for_fractions(self.fractions)
+    }
 }
 
 fn get_provider_values_after_duration_addition(
@@ -710,18 +883,221 @@ impl Add<Duration> for &TimeProviderCcsdsEpoch {
     }
 }
 
+/// The sub-millisecond precision of the CDS timestamp is converted to the finest fractional
+/// resolution of ~60 ns. See [TimeProviderCcsdsEpoch::from_cds] for more details.
+impl TryFrom<cds::TimeProvider<cds::DaysLen16Bits>> for TimeProviderCcsdsEpoch {
+    type Error = TimestampError;
+
+    fn try_from(value: cds::TimeProvider<cds::DaysLen16Bits>) -> Result<Self, Self::Error> {
+        Self::from_cds(&value, FractionalResolution::SixtyNs)
+    }
+}
+
+/// The sub-millisecond precision of the CDS timestamp is converted to the finest fractional
+/// resolution of ~60 ns. See [TimeProviderCcsdsEpoch::from_cds] for more details.
+impl TryFrom<cds::TimeProvider<cds::DaysLen24Bits>> for TimeProviderCcsdsEpoch {
+    type Error = TimestampError;
+
+    fn try_from(value: cds::TimeProvider<cds::DaysLen24Bits>) -> Result<Self, Self::Error> {
+        Self::from_cds(&value, FractionalResolution::SixtyNs)
+    }
+}
+
+/// CCSDS days and milliseconds of day of the time represented by a CUC timestamp, rounded to the
+/// nearest millisecond.
+fn cds_fields_from_cuc(cuc: &TimeProviderCcsdsEpoch) -> (u32, u32) {
+    let subsec_ns = cuc
+        .fractions
+        .filter(|fractions| fractions.0 != FractionalResolution::Seconds)
+        .map_or(0, convert_fractional_part_to_ns);
+    let ms_since_epoch =
+        cuc.counter.1 as u64 * 1000 + (subsec_ns + 10_u64.pow(6) / 2) / 10_u64.pow(6);
+    (
+        (ms_since_epoch / MS_PER_DAY as u64) as u32,
+        (ms_since_epoch % MS_PER_DAY as u64) as u32,
+    )
+}
+
+/// The CDS timestamp has a resolution of one millisecond, so the fractional part of the CUC
+/// timestamp is rounded to the nearest millisecond. Any finer resolution of the fractional part
+/// is lost.
+impl From<TimeProviderCcsdsEpoch> for cds::TimeProvider<cds::DaysLen16Bits> {
+    fn from(value: TimeProviderCcsdsEpoch) -> Self {
+        let (ccsds_days, ms_of_day) = cds_fields_from_cuc(&value);
+        // The four byte counter can not exceed the 16 bit days field.
+        Self::new_with_u16_days(ccsds_days as u16, ms_of_day)
+    }
+}
+
+/// The CDS timestamp has a resolution of one millisecond, so the fractional part of the CUC
+/// timestamp is rounded to the nearest millisecond. Any finer resolution of the fractional part
+/// is lost.
+impl From<TimeProviderCcsdsEpoch> for cds::TimeProvider<cds::DaysLen24Bits> {
+    fn from(value: TimeProviderCcsdsEpoch) -> Self {
+        let (ccsds_days, ms_of_day) = cds_fields_from_cuc(&value);
+        Self::new_with_u24_days(ccsds_days, ms_of_day)
+            .expect("four byte counter can not exceed the 24 bit days field")
+    }
+}
+
+/// CUC timestamp provider which uses an agency-defined epoch instead of the CCSDS epoch.
+///
+/// The counter and the fractional part are encoded like for [TimeProviderCcsdsEpoch], but the
+/// time code identification of the p-field is set to [CcsdsTimeCodes::CucAgencyEpoch]. The
+/// epoch itself is not part of the timestamp and needs to be known by both sides, for example
+/// the GPS epoch or the launch time of a mission. Absolute times are calculated by adding the
+/// counter and the fractional part to the epoch.
+#[cfg(feature = "chrono")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimeProviderAgencyEpoch {
+    epoch: DateTime<Utc>,
+    pfield: u8,
+    counter: WidthCounterPair,
+    fractions: Option<FractionalPart>,
+}
+
+#[cfg(feature = "chrono")]
+impl TimeProviderAgencyEpoch {
+    /// Create a time provider with a four byte counter and no fractional part.
+    pub fn new(epoch: DateTime<Utc>, counter: u32) -> Self {
+        // These values are definitely valid, so it is okay to unwrap here.
+        Self::new_generic(epoch, WidthCounterPair(4, counter), None).unwrap()
+    }
+
+    /// Like [TimeProviderAgencyEpoch::new] but allow to supply a fractional part as well.
+    pub fn new_with_fractions(
+        epoch: DateTime<Utc>,
+        counter: u32,
+        fractions: FractionalPart,
+    ) -> Result<Self, CucError> {
+        Self::new_generic(epoch, WidthCounterPair(4, counter), Some(fractions))
+    }
+
+    pub fn new_generic(
+        epoch: DateTime<Utc>,
+        counter: WidthCounterPair,
+        fractions: Option<FractionalPart>,
+    ) -> Result<Self, CucError> {
+        // Re-use the verification of the CCSDS epoch provider.
+        let ccsds_provider = TimeProviderCcsdsEpoch::new_generic(counter, fractions)?;
+        Ok(Self {
+            epoch,
+            pfield: P_FIELD_BASE_AGENCY_EPOCH | (ccsds_provider.pfield & 0b1111),
+            counter,
+            fractions,
+        })
+    }
+
+    /// Read a timestamp from a raw buffer. The agency epoch is not part of the timestamp and
+    /// needs to be supplied separately.
+    pub fn from_bytes_with_epoch(buf: &[u8], epoch: DateTime<Utc>) -> Result<Self, TimestampError> {
+        let (counter, fractions) = read_cuc_fields(buf, CcsdsTimeCodes::CucAgencyEpoch)?;
+        let mut provider = Self::new_generic(epoch, counter, fractions)?;
+        provider.set_p_field_extension(pfield_len(buf[0]) == 2);
+        Ok(provider)
+    }
+
+    pub fn epoch(&self) -> DateTime<Utc> {
+        self.epoch
+    }
+
+    /// See [TimeProviderCcsdsEpoch::set_p_field_extension].
+    pub fn set_p_field_extension(&mut self, extended: bool) {
+        set_p_field_extension(&mut self.pfield, extended);
+    }
+
+    pub fn width_counter_pair(&self) -> WidthCounterPair {
+        self.counter
+    }
+
+    pub fn width_fractions_pair(&self) -> Option<FractionalPart> {
+        self.fractions
+    }
+
+    /// Returns the UNIX seconds and the subsecond nanoseconds of the timestamp.
+    fn unix_seconds_and_subsec_nanos(&self) -> (i64, u32) {
+        let mut subsec_nanos = self.epoch.timestamp_subsec_nanos() as u64;
+        if let Some(fractions) = self.fractions {
+            subsec_nanos += convert_fractional_part_to_ns(fractions);
+        }
+        let sec_as_ns = 10_u64.pow(9);
+        (
+            self.epoch.timestamp() + self.counter.1 as i64 + (subsec_nanos / sec_as_ns) as i64,
+            (subsec_nanos % sec_as_ns) as u32,
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TimeWriter for TimeProviderAgencyEpoch {
+    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
+        write_cuc_fields(self.pfield, self.counter, self.fractions, bytes)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CcsdsTimeProvider for TimeProviderAgencyEpoch {
+    fn len_as_bytes(&self) -> usize {
+        TimeProviderCcsdsEpoch::len_packed_from_pfield(self.pfield)
+    }
+
+    fn p_field(&self) -> (usize, [u8; 2]) {
+        (pfield_len(self.pfield), [self.pfield, 0])
+    }
+
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
+        CcsdsTimeCodes::CucAgencyEpoch
+    }
+
+    fn unix_seconds(&self) -> i64 {
+        self.unix_seconds_and_subsec_nanos().0
+    }
+
+    fn subsecond_millis(&self) -> Option<u16> {
+        let subsec_millis = (self.unix_seconds_and_subsec_nanos().1 / 10_u32.pow(6)) as u16;
+        if subsec_millis == 0 {
+            return None;
+        }
+        Some(subsec_millis)
+    }
+
+    fn unix_nanos(&self) -> i128 {
+        let (unix_seconds, subsec_nanos) = self.unix_seconds_and_subsec_nanos();
+        unix_seconds as i128 * 10_i128.pow(9) + subsec_nanos as i128
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        let (unix_seconds, subsec_nanos) = self.unix_seconds_and_subsec_nanos();
+        if let LocalResult::Single(res) = Utc.timestamp_opt(unix_seconds, subsec_nanos) {
+            return Some(res);
+        }
+        None
+    }
+
+    #[cfg(feature = "chrono")]
+    fn iso8601_seconds_format(&self) -> SecondsFormat {
+        iso8601_seconds_format_for_fractions(self.fractions)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::time::cds::CdsCommon;
+    #[cfg(feature = "chrono")]
     use chrono::{Datelike, Timelike};
     #[allow(unused_imports)]
     use std::println;
 
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_basic_zero_epoch() {
         let zero_cuc = TimeProviderCcsdsEpoch::new(0);
         assert_eq!(zero_cuc.len_as_bytes(), 5);
-        assert_eq!(zero_cuc.ccdsd_time_code(), CcsdsTimeCodes::CucCcsdsEpoch);
+        assert_eq!(zero_cuc.ccsds_time_code(), CcsdsTimeCodes::CucCcsdsEpoch);
         let counter = zero_cuc.width_counter_pair();
         assert_eq!(counter.0, 4);
         assert_eq!(counter.1, 0);
@@ -738,6 +1114,172 @@ mod tests {
         assert_eq!(dt.second(), 0);
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_date_time() {
+        // 2023-01-14T16:49:30.123+00:00
+        let dt = Utc.timestamp_opt(1673714970, 123_000_000).unwrap();
+        let cuc = TimeProviderCcsdsEpoch::from_date_time(&dt, FractionalResolution::FifteenUs);
+        assert!(cuc.is_ok());
+        let cuc = cuc.unwrap();
+        assert_eq!(
+            cuc.width_counter_pair().1 as i64,
+            unix_epoch_to_ccsds_epoch(1673714970)
+        );
+        assert_eq!(cuc.unix_seconds(), 1673714970);
+        assert_eq!(cuc.subsecond_millis(), Some(122));
+        let dt_from_cuc = cuc.date_time().unwrap();
+        assert_eq!(dt_from_cuc.timestamp(), dt.timestamp());
+        assert!(dt.timestamp_subsec_nanos() - dt_from_cuc.timestamp_subsec_nanos() < 16_000);
+
+        let mut buf: [u8; 16] = [0; 16];
+        let written = cuc.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written, 7);
+        let read_back = TimeProviderCcsdsEpoch::from_bytes(&buf).unwrap();
+        assert_eq!(read_back, cuc);
+        assert_eq!(read_back.unix_seconds(), 1673714970);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_from_date_time_before_ccsds_epoch() {
+        let dt = Utc.timestamp_opt(-378691201, 0).unwrap();
+        let res = TimeProviderCcsdsEpoch::from_date_time(&dt, FractionalResolution::Seconds);
+        assert!(matches!(
+            res.unwrap_err(),
+            TimestampError::DateBeforeCcsdsEpoch(_)
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_agency_epoch() {
+        // GPS epoch: 1980-01-06T00:00:00+00:00
+        let gps_epoch = Utc.timestamp_opt(315964800, 0).unwrap();
+        let stamp = TimeProviderAgencyEpoch::new(gps_epoch, 1_000_000_000);
+        assert_eq!(stamp.ccsds_time_code(), CcsdsTimeCodes::CucAgencyEpoch);
+        assert_eq!(stamp.len_as_bytes(), 5);
+        assert_eq!(stamp.epoch(), gps_epoch);
+        assert_eq!(stamp.unix_seconds(), 315964800 + 1_000_000_000);
+        assert!(stamp.subsecond_millis().is_none());
+        let dt = stamp.date_time().unwrap();
+        assert_eq!(dt.year(), 2011);
+        assert_eq!(dt.month(), 9);
+        assert_eq!(dt.day(), 14);
+
+        let mut buf: [u8; 16] = [0; 16];
+        let written = stamp.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written, 5);
+        assert_eq!(
+            ccsds_time_code_from_p_field(buf[0]).unwrap(),
+            CcsdsTimeCodes::CucAgencyEpoch
+        );
+        assert_eq!((buf[0] >> 4) & 0b111, 0b010);
+        assert_eq!(
+            u32::from_be_bytes(buf[1..5].try_into().unwrap()),
+            1_000_000_000
+        );
+        let read_back = TimeProviderAgencyEpoch::from_bytes_with_epoch(&buf, gps_epoch).unwrap();
+        assert_eq!(read_back, stamp);
+        // The CCSDS epoch provider must reject the agency time code.
+        let res = TimeProviderCcsdsEpoch::from_bytes(&buf);
+        assert!(matches!(
+            res.unwrap_err(),
+            TimestampError::InvalidTimeCode(CcsdsTimeCodes::CucCcsdsEpoch, 0b010)
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_agency_epoch_with_fractions() {
+        let epoch = Utc.timestamp_opt(1_600_000_000, 750_000_000).unwrap();
+        let stamp = TimeProviderAgencyEpoch::new_with_fractions(
+            epoch,
+            10,
+            FractionalPart(FractionalResolution::FourMs, 128),
+        )
+        .unwrap();
+        assert_eq!(stamp.len_as_bytes(), 6);
+        // 0.75 s from the epoch and 0.5 s from the fractional part cause a carry.
+        assert_eq!(stamp.unix_seconds(), 1_600_000_011);
+        assert_eq!(stamp.subsecond_millis(), Some(250));
+        let dt = stamp.date_time().unwrap();
+        assert_eq!(dt.timestamp(), 1_600_000_011);
+        let mut buf: [u8; 16] = [0; 16];
+        stamp.write_to_bytes(&mut buf).unwrap();
+        let read_back = TimeProviderAgencyEpoch::from_bytes_with_epoch(&buf, epoch).unwrap();
+        assert_eq!(read_back, stamp);
+    }
+
+    #[test]
+    fn test_configurable_widths() {
+        let resolutions = [
+            FractionalResolution::Seconds,
+            FractionalResolution::FourMs,
+            FractionalResolution::FifteenUs,
+            FractionalResolution::SixtyNs,
+        ];
+        let mut buf: [u8; 16] = [0; 16];
+        for counter_width in 1..=4_u8 {
+            for res in resolutions {
+                let max_counter = (2_u64.pow(counter_width as u32 * 8) - 1) as u32;
+                let fractions = if res == FractionalResolution::Seconds {
+                    None
+                } else {
+                    Some(FractionalPart(res, fractional_res_to_div(res)))
+                };
+                let stamp = TimeProviderCcsdsEpoch::new_generic(
+                    WidthCounterPair(counter_width, max_counter),
+                    fractions,
+                )
+                .unwrap();
+                let expected_len = 1 + counter_width as usize + res as usize;
+                assert_eq!(stamp.len_as_bytes(), expected_len);
+                let written = stamp.write_to_bytes(&mut buf).unwrap();
+                assert_eq!(written, expected_len);
+                let (cntr_len, fractions_len, total_len) =
+                    TimeProviderCcsdsEpoch::len_components_and_total_from_pfield(buf[0]);
+                assert_eq!(cntr_len, counter_width);
+                assert_eq!(fractions_len, res as u8);
+                assert_eq!(total_len, expected_len);
+                let read_back = TimeProviderCcsdsEpoch::from_bytes(&buf).unwrap();
+                assert_eq!(read_back, stamp);
+            }
+        }
+    }
+
+    #[test]
+    fn test_configurable_widths_invalid() {
+        for width in [0, 5] {
+            let res = TimeProviderCcsdsEpoch::new_generic(WidthCounterPair(width, 0), None);
+            assert_eq!(res.unwrap_err(), CucError::InvalidCounterWidth(width));
+        }
+        let res = TimeProviderCcsdsEpoch::new_generic(WidthCounterPair(2, 0x10000), None);
+        assert_eq!(res.unwrap_err(), CucError::InvalidCounter(2, 0x10000));
+        let res = TimeProviderCcsdsEpoch::new_generic(
+            WidthCounterPair(1, 0),
+            Some(FractionalPart(FractionalResolution::FourMs, 256)),
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            CucError::InvalidFractions(FractionalResolution::FourMs, 256)
+        );
+    }
+
+    #[test]
+    fn test_resolution_nanos() {
+        assert_eq!(
+            TimeProviderCcsdsEpoch::new(0).resolution_nanos(),
+            10_u64.pow(9)
+        );
+        let coarse = TimeProviderCcsdsEpoch::new_with_coarse_fractions(0, 0);
+        assert_eq!(coarse.resolution_nanos(), 3_921_568);
+        let medium = TimeProviderCcsdsEpoch::new_with_medium_fractions(0, 0);
+        assert_eq!(medium.resolution_nanos(), 15_259);
+        let fine = TimeProviderCcsdsEpoch::new_with_fine_fractions(0, 0).unwrap();
+        assert_eq!(fine.resolution_nanos(), 59);
+    }
+
     #[test]
     fn test_write_no_fractions() {
         let mut buf: [u8; 16] = [0; 16];
@@ -762,7 +1304,9 @@ mod tests {
         assert_eq!(buf[5], 0);
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
+    #[cfg(feature = "std")]
     fn test_datetime_now() {
         let now = Utc::now();
         let cuc_now = TimeProviderCcsdsEpoch::from_now(FractionalResolution::SixtyNs);
@@ -1000,29 +1544,57 @@ mod tests {
     #[test]
     fn test_fractional_converter() {
         let ns = convert_fractional_part_to_ns(FractionalPart(FractionalResolution::FourMs, 2));
-        // The formula for this is 2/255 * 10e9 = 7.843.137.
-        assert_eq!(ns, 7843137);
-        // This is the largest value we should be able to pass without this function panicking.
+        // The formula for this is 2/256 * 10e9 = 7.812.500.
+        assert_eq!(ns, 7812500);
         let ns = convert_fractional_part_to_ns(FractionalPart(
             FractionalResolution::SixtyNs,
             2_u32.pow(24) - 2,
         ));
-        assert_eq!(ns, 999999940);
+        assert_eq!(ns, 999999880);
     }
 
     #[test]
-    #[should_panic]
-    fn test_fractional_converter_invalid_input() {
-        convert_fractional_part_to_ns(FractionalPart(FractionalResolution::FourMs, 256));
+    fn test_fractional_converter_max_fractions() {
+        for (res, expected_ns) in [
+            (FractionalResolution::FourMs, 996_093_750),
+            (FractionalResolution::FifteenUs, 999_984_741),
+            (FractionalResolution::SixtyNs, 999_999_940),
+        ] {
+            let max_fractions = fractional_res_to_div(res);
+            let ns = convert_fractional_part_to_ns(FractionalPart(res, max_fractions));
+            assert_eq!(ns, expected_ns);
+            // Invalid values are clamped to the largest valid value.
+            let ns = convert_fractional_part_to_ns(FractionalPart(res, u32::MAX));
+            assert_eq!(ns, expected_ns);
+        }
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
-    #[should_panic]
-    fn test_fractional_converter_invalid_input_2() {
-        convert_fractional_part_to_ns(FractionalPart(
-            FractionalResolution::SixtyNs,
-            2_u32.pow(32) - 1,
-        ));
+    fn test_max_fractions_from_bytes() {
+        for (res, expected_ms) in [
+            (FractionalResolution::FourMs, 996),
+            (FractionalResolution::FifteenUs, 999),
+            (FractionalResolution::SixtyNs, 999),
+        ] {
+            let max_fractions = FractionalPart(res, fractional_res_to_div(res));
+            let stamp = TimeProviderCcsdsEpoch::new_with_fractions(10, max_fractions).unwrap();
+            let mut buf = [0; 16];
+            stamp.write_to_bytes(&mut buf).unwrap();
+            let read_stamp = TimeProviderCcsdsEpoch::from_bytes(&buf).unwrap();
+            assert_eq!(read_stamp.subsecond_millis(), Some(expected_ms));
+            assert_eq!(
+                read_stamp.unix_nanos(),
+                read_stamp.unix_seconds() as i128 * 10_i128.pow(9)
+                    + convert_fractional_part_to_ns(max_fractions) as i128
+            );
+            let dt = read_stamp.date_time().unwrap();
+            assert_eq!(dt.timestamp_subsec_millis(), expected_ms as u32);
+        }
+        // Raw timestamp with the largest fractional part of the 1 byte resolution.
+        let stamp = TimeProviderCcsdsEpoch::from_bytes(&[0x1d, 0, 0, 0, 10, 0xff]).unwrap();
+        assert_eq!(stamp.subsecond_millis(), Some(996));
+        assert!(stamp.to_iso8601().unwrap().ends_with(".996Z"));
     }
 
     #[test]
@@ -1075,6 +1647,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn set_fract_resolution() {
         let mut stamp = TimeProviderCcsdsEpoch::new(2000);
         stamp.set_fractional_resolution(FractionalResolution::SixtyNs);
@@ -1122,4 +1695,156 @@ mod tests {
         cuc_stamp += duration;
         assert_eq!(cuc_stamp.counter.1, 10);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialization() {
+        let stamp = TimeProviderCcsdsEpoch::new_with_fractions(
+            0x1234,
+            FractionalPart(FractionalResolution::FifteenUs, 0x5678),
+        )
+        .unwrap();
+        let val = postcard::to_allocvec(&stamp).expect("Serializing timestamp failed");
+        let stamp_deser: TimeProviderCcsdsEpoch =
+            postcard::from_bytes(&val).expect("Stamp deserialization failed");
+        assert_eq!(stamp_deser, stamp);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialization_agency_epoch() {
+        let gps_epoch = Utc.timestamp_opt(315964800, 0).unwrap();
+        let stamp = TimeProviderAgencyEpoch::new(gps_epoch, 0x1234);
+        let val = postcard::to_allocvec(&stamp).expect("Serializing timestamp failed");
+        let stamp_deser: TimeProviderAgencyEpoch =
+            postcard::from_bytes(&val).expect("Stamp deserialization failed");
+        assert_eq!(stamp_deser, stamp);
+        assert_eq!(stamp_deser.epoch(), gps_epoch);
+    }
+
+    #[test]
+    fn test_extended_p_field() {
+        let mut stamp = TimeProviderCcsdsEpoch::new_with_coarse_fractions(0x1234_5678, 0x20);
+        stamp.set_p_field_extension(true);
+        assert_eq!(stamp.len_as_bytes(), 7);
+        assert_eq!(stamp.p_field(), (2, [0b1001_1101, 0]));
+        let mut buf = [0xff; 16];
+        assert_eq!(stamp.write_to_bytes(&mut buf).unwrap(), 7);
+        assert_eq!(buf[0..7], [0b1001_1101, 0, 0x12, 0x34, 0x56, 0x78, 0x20]);
+        let read_back = TimeProviderCcsdsEpoch::from_bytes(&buf).unwrap();
+        assert_eq!(read_back, stamp);
+        assert_eq!(read_back.p_field().0, 2);
+        stamp.set_p_field_extension(false);
+        assert_eq!(stamp.p_field(), (1, [0b0001_1101, 0]));
+        assert_eq!(stamp.len_as_bytes(), 6);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_extended_p_field_agency_epoch() {
+        let gps_epoch = Utc.timestamp_opt(315964800, 0).unwrap();
+        let mut stamp = TimeProviderAgencyEpoch::new(gps_epoch, 0x1234);
+        stamp.set_p_field_extension(true);
+        let mut buf = [0; 16];
+        assert_eq!(stamp.write_to_bytes(&mut buf).unwrap(), 6);
+        assert_eq!(buf[1], 0);
+        let read_back = TimeProviderAgencyEpoch::from_bytes_with_epoch(&buf, gps_epoch).unwrap();
+        assert_eq!(read_back, stamp);
+    }
+
+    #[test]
+    fn test_extended_p_field_additional_octets() {
+        let mut buf = [0; 16];
+        let mut stamp = TimeProviderCcsdsEpoch::new(0x1234);
+        stamp.set_p_field_extension(true);
+        stamp.write_to_bytes(&mut buf).unwrap();
+        // One additional octet of the counter.
+        buf[1] = 0b0010_0000;
+        let res = TimeProviderCcsdsEpoch::from_bytes(&buf);
+        assert_eq!(
+            res.unwrap_err(),
+            TimestampError::CucError(CucError::UnsupportedPfieldExtension(0b0010_0000))
+        );
+    }
+
+    #[test]
+    fn test_from_cds() {
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(20000, 10 * 1000 + 500);
+        let cuc_stamp = TimeProviderCcsdsEpoch::try_from(cds_stamp).unwrap();
+        assert_eq!(
+            cuc_stamp.width_counter_pair().1,
+            20000 * SECONDS_PER_DAY + 10
+        );
+        assert_eq!(cuc_stamp.unix_seconds(), cds_stamp.unix_seconds());
+        assert_eq!(cuc_stamp.subsecond_millis(), Some(499));
+        let cds_back: cds::TimeProvider = cuc_stamp.into();
+        assert_eq!(cds_back, cds_stamp);
+    }
+
+    #[test]
+    fn test_from_cds_with_precision() {
+        let mut cds_stamp = cds::TimeProvider::new_with_u24_days(20000, 250).unwrap();
+        cds_stamp.set_submillis_precision(cds::SubmillisPrecision::Microseconds(700));
+        let cuc_stamp =
+            TimeProviderCcsdsEpoch::from_cds(&cds_stamp, FractionalResolution::FifteenUs).unwrap();
+        let fractions = cuc_stamp.width_fractions_pair().unwrap();
+        assert_eq!(
+            fractions,
+            fractional_part_from_subsec_ns(FractionalResolution::FifteenUs, 250_700_000).unwrap()
+        );
+        // The sub-millisecond part is rounded to the nearest millisecond in the reverse
+        // conversion.
+        let cds_back: cds::TimeProvider<cds::DaysLen24Bits> = cuc_stamp.into();
+        assert_eq!(cds_back.ms_of_day(), 251);
+        assert_eq!(cds_back.submillis_precision(), None);
+    }
+
+    #[test]
+    fn test_to_cds_max_fractions() {
+        let raw_stamp = [0x1d, 0, 0, 0, 10, 0xff];
+        let cuc_stamp = TimeProviderCcsdsEpoch::from_bytes(&raw_stamp).unwrap();
+        let cds_stamp: cds::TimeProvider = cuc_stamp.into();
+        assert_eq!(cds_stamp.ccsds_days(), 0);
+        assert_eq!(cds_stamp.ms_of_day(), 10 * 1000 + 996);
+        // The largest fractional part of the finest resolution is rounded up to the next second.
+        for counter in [10, u32::MAX] {
+            let cuc_stamp = TimeProviderCcsdsEpoch::new_with_fractions(
+                counter,
+                FractionalPart(
+                    FractionalResolution::SixtyNs,
+                    fractional_res_to_div(FractionalResolution::SixtyNs),
+                ),
+            )
+            .unwrap();
+            let ms_since_epoch = (counter as u64 + 1) * 1000;
+            let cds_stamp: cds::TimeProvider<cds::DaysLen24Bits> = cuc_stamp.into();
+            assert_eq!(
+                cds_stamp.ccsds_days_as_u32() as u64,
+                ms_since_epoch / MS_PER_DAY as u64
+            );
+            assert_eq!(
+                cds_stamp.ms_of_day() as u64,
+                ms_since_epoch % MS_PER_DAY as u64
+            );
+            let cds_stamp: cds::TimeProvider = cuc_stamp.into();
+            assert_eq!(
+                cds_stamp.ccsds_days() as u64,
+                ms_since_epoch / MS_PER_DAY as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_cds_counter_overflow() {
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(u16::MAX, 0);
+        let res = TimeProviderCcsdsEpoch::try_from(cds_stamp);
+        assert_eq!(
+            res.unwrap_err(),
+            TimestampError::CucError(CucError::InvalidCounter(
+                4,
+                u16::MAX as u64 * SECONDS_PER_DAY as u64
+            ))
+        );
+    }
 }
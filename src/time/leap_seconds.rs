@@ -0,0 +1,240 @@
+//! Leap second support for timestamps which use a continuous time scale.
+//!
+//! CCSDS time codes are nominally based on TAI, which does not contain leap seconds. The UNIX
+//! time and [DateTime] conversions of the time providers do not account for leap seconds, so the
+//! returned values deviate from UTC by the number of leap seconds which were inserted since the
+//! epoch. The [LeapSecondsAdjusted] wrapper, which is usually created with
+//! [CcsdsTimeProvider::with_leap_seconds], subtracts a leap second offset from these conversions.
+use super::{CcsdsTimeCodes, CcsdsTimeProvider, TimeWriter, TimestampError};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, LocalResult, SecondsFormat, TimeZone, Utc};
+
+/// Table of the difference between TAI and UTC in seconds. Each entry contains the UNIX seconds
+/// of the UTC instant from which on the offset is valid, and the offset itself.
+///
+/// The last leap second was inserted at the end of 2016.
+pub const TAI_MINUS_UTC_TABLE: [(i64, i32); 28] = [
+    (63072000, 10),   // 1972-01-01
+    (78796800, 11),   // 1972-07-01
+    (94694400, 12),   // 1973-01-01
+    (126230400, 13),  // 1974-01-01
+    (157766400, 14),  // 1975-01-01
+    (189302400, 15),  // 1976-01-01
+    (220924800, 16),  // 1977-01-01
+    (252460800, 17),  // 1978-01-01
+    (283996800, 18),  // 1979-01-01
+    (315532800, 19),  // 1980-01-01
+    (362793600, 20),  // 1981-07-01
+    (394329600, 21),  // 1982-07-01
+    (425865600, 22),  // 1983-07-01
+    (489024000, 23),  // 1985-07-01
+    (567993600, 24),  // 1988-01-01
+    (631152000, 25),  // 1990-01-01
+    (662688000, 26),  // 1991-01-01
+    (709948800, 27),  // 1992-07-01
+    (741484800, 28),  // 1993-07-01
+    (773020800, 29),  // 1994-07-01
+    (820454400, 30),  // 1996-01-01
+    (867715200, 31),  // 1997-07-01
+    (915148800, 32),  // 1999-01-01
+    (1136073600, 33), // 2006-01-01
+    (1230768000, 34), // 2009-01-01
+    (1341100800, 35), // 2012-07-01
+    (1435708800, 36), // 2015-07-01
+    (1483228800, 37), // 2017-01-01
+];
+
+/// Difference between TAI and UTC in seconds at the given UTC instant in UNIX seconds.
+///
+/// Returns 0 for instants before 1972, where the offset was not an integer number of seconds.
+pub fn tai_minus_utc(unix_seconds: i64) -> i32 {
+    TAI_MINUS_UTC_TABLE
+        .iter()
+        .rev()
+        .find(|(start, _)| unix_seconds >= *start)
+        .map_or(0, |(_, offset)| *offset)
+}
+
+/// Leap second offset which needs to be subtracted from seconds on a continuous TAI-like time
+/// scale, counted from 1970-01-01, to retrieve the UNIX seconds of the UTC instant.
+///
+/// An inserted leap second can not be represented as UNIX time, so it is mapped to the first
+/// second of the following day.
+pub fn leap_seconds_for_tai(tai_seconds: i64) -> i32 {
+    TAI_MINUS_UTC_TABLE
+        .iter()
+        .rev()
+        .find(|(start, offset)| tai_seconds >= *start + *offset as i64)
+        .map_or(0, |(_, offset)| *offset)
+}
+
+/// Wrapper for a time provider which subtracts a leap second offset from the UNIX time and
+/// [DateTime] conversions of the wrapped provider.
+///
+/// The raw timestamp is not modified, so [TimeWriter::write_to_bytes] writes the same bytes as
+/// the wrapped provider.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct LeapSecondsAdjusted<Provider> {
+    provider: Provider,
+    leap_seconds: i32,
+}
+
+impl<Provider: CcsdsTimeProvider> LeapSecondsAdjusted<Provider> {
+    pub fn new(provider: Provider, leap_seconds: i32) -> Self {
+        Self {
+            provider,
+            leap_seconds,
+        }
+    }
+
+    /// Wrap the provider and determine the leap second offset from [TAI_MINUS_UTC_TABLE].
+    pub fn new_from_table(provider: Provider) -> Self {
+        let leap_seconds = leap_seconds_for_tai(provider.unix_seconds());
+        Self::new(provider, leap_seconds)
+    }
+
+    pub fn inner(&self) -> &Provider {
+        &self.provider
+    }
+
+    pub fn into_inner(self) -> Provider {
+        self.provider
+    }
+
+    pub fn leap_seconds(&self) -> i32 {
+        self.leap_seconds
+    }
+}
+
+impl<Provider: TimeWriter> TimeWriter for LeapSecondsAdjusted<Provider> {
+    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
+        self.provider.write_to_bytes(bytes)
+    }
+}
+
+impl<Provider: CcsdsTimeProvider> CcsdsTimeProvider for LeapSecondsAdjusted<Provider> {
+    fn len_as_bytes(&self) -> usize {
+        self.provider.len_as_bytes()
+    }
+
+    fn p_field(&self) -> (usize, [u8; 2]) {
+        self.provider.p_field()
+    }
+
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
+        self.provider.ccsds_time_code()
+    }
+
+    fn unix_seconds(&self) -> i64 {
+        self.provider.unix_seconds() - self.leap_seconds as i64
+    }
+
+    fn subsecond_millis(&self) -> Option<u16> {
+        self.provider.subsecond_millis()
+    }
+
+    fn unix_nanos(&self) -> i128 {
+        self.provider.unix_nanos() - self.leap_seconds as i128 * 10_i128.pow(9)
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        let date_time = self.provider.date_time()?;
+        if let LocalResult::Single(res) = Utc.timestamp_opt(
+            date_time.timestamp() - self.leap_seconds as i64,
+            date_time.timestamp_subsec_nanos(),
+        ) {
+            return Some(res);
+        }
+        None
+    }
+
+    #[cfg(feature = "chrono")]
+    fn iso8601_seconds_format(&self) -> SecondsFormat {
+        self.provider.iso8601_seconds_format()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "chrono")]
+    use crate::time::cds::TimeProvider;
+    use crate::time::cuc::TimeProviderCcsdsEpoch;
+    #[cfg(feature = "chrono")]
+    use chrono::{Datelike, Timelike};
+
+    // 2017-01-01T00:00:00 UTC, directly after the last inserted leap second.
+    const LAST_LEAP_SECOND_UNIX: i64 = 1483228800;
+
+    #[test]
+    fn test_tai_minus_utc() {
+        assert_eq!(tai_minus_utc(0), 0);
+        assert_eq!(tai_minus_utc(63072000), 10);
+        assert_eq!(tai_minus_utc(LAST_LEAP_SECOND_UNIX - 1), 36);
+        assert_eq!(tai_minus_utc(LAST_LEAP_SECOND_UNIX), 37);
+    }
+
+    #[test]
+    fn test_leap_seconds_for_tai() {
+        // Last second of 2016 on the UTC time scale, without the inserted leap second.
+        assert_eq!(leap_seconds_for_tai(LAST_LEAP_SECOND_UNIX - 1 + 36), 36);
+        // The inserted leap second itself.
+        assert_eq!(leap_seconds_for_tai(LAST_LEAP_SECOND_UNIX + 36), 36);
+        assert_eq!(leap_seconds_for_tai(LAST_LEAP_SECOND_UNIX + 37), 37);
+    }
+
+    #[test]
+    fn test_adjusted_unix_seconds() {
+        let stamp = TimeProviderCcsdsEpoch::new(0x1000_0000);
+        let adjusted = stamp.with_leap_seconds(37);
+        assert_eq!(adjusted.leap_seconds(), 37);
+        assert_eq!(adjusted.unix_seconds(), stamp.unix_seconds() - 37);
+        assert_eq!(adjusted.ccsds_time_code(), stamp.ccsds_time_code());
+        assert_eq!(adjusted.p_field(), stamp.p_field());
+        assert_eq!(adjusted.len_as_bytes(), stamp.len_as_bytes());
+        assert_eq!(*adjusted.inner(), stamp);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_adjusted_date_time_at_boundary() {
+        // The first millisecond of 2017 on the UTC time scale, stamped on the TAI time scale.
+        let ccsds_days = (LAST_LEAP_SECOND_UNIX / 86400 + 4383) as u16;
+        let stamp = TimeProvider::new_with_u16_days(ccsds_days, 37 * 1000 + 1);
+        let adjusted = LeapSecondsAdjusted::new_from_table(stamp);
+        assert_eq!(adjusted.leap_seconds(), 37);
+        assert_eq!(adjusted.unix_seconds(), LAST_LEAP_SECOND_UNIX);
+        assert_eq!(adjusted.subsecond_millis(), Some(1));
+        let dt = adjusted.date_time().unwrap();
+        assert_eq!(dt.year(), 2017);
+        assert_eq!(dt.month(), 1);
+        assert_eq!(dt.day(), 1);
+        assert_eq!(dt.hour(), 0);
+        assert_eq!(dt.second(), 0);
+
+        // The last second of 2016 on the UTC time scale.
+        let stamp = TimeProvider::new_with_u16_days(ccsds_days, 35 * 1000);
+        let adjusted = LeapSecondsAdjusted::new_from_table(stamp);
+        assert_eq!(adjusted.leap_seconds(), 36);
+        let dt = adjusted.date_time().unwrap();
+        assert_eq!(dt.year(), 2016);
+        assert_eq!(dt.hour(), 23);
+        assert_eq!(dt.minute(), 59);
+        assert_eq!(dt.second(), 59);
+    }
+
+    #[test]
+    fn test_write_unchanged() {
+        let stamp = TimeProviderCcsdsEpoch::new(0x1234);
+        let mut buf = [0; 16];
+        let mut buf_adjusted = [0; 16];
+        stamp.write_to_bytes(&mut buf).unwrap();
+        let written = stamp
+            .with_leap_seconds(37)
+            .write_to_bytes(&mut buf_adjusted)
+            .unwrap();
+        assert_eq!(written, stamp.len_as_bytes());
+        assert_eq!(buf, buf_adjusted);
+    }
+}
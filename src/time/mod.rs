@@ -1,6 +1,31 @@
 //! CCSDS Time Code Formats according to [CCSDS 301.0-B-4](https://public.ccsds.org/Pubs/301x0b4e1.pdf)
+//!
+//! ## `no_std` support
+//!
+//! All time providers can be created from raw values, for example with
+//! [cds::TimeProvider::new_with_u16_days], and serialized with [TimeWriter::write_to_bytes] when
+//! neither the `std` nor the `alloc` feature is enabled. The raw fields remain accessible
+//! without any `DateTime` conversion, for example with [CcsdsTimeProvider::unix_seconds] and
+//! [cds::CdsCommon::ms_of_day].
+//!
+//! ## `chrono` feature
+//!
+//! The `DateTime` conversions, the ISO-8601 formatting and the [ascii] module require the
+//! `chrono` feature, which is enabled by default. Without it, the time providers can still be
+//! created from raw fields, UNIX timestamps and the system clock, and the calendar fields of a
+//! timestamp are available with methods like [cds::TimeProvider::to_ymd_hms].
+//!
+//! ## Borrowed and owned time providers
+//!
+//! The CDS, CUC and CCS time providers as well as [AnyTimeProvider] own their data and implement
+//! [Copy], so a provider parsed from a packet can outlive the packet buffer. The boxed providers
+//! returned by [read_dyn_time_from_bytes] own their data as well. Only [raw::RawTimeProvider]
+//! borrows the raw timestamp from the packet buffer. It can be detached with
+//! [raw::RawTimeProvider::to_owned], and [raw::RawTimeProviderOwned::as_borrowed] converts it
+//! back.
 use crate::{ByteConversionError, SizeMissmatch};
-use chrono::{DateTime, LocalResult, TimeZone, Utc};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, LocalResult, SecondsFormat, TimeZone, Utc};
 use core::cmp::Ordering;
 use core::fmt::{Display, Formatter};
 use core::ops::{Add, AddAssign};
@@ -10,6 +35,12 @@ use core::time::Duration;
 #[cfg(not(feature = "std"))]
 use num_traits::float::FloatCore;
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", feature = "chrono"))]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "std")]
@@ -17,9 +48,14 @@ use std::error::Error;
 #[cfg(feature = "std")]
 use std::time::{SystemTime, SystemTimeError};
 
+#[cfg(feature = "chrono")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
 pub mod ascii;
+pub mod ccs;
 pub mod cds;
 pub mod cuc;
+pub mod leap_seconds;
+pub mod raw;
 
 pub const DAYS_CCSDS_TO_UNIX: i32 = -4383;
 pub const SECONDS_PER_DAY: u32 = 86400;
@@ -35,6 +71,15 @@ pub enum CcsdsTimeCodes {
     AgencyDefined = 0b110,
 }
 
+impl CcsdsTimeCodes {
+    /// Retrieve the time code from the first byte of a p-field. This is the same as
+    /// [ccsds_time_code_from_p_field], but returns [TimestampError::UnknownTimeCode] for unknown
+    /// time code identifiers.
+    pub fn try_from_pfield(pfield: u8) -> Result<Self, TimestampError> {
+        ccsds_time_code_from_p_field(pfield).map_err(TimestampError::UnknownTimeCode)
+    }
+}
+
 impl TryFrom<u8> for CcsdsTimeCodes {
     type Error = ();
 
@@ -57,6 +102,14 @@ pub fn ccsds_time_code_from_p_field(pfield: u8) -> Result<CcsdsTimeCodes, u8> {
     CcsdsTimeCodes::try_from(raw_bits).map_err(|_| raw_bits)
 }
 
+/// Generic error type for the parsing, creation and serialization of all timestamps in this
+/// module.
+///
+/// Errors which are specific to a time code, for example a milliseconds of day value exceeding
+/// one day or a sub-millisecond value exceeding one millisecond, are wrapped inside the
+/// [cds::CdsError], [cuc::CucError] and [ccs::CcsError] variants. Dates which can not be
+/// represented by a time code are reported with [TimestampError::DateBeforeCcsdsEpoch] or the
+/// range error of the respective time code.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[non_exhaustive]
@@ -64,11 +117,19 @@ pub enum TimestampError {
     /// Contains tuple where first value is the expected time code and the second
     /// value is the found raw value
     InvalidTimeCode(CcsdsTimeCodes, u8),
+    /// The p-field contains a time code identification which is not a known [CcsdsTimeCodes]
+    /// value. Contains the raw value of the time code identification field.
+    UnknownTimeCode(u8),
     ByteConversionError(ByteConversionError),
     CdsError(cds::CdsError),
     CucError(cuc::CucError),
-    DateBeforeCcsdsEpoch(DateTime<Utc>),
+    CcsError(ccs::CcsError),
+    /// The date is before the CCSDS epoch and can not be represented by the time code. Contains
+    /// the UNIX timestamp of the date.
+    DateBeforeCcsdsEpoch(UnixTimestamp),
     CustomEpochNotSupported,
+    /// A string could not be parsed as an ISO-8601 date time.
+    InvalidDateTimeString,
 }
 
 impl From<cds::CdsError> for TimestampError {
@@ -83,6 +144,12 @@ impl From<cuc::CucError> for TimestampError {
     }
 }
 
+impl From<ccs::CcsError> for TimestampError {
+    fn from(e: ccs::CcsError) -> Self {
+        TimestampError::CcsError(e)
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 #[derive(Debug, Clone)]
@@ -114,21 +181,34 @@ impl Display for TimestampError {
                     "invalid raw time code value {raw_val} for time code {time_code:?}"
                 )
             }
+            TimestampError::UnknownTimeCode(raw_val) => {
+                write!(f, "unknown raw time code value {raw_val}")
+            }
             TimestampError::CdsError(e) => {
                 write!(f, "cds error {e}")
             }
             TimestampError::CucError(e) => {
                 write!(f, "cuc error {e}")
             }
+            TimestampError::CcsError(e) => {
+                write!(f, "ccs error {e}")
+            }
             TimestampError::ByteConversionError(e) => {
                 write!(f, "byte conversion error {e}")
             }
             TimestampError::DateBeforeCcsdsEpoch(e) => {
-                write!(f, "datetime with date before ccsds epoch: {e}")
+                write!(
+                    f,
+                    "datetime with date before ccsds epoch: {} unix seconds",
+                    e.unix_seconds_f64()
+                )
             }
             TimestampError::CustomEpochNotSupported => {
                 write!(f, "custom epochs are not supported")
             }
+            TimestampError::InvalidDateTimeString => {
+                write!(f, "invalid ISO-8601 date time string")
+            }
         }
     }
 }
@@ -140,18 +220,42 @@ impl Error for TimestampError {
             TimestampError::ByteConversionError(e) => Some(e),
             TimestampError::CdsError(e) => Some(e),
             TimestampError::CucError(e) => Some(e),
+            TimestampError::CcsError(e) => Some(e),
             _ => None,
         }
     }
 }
 
+/// Seconds since the UNIX epoch using the system clock.
+///
+/// ## Panics
+///
+/// This function panics if the system clock is set to a time before the UNIX epoch. See
+/// [try_seconds_since_epoch] for a variant which returns an error instead.
 #[cfg(feature = "std")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 pub fn seconds_since_epoch() -> f64 {
-    SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("System time generation failed")
-        .as_secs_f64()
+    try_seconds_since_epoch().expect("System time generation failed")
+}
+
+/// Seconds since the UNIX epoch using the system clock. Returns a [SystemTimeError] if the
+/// system clock is set to a time before the UNIX epoch.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn try_seconds_since_epoch() -> Result<f64, SystemTimeError> {
+    Ok(SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_secs_f64())
+}
+
+/// Convert a [time::OffsetDateTime](::time::OffsetDateTime) to a [`DateTime<Utc>`] using its UNIX
+/// seconds and nanoseconds.
+#[cfg(all(feature = "time", feature = "chrono"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "time", feature = "chrono"))))]
+pub fn date_time_from_offset_date_time(dt: &::time::OffsetDateTime) -> DateTime<Utc> {
+    // The date range of the time crate is a subset of the chrono date range.
+    Utc.timestamp_opt(dt.unix_timestamp(), dt.nanosecond())
+        .unwrap()
 }
 
 /// Convert UNIX days to CCSDS days
@@ -170,6 +274,25 @@ pub const fn ccsds_to_unix_days(ccsds_days: i64) -> i64 {
     ccsds_days + DAYS_CCSDS_TO_UNIX as i64
 }
 
+/// Number of full days elapsed between two epochs. The result is negative if `to` is before
+/// `from`, and partial days are rounded towards negative infinity, so that the start of the day
+/// containing `to` is reached by adding the result to `from`.
+#[cfg(feature = "chrono")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+pub fn days_between_epochs(from: DateTime<Utc>, to: DateTime<Utc>) -> i32 {
+    // The chrono date range is approximately 100 million days, which fits into an i32.
+    (to - from).num_seconds().div_euclid(SECONDS_PER_DAY as i64) as i32
+}
+
+/// Day offset of an arbitrary epoch relative to the UNIX epoch. This is the generic version of
+/// [DAYS_CCSDS_TO_UNIX], which is equal to the offset of the CCSDS epoch. The days relative to
+/// the custom epoch can be converted to UNIX days by adding the offset.
+#[cfg(feature = "chrono")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+pub fn epoch_offset_days(epoch: DateTime<Utc>) -> i32 {
+    days_between_epochs(Utc.timestamp_opt(0, 0).unwrap(), epoch)
+}
+
 /// Similar to [unix_to_ccsds_days] but converts the epoch instead, which is the number of elpased
 /// seconds since the CCSDS and UNIX epoch times.
 pub const fn unix_epoch_to_ccsds_epoch(unix_epoch: i64) -> i64 {
@@ -180,12 +303,90 @@ pub const fn ccsds_epoch_to_unix_epoch(ccsds_epoch: i64) -> i64 {
     ccsds_epoch + (DAYS_CCSDS_TO_UNIX as i64 * SECONDS_PER_DAY as i64)
 }
 
+/// Convert UNIX days to a (year, month, day) tuple of the proleptic Gregorian calendar without
+/// requiring [chrono]. This is the `civil_from_days` algorithm described in
+/// <https://howardhinnant.github.io/date_algorithms.html>.
+pub(crate) const fn civil_from_unix_days(unix_days: i64) -> (i32, u32, u32) {
+    let shifted_days = unix_days + 719468;
+    let era = if shifted_days >= 0 {
+        shifted_days
+    } else {
+        shifted_days - 146096
+    } / 146097;
+    let day_of_era = shifted_days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let mut year = year_of_era + era * 400;
+    if month <= 2 {
+        year += 1;
+    }
+    (year as i32, month as u32, day as u32)
+}
+
+/// Inverse of [civil_from_unix_days] which converts a date of the proleptic Gregorian calendar
+/// to UNIX days. The month and day are not validated. This is the `days_from_civil` algorithm
+/// described in <https://howardhinnant.github.io/date_algorithms.html>.
+pub(crate) const fn unix_days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+pub(crate) const fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub(crate) const fn days_in_year(year: i32) -> u32 {
+    if is_leap_year(year) {
+        366
+    } else {
+        365
+    }
+}
+
+/// Number of days of the month, or [None] if the month is not in the range 1 to 12.
+pub(crate) const fn days_in_month(year: i32, month: u32) -> Option<u32> {
+    Some(match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => return None,
+    })
+}
+
+/// Milliseconds of the current day using the system clock.
+///
+/// ## Panics
+///
+/// This function panics if the system clock is set to a time before the UNIX epoch. See
+/// [try_ms_of_day_using_sysclock] for a variant which returns an error instead.
 #[cfg(feature = "std")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 pub fn ms_of_day_using_sysclock() -> u32 {
     ms_of_day(seconds_since_epoch())
 }
 
+/// Like [ms_of_day_using_sysclock] but returns a [SystemTimeError] if the system clock is set to
+/// a time before the UNIX epoch.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn try_ms_of_day_using_sysclock() -> Result<u32, SystemTimeError> {
+    Ok(ms_of_day(try_seconds_since_epoch()?))
+}
+
 pub fn ms_of_day(seconds_since_epoch: f64) -> u32 {
     let fraction_ms = seconds_since_epoch - seconds_since_epoch.floor();
     let ms_of_day: u32 = (((seconds_since_epoch.floor() as u32 % SECONDS_PER_DAY) * 1000) as f64
@@ -218,7 +419,12 @@ pub trait CcsdsTimeProvider {
     /// entry denotes the length of the pfield and the second entry is the value of the pfield
     /// in big endian format.
     fn p_field(&self) -> (usize, [u8; 2]);
-    fn ccdsd_time_code(&self) -> CcsdsTimeCodes;
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes;
+
+    #[deprecated(since = "0.5.5", note = "use ccsds_time_code instead")]
+    fn ccdsd_time_code(&self) -> CcsdsTimeCodes {
+        self.ccsds_time_code()
+    }
 
     fn unix_seconds(&self) -> i64;
     fn subsecond_millis(&self) -> Option<u16>;
@@ -229,7 +435,248 @@ pub trait CcsdsTimeProvider {
         UnixTimestamp::const_new(self.unix_seconds(), self.subsecond_millis().unwrap())
     }
 
-    fn date_time(&self) -> Option<DateTime<Utc>>;
+    /// Nanoseconds since the UNIX epoch. The default implementation only has millisecond
+    /// resolution, providers with a finer resolution override it.
+    fn unix_nanos(&self) -> i128 {
+        self.unix_seconds() as i128 * 10_i128.pow(9)
+            + self.subsecond_millis().unwrap_or(0) as i128 * 10_i128.pow(6)
+    }
+
+    /// Convert the timestamp to a [`DateTime<Utc>`]. The default implementation uses the
+    /// nanoseconds returned by [Self::unix_nanos].
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        let unix_nanos = self.unix_nanos();
+        let seconds = i64::try_from(unix_nanos.div_euclid(10_i128.pow(9))).ok()?;
+        Utc.timestamp_opt(seconds, unix_nanos.rem_euclid(10_i128.pow(9)) as u32)
+            .single()
+    }
+
+    /// Convert the timestamp to a [time::OffsetDateTime](::time::OffsetDateTime) with an UTC
+    /// offset, using the nanoseconds returned by [Self::unix_nanos].
+    #[cfg(feature = "time")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "time")))]
+    fn offset_date_time(&self) -> Option<::time::OffsetDateTime> {
+        ::time::OffsetDateTime::from_unix_timestamp_nanos(self.unix_nanos()).ok()
+    }
+
+    /// Number of sub-second digits which is used by [Self::to_iso8601]. The default
+    /// implementation uses milliseconds, providers override it to match their resolution.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
+    fn iso8601_seconds_format(&self) -> SecondsFormat {
+        SecondsFormat::Millis
+    }
+
+    /// Format the timestamp as an ISO-8601 string with the format `YYYY-MM-DDTHH:MM:SS.sssZ`,
+    /// where the number of sub-second digits is determined by
+    /// [Self::iso8601_seconds_format]. Returns [None] if the timestamp can not be converted to a
+    /// [DateTime].
+    #[cfg(all(feature = "alloc", feature = "chrono"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "alloc", feature = "chrono"))))]
+    fn to_iso8601(&self) -> Option<String> {
+        self.date_time()
+            .map(|dt| dt.to_rfc3339_opts(self.iso8601_seconds_format(), true))
+    }
+
+    /// Serialize the timestamp into a newly allocated [Vec] which has the exact length of the
+    /// timestamp.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn to_vec(&self) -> Result<Vec<u8>, TimestampError>
+    where
+        Self: TimeWriter + Sized,
+    {
+        let mut vec = alloc::vec![0; self.len_as_bytes()];
+        self.write_to_bytes(&mut vec)?;
+        Ok(vec)
+    }
+
+    /// Wrap the time provider so that the given number of leap seconds is subtracted from the
+    /// UNIX time and [DateTime] conversions. See the [leap_seconds] module for more information.
+    fn with_leap_seconds(self, leap_seconds: i32) -> leap_seconds::LeapSecondsAdjusted<Self>
+    where
+        Self: Sized,
+    {
+        leap_seconds::LeapSecondsAdjusted::new(self, leap_seconds)
+    }
+}
+
+/// Wrapper for all time providers which can be read from a raw timestamp without additional
+/// context, returned by [read_time_from_bytes].
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AnyTimeProvider {
+    CdsDays16(cds::TimeProvider<cds::DaysLen16Bits>),
+    CdsDays24(cds::TimeProvider<cds::DaysLen24Bits>),
+    Cuc(cuc::TimeProviderCcsdsEpoch),
+    Ccs(ccs::TimeProvider),
+}
+
+impl AnyTimeProvider {
+    fn inner(&self) -> &dyn CcsdsTimeProvider {
+        match self {
+            AnyTimeProvider::CdsDays16(provider) => provider,
+            AnyTimeProvider::CdsDays24(provider) => provider,
+            AnyTimeProvider::Cuc(provider) => provider,
+            AnyTimeProvider::Ccs(provider) => provider,
+        }
+    }
+}
+
+impl TimeWriter for AnyTimeProvider {
+    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
+        match self {
+            AnyTimeProvider::CdsDays16(provider) => provider.write_to_bytes(bytes),
+            AnyTimeProvider::CdsDays24(provider) => provider.write_to_bytes(bytes),
+            AnyTimeProvider::Cuc(provider) => provider.write_to_bytes(bytes),
+            AnyTimeProvider::Ccs(provider) => provider.write_to_bytes(bytes),
+        }
+    }
+}
+
+impl CcsdsTimeProvider for AnyTimeProvider {
+    fn len_as_bytes(&self) -> usize {
+        self.inner().len_as_bytes()
+    }
+
+    fn p_field(&self) -> (usize, [u8; 2]) {
+        self.inner().p_field()
+    }
+
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
+        self.inner().ccsds_time_code()
+    }
+
+    fn unix_seconds(&self) -> i64 {
+        self.inner().unix_seconds()
+    }
+
+    fn subsecond_millis(&self) -> Option<u16> {
+        self.inner().subsecond_millis()
+    }
+
+    fn unix_nanos(&self) -> i128 {
+        self.inner().unix_nanos()
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        self.inner().date_time()
+    }
+
+    #[cfg(feature = "chrono")]
+    fn iso8601_seconds_format(&self) -> SecondsFormat {
+        self.inner().iso8601_seconds_format()
+    }
+}
+
+/// Length of a timestamp in bytes, including the p-field, which is determined only from its
+/// p-field.
+///
+/// The passed slice needs to contain the first octet of the p-field, and the second octet as
+/// well if the p-field is extended. This allows to determine the length of a timestamp inside a
+/// larger buffer before the timestamp is parsed.
+///
+/// ## Errors
+///
+/// This function returns [TimestampError::UnknownTimeCode] if the p-field contains an unknown
+/// time code and [TimestampError::CustomEpochNotSupported] for the agency-defined time code,
+/// for which the length can not be determined.
+pub fn len_for_pfield(pfield: &[u8]) -> Result<usize, TimestampError> {
+    if pfield.is_empty() {
+        return Err(TimestampError::ByteConversionError(
+            ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                found: 0,
+                expected: 1,
+            }),
+        ));
+    }
+    match CcsdsTimeCodes::try_from_pfield(pfield[0])? {
+        CcsdsTimeCodes::CucCcsdsEpoch | CcsdsTimeCodes::CucAgencyEpoch => {
+            if cuc::pfield_len(pfield[0]) == 2 {
+                if pfield.len() < 2 {
+                    return Err(TimestampError::ByteConversionError(
+                        ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                            found: pfield.len(),
+                            expected: 2,
+                        }),
+                    ));
+                }
+                if pfield[1] & 0b1111_1100 != 0 {
+                    return Err(cuc::CucError::UnsupportedPfieldExtension(pfield[1]).into());
+                }
+            }
+            Ok(cuc::TimeProviderCcsdsEpoch::len_packed_from_pfield(
+                pfield[0],
+            ))
+        }
+        CcsdsTimeCodes::Cds => Ok(cds::TimeProvider::<cds::DaysLen16Bits>::calc_stamp_len(
+            pfield[0],
+        )),
+        CcsdsTimeCodes::Ccs => {
+            let subsec_octets = pfield[0] & 0b111;
+            if subsec_octets > ccs::MAX_SUBSEC_OCTETS {
+                return Err(ccs::CcsError::InvalidSubseconds(subsec_octets, 0).into());
+            }
+            Ok(ccs::MIN_CCS_LEN + subsec_octets as usize)
+        }
+        CcsdsTimeCodes::AgencyDefined => Err(TimestampError::CustomEpochNotSupported),
+    }
+}
+
+/// Read a timestamp with a time code which is determined from the p-field at the start of the
+/// passed buffer.
+///
+/// ## Errors
+///
+/// This function returns [TimestampError::UnknownTimeCode] if the p-field contains an unknown
+/// time code and [TimestampError::CustomEpochNotSupported] for time codes which require an
+/// agency-defined epoch or format.
+pub fn read_time_from_bytes(buf: &[u8]) -> Result<AnyTimeProvider, TimestampError> {
+    if buf.is_empty() {
+        return Err(TimestampError::ByteConversionError(
+            ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                found: 0,
+                expected: 1,
+            }),
+        ));
+    }
+    match CcsdsTimeCodes::try_from_pfield(buf[0])? {
+        CcsdsTimeCodes::Cds => {
+            if cds::length_of_day_segment_from_pfield(buf[0])
+                == cds::LengthOfDaySegment::Short16Bits
+            {
+                Ok(AnyTimeProvider::CdsDays16(
+                    cds::TimeProvider::from_bytes_with_u16_days(buf)?,
+                ))
+            } else {
+                Ok(AnyTimeProvider::CdsDays24(
+                    cds::TimeProvider::from_bytes_with_u24_days(buf)?,
+                ))
+            }
+        }
+        CcsdsTimeCodes::CucCcsdsEpoch => Ok(AnyTimeProvider::Cuc(
+            cuc::TimeProviderCcsdsEpoch::from_bytes(buf)?,
+        )),
+        CcsdsTimeCodes::Ccs => Ok(AnyTimeProvider::Ccs(ccs::TimeProvider::from_bytes(buf)?)),
+        CcsdsTimeCodes::CucAgencyEpoch | CcsdsTimeCodes::AgencyDefined => {
+            Err(TimestampError::CustomEpochNotSupported)
+        }
+    }
+}
+
+/// Boxed variant of [read_time_from_bytes].
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub fn read_dyn_time_from_bytes(buf: &[u8]) -> Result<Box<dyn CcsdsTimeProvider>, TimestampError> {
+    Ok(match read_time_from_bytes(buf)? {
+        AnyTimeProvider::CdsDays16(provider) => Box::new(provider),
+        AnyTimeProvider::CdsDays24(provider) => Box::new(provider),
+        AnyTimeProvider::Cuc(provider) => Box::new(provider),
+        AnyTimeProvider::Ccs(provider) => Box::new(provider),
+    })
 }
 
 /// UNIX timestamp: Elapsed seconds since 1970-01-01T00:00:00+00:00.
@@ -297,6 +744,8 @@ impl UnixTimestamp {
         secs
     }
 
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
     pub fn as_date_time(&self) -> LocalResult<DateTime<Utc>> {
         Utc.timestamp_opt(
             self.unix_seconds,
@@ -305,6 +754,8 @@ impl UnixTimestamp {
     }
 }
 
+#[cfg(feature = "chrono")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "chrono")))]
 impl From<DateTime<Utc>> for UnixTimestamp {
     fn from(value: DateTime<Utc>) -> Self {
         Self::const_new(value.timestamp(), value.timestamp_subsec_millis() as u16)
@@ -419,6 +870,7 @@ impl Add<Duration> for &UnixTimestamp {
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
+    use std::string::ToString;
 
     #[test]
     fn test_days_conversion() {
@@ -426,10 +878,154 @@ mod tests {
         assert_eq!(ccsds_to_unix_days(0), DAYS_CCSDS_TO_UNIX.into());
     }
 
+    #[test]
+    fn test_time_code_from_pfield() {
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(0, 0);
+        assert_eq!(
+            CcsdsTimeCodes::try_from_pfield(cds_stamp.p_field().1[0]).unwrap(),
+            CcsdsTimeCodes::Cds
+        );
+        // The extension bit is ignored.
+        assert_eq!(
+            CcsdsTimeCodes::try_from_pfield(0b1001_0000).unwrap(),
+            CcsdsTimeCodes::CucCcsdsEpoch
+        );
+        assert_eq!(
+            CcsdsTimeCodes::try_from_pfield(0b0000_0000).unwrap_err(),
+            TimestampError::UnknownTimeCode(0)
+        );
+        assert_eq!(CcsdsTimeCodes::try_from(0b101), Ok(CcsdsTimeCodes::Ccs));
+        assert_eq!(CcsdsTimeCodes::try_from(0b111), Err(()));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_calendar_helpers() {
+        use chrono::{Datelike, NaiveDate};
+        // Covers the years 1547 to 2380, which includes all special cases of the leap year rule.
+        for unix_days in -155_000..150_000 {
+            let dt = Utc
+                .timestamp_opt(unix_days * SECONDS_PER_DAY as i64, 0)
+                .unwrap();
+            let (year, month, day) = civil_from_unix_days(unix_days);
+            assert_eq!((year, month, day), (dt.year(), dt.month(), dt.day()));
+            assert_eq!(unix_days_from_civil(year, month, day), unix_days);
+            assert_eq!(
+                is_leap_year(year),
+                NaiveDate::from_ymd_opt(year, 2, 29).is_some()
+            );
+            assert_eq!(
+                days_in_year(year),
+                if is_leap_year(year) { 366 } else { 365 }
+            );
+            assert!(day <= days_in_month(year, month).unwrap());
+        }
+        assert_eq!(days_in_month(2023, 0), None);
+        assert_eq!(days_in_month(2023, 13), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_custom_epoch_days() {
+        let ccsds_epoch = Utc.with_ymd_and_hms(1958, 1, 1, 0, 0, 0).unwrap();
+        let gps_epoch = Utc.with_ymd_and_hms(1980, 1, 6, 0, 0, 0).unwrap();
+        assert_eq!(epoch_offset_days(ccsds_epoch), DAYS_CCSDS_TO_UNIX);
+        assert_eq!(epoch_offset_days(gps_epoch), 3657);
+        assert_eq!(days_between_epochs(ccsds_epoch, gps_epoch), 3657 + 4383);
+        assert_eq!(days_between_epochs(gps_epoch, ccsds_epoch), -(3657 + 4383));
+        let almost_one_day = Utc.with_ymd_and_hms(1980, 1, 6, 23, 59, 59).unwrap();
+        assert_eq!(days_between_epochs(gps_epoch, almost_one_day), 0);
+        assert_eq!(days_between_epochs(almost_one_day, gps_epoch), -1);
+    }
+
+    #[test]
+    fn test_time_code_conversion() {
+        for time_code in [
+            CcsdsTimeCodes::CucCcsdsEpoch,
+            CcsdsTimeCodes::CucAgencyEpoch,
+            CcsdsTimeCodes::Cds,
+            CcsdsTimeCodes::Ccs,
+            CcsdsTimeCodes::AgencyDefined,
+        ] {
+            assert_eq!(CcsdsTimeCodes::try_from(time_code as u8), Ok(time_code));
+        }
+        assert!(CcsdsTimeCodes::try_from(0b111).is_err());
+    }
+
+    #[test]
+    fn test_time_code_from_generic_provider() {
+        fn time_code(provider: &impl CcsdsTimeProvider) -> CcsdsTimeCodes {
+            provider.ccsds_time_code()
+        }
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(0, 0);
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::new(0);
+        assert_eq!(time_code(&cds_stamp), CcsdsTimeCodes::Cds);
+        assert_eq!(time_code(&cuc_stamp), CcsdsTimeCodes::CucCcsdsEpoch);
+        assert_ne!(time_code(&cds_stamp), time_code(&cuc_stamp));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_time_code_alias() {
+        let stamp = cds::TimeProvider::new_with_u16_days(0, 0);
+        assert_eq!(stamp.ccdsd_time_code(), stamp.ccsds_time_code());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_read_time_from_bytes() {
+        let mut buf: [u8; 16] = [0; 16];
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(23754, 60570123);
+        cds_stamp.write_to_bytes(&mut buf).unwrap();
+        let stamp = read_time_from_bytes(&buf).unwrap();
+        assert_eq!(stamp, AnyTimeProvider::CdsDays16(cds_stamp));
+        assert_eq!(stamp.len_as_bytes(), 7);
+        assert_eq!(stamp.unix_seconds(), cds_stamp.unix_seconds());
+
+        let cds_stamp = cds::TimeProvider::new_with_u24_days(0x108020, 0).unwrap();
+        cds_stamp.write_to_bytes(&mut buf).unwrap();
+        let stamp = read_time_from_bytes(&buf).unwrap();
+        assert_eq!(stamp, AnyTimeProvider::CdsDays24(cds_stamp));
+
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::new_with_coarse_fractions(100, 128);
+        cuc_stamp.write_to_bytes(&mut buf).unwrap();
+        let stamp = read_time_from_bytes(&buf).unwrap();
+        assert_eq!(stamp, AnyTimeProvider::Cuc(cuc_stamp));
+        assert_eq!(stamp.ccsds_time_code(), CcsdsTimeCodes::CucCcsdsEpoch);
+        assert_eq!(stamp.subsecond_millis(), cuc_stamp.subsecond_millis());
+
+        let ccs_stamp = ccs::TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        ccs_stamp.write_to_bytes(&mut buf).unwrap();
+        let stamp = read_time_from_bytes(&buf).unwrap();
+        assert_eq!(stamp, AnyTimeProvider::Ccs(ccs_stamp));
+        let dyn_stamp = read_dyn_time_from_bytes(&buf).unwrap();
+        assert_eq!(dyn_stamp.ccsds_time_code(), CcsdsTimeCodes::Ccs);
+        assert_eq!(dyn_stamp.date_time(), ccs_stamp.date_time());
+    }
+
+    #[test]
+    fn test_read_time_from_bytes_invalid() {
+        assert!(matches!(
+            read_time_from_bytes(&[]).unwrap_err(),
+            TimestampError::ByteConversionError(ByteConversionError::FromSliceTooSmall(_))
+        ));
+        assert_eq!(
+            read_time_from_bytes(&[0b0111_0000, 0, 0, 0]).unwrap_err(),
+            TimestampError::UnknownTimeCode(0b111)
+        );
+        assert_eq!(
+            read_time_from_bytes(&[0b0010_1100, 0, 0, 0, 0]).unwrap_err(),
+            TimestampError::CustomEpochNotSupported
+        );
+    }
+
     #[test]
     fn test_get_current_time() {
         let sec_floats = seconds_since_epoch();
         assert!(sec_floats > 0.0);
+        let sec_floats_checked = try_seconds_since_epoch().unwrap();
+        assert!(sec_floats_checked >= sec_floats);
+        assert!(try_ms_of_day_using_sysclock().unwrap() < MS_PER_DAY);
     }
 
     #[test]
@@ -550,4 +1146,175 @@ mod tests {
         assert_eq!(stamp0.unix_seconds, 3);
         assert_eq!(stamp0.subsecond_millis().unwrap(), 100);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_any_provider_serialization() {
+        let stamp = AnyTimeProvider::Cuc(cuc::TimeProviderCcsdsEpoch::new(0x1234));
+        let val = postcard::to_allocvec(&stamp).expect("Serializing timestamp failed");
+        let stamp_deser: AnyTimeProvider =
+            postcard::from_bytes(&val).expect("Stamp deserialization failed");
+        assert_eq!(stamp_deser, stamp);
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let stamp = cds::TimeProvider::new_with_u16_days(0x1234, 0x0102_0304);
+        let mut buf = [0; 7];
+        stamp.write_to_bytes(&mut buf).unwrap();
+        let vec = stamp.to_vec().unwrap();
+        assert_eq!(vec.len(), stamp.len_as_bytes());
+        assert_eq!(vec.as_slice(), buf);
+        let stamp = cuc::TimeProviderCcsdsEpoch::new(0x1234);
+        assert_eq!(stamp.to_vec().unwrap().len(), stamp.len_as_bytes());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_unix_nanos() {
+        let mut cds_stamp = cds::TimeProvider::new_with_u16_days(20000, 1500);
+        cds_stamp.set_submillis_precision(cds::SubmillisPrecision::Microseconds(250));
+        assert_eq!(
+            cds_stamp.unix_nanos(),
+            cds_stamp.unix_seconds() as i128 * 10_i128.pow(9) + 500_250_000
+        );
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::new_with_medium_fractions(0x1234, 0x8000);
+        let dt = cuc_stamp.date_time().unwrap();
+        assert_eq!(
+            cuc_stamp.unix_nanos(),
+            dt.timestamp() as i128 * 10_i128.pow(9) + dt.timestamp_subsec_nanos() as i128
+        );
+        let any_stamp = AnyTimeProvider::CdsDays16(cds_stamp);
+        assert_eq!(any_stamp.unix_nanos(), cds_stamp.unix_nanos());
+        let ccs_stamp = ccs::TimeProvider::new(1970, 1, 1, 0, 0, 20).unwrap();
+        assert_eq!(ccs_stamp.unix_nanos(), 20 * 10_i128.pow(9));
+    }
+
+    #[test]
+    fn test_len_for_pfield() {
+        let mut cds_stamp = cds::TimeProvider::new_with_u16_days(0, 0);
+        assert_eq!(len_for_pfield(&[cds_stamp.p_field().1[0]]).unwrap(), 7);
+        cds_stamp.set_submillis_precision(cds::SubmillisPrecision::Picoseconds(0));
+        assert_eq!(
+            len_for_pfield(&[cds_stamp.p_field().1[0]]).unwrap(),
+            cds::TimeProvider::<cds::DaysLen16Bits>::MAX_LEN
+        );
+        let cds_stamp = cds::TimeProvider::new_with_u24_days(0, 0).unwrap();
+        assert_eq!(
+            len_for_pfield(&[cds_stamp.p_field().1[0]]).unwrap(),
+            cds::TimeProvider::<cds::DaysLen24Bits>::MIN_LEN
+        );
+        let mut cuc_stamp = cuc::TimeProviderCcsdsEpoch::new_with_fine_fractions(0, 0).unwrap();
+        cuc_stamp.set_p_field_extension(true);
+        let mut buf = [0; 16];
+        cuc_stamp.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(
+            len_for_pfield(&buf).unwrap(),
+            cuc::TimeProviderCcsdsEpoch::MAX_LEN
+        );
+        assert!(len_for_pfield(&buf[0..1]).is_err());
+        let ccs_stamp = ccs::TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        assert_eq!(
+            len_for_pfield(&[ccs_stamp.p_field().1[0]]).unwrap(),
+            ccs::TimeProvider::MIN_LEN
+        );
+        assert!(len_for_pfield(&[]).is_err());
+        assert_eq!(
+            len_for_pfield(&[0b0111_0000]).unwrap_err(),
+            TimestampError::UnknownTimeCode(0b111)
+        );
+        assert_eq!(
+            len_for_pfield(&[0b0110_0000]).unwrap_err(),
+            TimestampError::CustomEpochNotSupported
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_to_iso8601() {
+        let mut cds_stamp = cds::TimeProvider::new_with_u16_days(4383, 12_345_678);
+        assert_eq!(cds_stamp.to_iso8601().unwrap(), "1970-01-01T03:25:45.678Z");
+        cds_stamp.set_submillis_precision(cds::SubmillisPrecision::Microseconds(12));
+        assert_eq!(
+            cds_stamp.to_iso8601().unwrap(),
+            "1970-01-01T03:25:45.678012Z"
+        );
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::new(0);
+        assert_eq!(cuc_stamp.to_iso8601().unwrap(), "1958-01-01T00:00:00Z");
+        let ccs_stamp = ccs::TimeProvider::new(2023, 1, 14, 16, 49, 30).unwrap();
+        assert_eq!(
+            AnyTimeProvider::Ccs(ccs_stamp).to_iso8601().unwrap(),
+            "2023-01-14T16:49:30Z"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_offset_date_time_conversions() {
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(4383, 12_345_678);
+        let odt = cds_stamp.offset_date_time().unwrap();
+        assert_eq!(odt.unix_timestamp(), 12_345);
+        assert_eq!(odt.nanosecond(), 678_000_000);
+        assert_eq!(
+            date_time_from_offset_date_time(&odt),
+            cds_stamp.date_time().unwrap()
+        );
+        assert_eq!(
+            cds::TimeProvider::<cds::DaysLen16Bits>::try_from(odt).unwrap(),
+            cds_stamp
+        );
+        let cds_stamp_u24 = cds::TimeProvider::<cds::DaysLen24Bits>::try_from(odt).unwrap();
+        assert_eq!(cds_stamp_u24.unix_nanos(), cds_stamp.unix_nanos());
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::from_offset_date_time(
+            &odt,
+            cuc::FractionalResolution::Seconds,
+        )
+        .unwrap();
+        assert_eq!(cuc_stamp.unix_seconds(), 12_345);
+        let ccs_stamp = ccs::TimeProvider::from_offset_date_time(&odt, 2).unwrap();
+        assert_eq!(ccs_stamp.offset_date_time().unwrap(), odt);
+        let before_epoch = ::time::OffsetDateTime::from_unix_timestamp(-400_000_000).unwrap();
+        assert!(matches!(
+            cds::TimeProvider::<cds::DaysLen16Bits>::try_from(before_epoch).unwrap_err(),
+            TimestampError::DateBeforeCcsdsEpoch(_)
+        ));
+    }
+
+    #[test]
+    fn test_error_display_and_source() {
+        let error = TimestampError::from(cds::CdsError::InvalidSubmillisPrecision(
+            cds::SubmillisPrecision::Microseconds(1000),
+        ));
+        assert_eq!(
+            error.to_string(),
+            "cds error sub-millisecond precision Microseconds(1000) exceeds one millisecond"
+        );
+        assert!(error.source().is_some());
+        let error = TimestampError::InvalidTimeCode(CcsdsTimeCodes::Cds, 0b001);
+        assert_eq!(
+            error.to_string(),
+            "invalid raw time code value 1 for time code Cds"
+        );
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_write_consecutive_stamps() {
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(20000, 1000);
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::new(0x1234);
+        let mut buf = [0; 32];
+        let mut current_idx = 2;
+        current_idx += cds_stamp.write_to_bytes(&mut buf[current_idx..]).unwrap();
+        assert_eq!(current_idx, 2 + cds_stamp.len_as_bytes());
+        current_idx += cuc_stamp.write_to_bytes(&mut buf[current_idx..]).unwrap();
+        assert_eq!(
+            current_idx,
+            2 + cds_stamp.len_as_bytes() + cuc_stamp.len_as_bytes()
+        );
+        let read_cds = read_time_from_bytes(&buf[2..]).unwrap();
+        assert_eq!(read_cds, AnyTimeProvider::CdsDays16(cds_stamp));
+        let read_cuc = read_time_from_bytes(&buf[2 + read_cds.len_as_bytes()..]).unwrap();
+        assert_eq!(read_cuc, AnyTimeProvider::Cuc(cuc_stamp));
+    }
 }
@@ -0,0 +1,262 @@
+//! Pass-through time provider for timestamps which are forwarded without being decoded.
+//!
+//! This is useful for relays which need to forward the timestamp of a telemetry packet verbatim
+//! but which do not support all CCSDS time code formats.
+use super::{CcsdsTimeCodes, CcsdsTimeProvider, TimeWriter, TimestampError};
+use crate::{ByteConversionError, SizeMissmatch};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Time provider which wraps the raw bytes of a timestamp, including its p-field.
+///
+/// Only the p-field is inspected to determine the [CcsdsTimeCodes] of the timestamp. The time
+/// information itself is not decoded, so [CcsdsTimeProvider::unix_seconds] always returns 0,
+/// while [CcsdsTimeProvider::subsecond_millis] and [CcsdsTimeProvider::date_time] return [None].
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct RawTimeProvider<'raw> {
+    raw: &'raw [u8],
+    time_code: CcsdsTimeCodes,
+}
+
+impl<'raw> RawTimeProvider<'raw> {
+    /// Create a new raw time provider. The passed slice needs to contain exactly one timestamp
+    /// starting with its p-field.
+    pub fn new(raw: &'raw [u8]) -> Result<Self, TimestampError> {
+        if raw.is_empty() {
+            return Err(TimestampError::ByteConversionError(
+                ByteConversionError::FromSliceTooSmall(SizeMissmatch {
+                    found: 0,
+                    expected: 1,
+                }),
+            ));
+        }
+        let time_code = CcsdsTimeCodes::try_from_pfield(raw[0])?;
+        Ok(Self { raw, time_code })
+    }
+
+    pub fn raw(&self) -> &'raw [u8] {
+        self.raw
+    }
+
+    /// Create a [RawTimeProviderOwned] which owns a copy of the raw timestamp, so it is not bound
+    /// to the lifetime of the packet buffer this provider borrows from.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_owned(&self) -> RawTimeProviderOwned {
+        RawTimeProviderOwned {
+            raw: self.raw.to_vec(),
+            time_code: self.time_code,
+        }
+    }
+}
+
+/// Owned variant of [RawTimeProvider] which stores the raw timestamp in a vector. It can be
+/// created with [RawTimeProvider::to_owned] and be moved and stored freely.
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RawTimeProviderOwned {
+    raw: Vec<u8>,
+    time_code: CcsdsTimeCodes,
+}
+
+#[cfg(feature = "alloc")]
+impl RawTimeProviderOwned {
+    /// Like [RawTimeProvider::new], but the raw timestamp is copied.
+    pub fn new(raw: &[u8]) -> Result<Self, TimestampError> {
+        Ok(RawTimeProvider::new(raw)?.to_owned())
+    }
+
+    pub fn raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Borrowed view on the timestamp, which provides the full [RawTimeProvider] API.
+    pub fn as_borrowed(&self) -> RawTimeProvider<'_> {
+        RawTimeProvider {
+            raw: &self.raw,
+            time_code: self.time_code,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<RawTimeProvider<'_>> for RawTimeProviderOwned {
+    fn from(provider: RawTimeProvider<'_>) -> Self {
+        provider.to_owned()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TimeWriter for RawTimeProviderOwned {
+    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
+        self.as_borrowed().write_to_bytes(bytes)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl CcsdsTimeProvider for RawTimeProviderOwned {
+    fn len_as_bytes(&self) -> usize {
+        self.raw.len()
+    }
+
+    fn p_field(&self) -> (usize, [u8; 2]) {
+        self.as_borrowed().p_field()
+    }
+
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
+        self.time_code
+    }
+
+    fn unix_seconds(&self) -> i64 {
+        0
+    }
+
+    fn subsecond_millis(&self) -> Option<u16> {
+        None
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+}
+
+impl TimeWriter for RawTimeProvider<'_> {
+    fn write_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, TimestampError> {
+        if bytes.len() < self.raw.len() {
+            return Err(TimestampError::ByteConversionError(
+                ByteConversionError::ToSliceTooSmall(SizeMissmatch {
+                    found: bytes.len(),
+                    expected: self.raw.len(),
+                }),
+            ));
+        }
+        bytes[0..self.raw.len()].copy_from_slice(self.raw);
+        Ok(self.raw.len())
+    }
+}
+
+impl CcsdsTimeProvider for RawTimeProvider<'_> {
+    fn len_as_bytes(&self) -> usize {
+        self.raw.len()
+    }
+
+    fn p_field(&self) -> (usize, [u8; 2]) {
+        if (self.raw[0] >> 7) & 0b1 == 1 && self.raw.len() > 1 {
+            return (2, [self.raw[0], self.raw[1]]);
+        }
+        (1, [self.raw[0], 0])
+    }
+
+    fn ccsds_time_code(&self) -> CcsdsTimeCodes {
+        self.time_code
+    }
+
+    fn unix_seconds(&self) -> i64 {
+        0
+    }
+
+    fn subsecond_millis(&self) -> Option<u16> {
+        None
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::cds::TimeProvider;
+    use crate::time::cuc::TimeProviderCcsdsEpoch;
+    #[cfg(feature = "alloc")]
+    use crate::time::TimeReader;
+
+    #[test]
+    fn test_cds_pass_through() {
+        let cds_stamp = TimeProvider::new_with_u16_days(23754, 60570123);
+        let mut buf: [u8; 16] = [0; 16];
+        let stamp_len = cds_stamp.write_to_bytes(&mut buf).unwrap();
+        let raw_provider = RawTimeProvider::new(&buf[0..stamp_len]).unwrap();
+        assert_eq!(raw_provider.ccsds_time_code(), CcsdsTimeCodes::Cds);
+        assert_eq!(raw_provider.len_as_bytes(), 7);
+        assert_eq!(raw_provider.p_field(), cds_stamp.p_field());
+        assert_eq!(raw_provider.raw(), &buf[0..stamp_len]);
+        let mut forwarded: [u8; 16] = [0; 16];
+        let written_len = raw_provider.write_to_bytes(&mut forwarded).unwrap();
+        assert_eq!(written_len, stamp_len);
+        assert_eq!(forwarded, buf);
+    }
+
+    #[test]
+    fn test_cuc_pass_through() {
+        let cuc_stamp = TimeProviderCcsdsEpoch::new_with_fine_fractions(0x12345678, 1000).unwrap();
+        let mut buf: [u8; 16] = [0; 16];
+        let stamp_len = cuc_stamp.write_to_bytes(&mut buf).unwrap();
+        let raw_provider = RawTimeProvider::new(&buf[0..stamp_len]).unwrap();
+        assert_eq!(
+            raw_provider.ccsds_time_code(),
+            CcsdsTimeCodes::CucCcsdsEpoch
+        );
+        assert_eq!(raw_provider.len_as_bytes(), cuc_stamp.len_as_bytes());
+        assert_eq!(raw_provider.unix_seconds(), 0);
+        assert!(raw_provider.subsecond_millis().is_none());
+        #[cfg(feature = "chrono")]
+        assert!(raw_provider.date_time().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_owned_round_trip() {
+        let cds_stamp = TimeProvider::new_with_u16_days(23754, 60570123);
+        let mut buf: [u8; 16] = [0; 16];
+        let stamp_len = cds_stamp.write_to_bytes(&mut buf).unwrap();
+        let owned = RawTimeProvider::new(&buf[0..stamp_len]).unwrap().to_owned();
+        // The owned provider does not borrow from the buffer anymore.
+        buf.fill(0);
+        assert_eq!(owned.ccsds_time_code(), CcsdsTimeCodes::Cds);
+        assert_eq!(owned.len_as_bytes(), 7);
+        assert_eq!(owned.p_field(), cds_stamp.p_field());
+        let mut forwarded: [u8; 16] = [0; 16];
+        assert_eq!(owned.write_to_bytes(&mut forwarded).unwrap(), stamp_len);
+        assert_eq!(
+            TimeProvider::from_bytes(&forwarded[0..stamp_len]).unwrap(),
+            cds_stamp
+        );
+        let borrowed = owned.as_borrowed();
+        assert_eq!(borrowed.raw(), owned.raw());
+        assert_eq!(borrowed.ccsds_time_code(), owned.ccsds_time_code());
+        assert_eq!(RawTimeProviderOwned::from(borrowed), owned);
+        assert_eq!(RawTimeProviderOwned::new(owned.raw()).unwrap(), owned);
+        assert!(RawTimeProviderOwned::new(&[]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let res = RawTimeProvider::new(&[]);
+        assert!(matches!(
+            res.unwrap_err(),
+            TimestampError::ByteConversionError(ByteConversionError::FromSliceTooSmall(_))
+        ));
+        let res = RawTimeProvider::new(&[0b0111_0000, 1, 2]);
+        assert_eq!(res.unwrap_err(), TimestampError::UnknownTimeCode(0b111));
+    }
+
+    #[test]
+    fn test_target_buf_too_small() {
+        let raw = [0b0100_0000, 0, 1, 0, 0, 0, 1];
+        let raw_provider = RawTimeProvider::new(&raw).unwrap();
+        let mut buf: [u8; 4] = [0; 4];
+        let res = raw_provider.write_to_bytes(&mut buf);
+        assert!(matches!(
+            res.unwrap_err(),
+            TimestampError::ByteConversionError(ByteConversionError::ToSliceTooSmall(_))
+        ));
+    }
+}
@@ -2,11 +2,12 @@
 //! to [ECSS-E-ST-70-41C](https://ecss.nl/standard/ecss-e-st-70-41c-space-engineering-telemetry-and-telecommand-packet-utilization-15-april-2016/).
 use crate::ecss::{
     ccsds_impl, crc_from_raw_data, crc_procedure, sp_header_impls, user_data_from_raw,
-    verify_crc16_from_raw, CrcType, PusError, PusPacket, PusVersion, CRC_CCITT_FALSE,
+    verify_crc16_from_raw, CrcType, PusCrcKind, PusError, PusPacket, PusVersion, CRC_CCITT_FALSE,
 };
+use crate::time::{TimeReader, TimeWriter, TimestampError};
 use crate::{
-    ByteConversionError, CcsdsPacket, PacketType, SequenceFlags, SizeMissmatch, SpHeader,
-    CCSDS_HEADER_LEN,
+    ByteConversionError, CcsdsPacket, PacketId, PacketSequenceCtrl, PacketType, SequenceFlags,
+    SizeMissmatch, SpHeader, CCSDS_HEADER_LEN,
 };
 use core::mem::size_of;
 #[cfg(feature = "serde")]
@@ -21,6 +22,8 @@ use delegate::delegate;
 pub const PUC_TM_MIN_SEC_HEADER_LEN: usize = 7;
 pub const PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA: usize =
     CCSDS_HEADER_LEN + PUC_TM_MIN_SEC_HEADER_LEN + size_of::<CrcType>();
+/// Maximum packet length which can be expressed with the CCSDS data length field.
+pub const PUS_TM_MAX_LEN: usize = u16::MAX as usize + CCSDS_HEADER_LEN + 1;
 
 pub trait GenericPusTmSecondaryHeader {
     fn pus_version(&self) -> PusVersion;
@@ -58,7 +61,7 @@ pub mod zc {
                 return Err(PusError::VersionNotSupported(header.pus_version));
             }
             Ok(PusTmSecHeaderWithoutTimestamp {
-                pus_version_and_sc_time_ref_status: ((header.pus_version as u8) << 4)
+                pus_version_and_sc_time_ref_status: (header.pus_version.raw() << 4)
                     | header.sc_time_ref_status,
                 service: header.service,
                 subservice: header.subservice,
@@ -80,8 +83,7 @@ pub mod zc {
 
     impl GenericPusTmSecondaryHeader for PusTmSecHeaderWithoutTimestamp {
         fn pus_version(&self) -> PusVersion {
-            PusVersion::try_from(self.pus_version_and_sc_time_ref_status >> 4 & 0b1111)
-                .unwrap_or(PusVersion::Invalid)
+            PusVersion::from_raw(self.pus_version_and_sc_time_ref_status >> 4 & 0b1111)
         }
 
         fn sc_time_ref_status(&self) -> u8 {
@@ -106,6 +108,14 @@ pub mod zc {
     }
 }
 
+/// High-level PUS TM secondary header.
+///
+/// The timestamp is stored as a borrowed raw slice instead of a time provider. Parsed packets
+/// only have the raw timestamp bytes, because the time code is mission specific, and a slice
+/// keeps the header [Copy], comparable and serializable with [serde]. Time providers are
+/// integrated by serializing them into a buffer first, see [Self::new_with_time_provider] and
+/// [PusTm::set_timestamp_with_time_provider]. The length of the written timestamp then
+/// determines the length of the header and the CCSDS data length field.
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PusTmSecondaryHeader<'stamp> {
@@ -145,6 +155,30 @@ impl<'stamp> PusTmSecondaryHeader<'stamp> {
             timestamp,
         }
     }
+
+    /// Like [Self::new], but the timestamp is created by serializing the passed time provider
+    /// into `stamp_buf`. The written part of the buffer is used as the timestamp, so the
+    /// length of the secondary header depends on the length of the time provider. The header
+    /// borrows the serialized timestamp instead of the time provider itself, so changes of the
+    /// time provider after this call are not reflected in the header.
+    pub fn new_with_time_provider(
+        service: u8,
+        subservice: u8,
+        msg_counter: u16,
+        dest_id: u16,
+        time_provider: &(impl TimeWriter + ?Sized),
+        stamp_buf: &'stamp mut [u8],
+    ) -> Result<Self, TimestampError> {
+        let stamp_len = time_provider.write_to_bytes(stamp_buf)?;
+        let stamp_buf: &'stamp [u8] = stamp_buf;
+        Ok(Self::new(
+            service,
+            subservice,
+            msg_counter,
+            dest_id,
+            Some(&stamp_buf[0..stamp_len]),
+        ))
+    }
 }
 
 impl GenericPusTmSecondaryHeader for PusTmSecondaryHeader<'_> {
@@ -196,7 +230,10 @@ impl<'slice> TryFrom<zc::PusTmSecHeader<'slice>> for PusTmSecondaryHeader<'slice
 /// feature is used which allows to send around TM packets in a raw byte format using a serde
 /// provider like [postcard](https://docs.rs/postcard/latest/postcard/).
 ///
-/// There is no spare bytes support yet.
+/// Optional spare bytes, which are serialized between the source data and the CRC16, can be set
+/// with [PusTm::set_spare_bytes]. The CRC16 is located with the CCSDS data length field, but the
+/// number of spare bytes in front of it can not be derived from the packet itself, so
+/// [PusTm::from_bytes_with_spare_bytes] needs to be used to parse packets containing them.
 ///
 /// # Lifetimes
 ///
@@ -214,6 +251,7 @@ pub struct PusTm<'raw_data> {
     #[cfg_attr(feature = "serde", serde(skip))]
     raw_data: Option<&'raw_data [u8]>,
     source_data: Option<&'raw_data [u8]>,
+    spare_bytes: Option<&'raw_data [u8]>,
     crc16: Option<u16>,
 }
 
@@ -242,6 +280,7 @@ impl<'raw_data> PusTm<'raw_data> {
             sp_header: *sp_header,
             raw_data: None,
             source_data,
+            spare_bytes: None,
             sec_header,
             calc_crc_on_serialization: true,
             crc16: None,
@@ -260,13 +299,56 @@ impl<'raw_data> PusTm<'raw_data> {
         if let Some(src_data) = self.source_data {
             length += src_data.len();
         }
-        length
+        length + self.spare_bytes_len()
+    }
+
+    /// Set the spare bytes which are serialized between the source data and the CRC16.
+    /// [PusTm::update_ccsds_data_len] needs to be called afterwards if the CCSDS data length
+    /// field is not updated otherwise.
+    pub fn set_spare_bytes(&mut self, spare_bytes: Option<&'raw_data [u8]>) {
+        self.spare_bytes = spare_bytes;
+    }
+
+    pub fn spare_bytes(&self) -> Option<&'raw_data [u8]> {
+        self.spare_bytes
+    }
+
+    fn spare_bytes_len(&self) -> usize {
+        self.spare_bytes.map_or(0, |spare_bytes| spare_bytes.len())
     }
 
     pub fn timestamp(&self) -> Option<&'raw_data [u8]> {
         self.sec_header.timestamp
     }
 
+    /// Parse the raw timestamp with the given time provider. Returns [None] if the packet does
+    /// not have a timestamp.
+    pub fn timestamp_as<T: TimeReader>(&self) -> Option<Result<T, TimestampError>> {
+        self.sec_header.timestamp.map(T::from_bytes)
+    }
+
+    /// Set the raw timestamp of the secondary header. The cached CRC16 is invalidated and
+    /// [PusTm::update_ccsds_data_len] needs to be called afterwards if the length of the
+    /// timestamp changed.
+    pub fn set_timestamp(&mut self, timestamp: Option<&'raw_data [u8]>) {
+        self.sec_header.timestamp = timestamp;
+        self.crc16 = None;
+    }
+
+    /// Like [Self::set_timestamp], but the timestamp is created by serializing the passed time
+    /// provider into `stamp_buf`. The CCSDS data length field is updated as well.
+    pub fn set_timestamp_with_time_provider(
+        &mut self,
+        time_provider: &(impl TimeWriter + ?Sized),
+        stamp_buf: &'raw_data mut [u8],
+    ) -> Result<(), TimestampError> {
+        let stamp_len = time_provider.write_to_bytes(stamp_buf)?;
+        let stamp_buf: &'raw_data [u8] = stamp_buf;
+        self.set_timestamp(Some(&stamp_buf[0..stamp_len]));
+        self.update_ccsds_data_len();
+        Ok(())
+    }
+
     pub fn source_data(&self) -> Option<&'raw_data [u8]> {
         self.source_data
     }
@@ -291,8 +373,7 @@ impl<'raw_data> PusTm<'raw_data> {
     /// this function needs to be called to ensure that the data length field of the CCSDS header
     /// is set correctly
     pub fn update_ccsds_data_len(&mut self) {
-        self.sp_header.data_len =
-            self.len_packed() as u16 - size_of::<crate::zc::SpHeader>() as u16 - 1;
+        self.sp_header.data_len = (self.len_packed() - CCSDS_HEADER_LEN - 1) as u16;
     }
 
     /// This function should be called before the TM packet is serialized if
@@ -309,6 +390,9 @@ impl<'raw_data> PusTm<'raw_data> {
         if let Some(src_data) = self.source_data {
             digest.update(src_data);
         }
+        if let Some(spare_bytes) = self.spare_bytes {
+            digest.update(spare_bytes);
+        }
         self.crc16 = Some(digest.finalize())
     }
 
@@ -333,7 +417,7 @@ impl<'raw_data> PusTm<'raw_data> {
             .write_to_be_bytes(&mut slice[0..CCSDS_HEADER_LEN])?;
         curr_idx += CCSDS_HEADER_LEN;
         let sec_header_len = size_of::<zc::PusTmSecHeaderWithoutTimestamp>();
-        let sec_header = zc::PusTmSecHeaderWithoutTimestamp::try_from(self.sec_header).unwrap();
+        let sec_header = zc::PusTmSecHeaderWithoutTimestamp::try_from(self.sec_header)?;
         sec_header
             .write_to_bytes(&mut slice[curr_idx..curr_idx + sec_header_len])
             .ok_or(ByteConversionError::ZeroCopyToError)?;
@@ -347,7 +431,12 @@ impl<'raw_data> PusTm<'raw_data> {
             slice[curr_idx..curr_idx + src_data.len()].copy_from_slice(src_data);
             curr_idx += src_data.len();
         }
+        if let Some(spare_bytes) = self.spare_bytes {
+            slice[curr_idx..curr_idx + spare_bytes.len()].copy_from_slice(spare_bytes);
+            curr_idx += spare_bytes.len();
+        }
         let crc16 = crc_procedure(
+            PusCrcKind::CcittFalse,
             self.calc_crc_on_serialization,
             &self.crc16,
             0,
@@ -364,19 +453,12 @@ impl<'raw_data> PusTm<'raw_data> {
     #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     pub fn append_to_vec(&self, vec: &mut Vec<u8>) -> Result<usize, PusError> {
         let sph_zc = crate::zc::SpHeader::from(self.sp_header);
-        let mut appended_len = PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA;
-        if let Some(timestamp) = self.sec_header.timestamp {
-            appended_len += timestamp.len();
-        }
-        if let Some(src_data) = self.source_data {
-            appended_len += src_data.len();
-        };
+        let appended_len = self.len_packed();
         let start_idx = vec.len();
         let mut ser_len = 0;
         vec.extend_from_slice(sph_zc.as_bytes());
         ser_len += sph_zc.as_bytes().len();
-        // The PUS version is hardcoded to PUS C
-        let sec_header = zc::PusTmSecHeaderWithoutTimestamp::try_from(self.sec_header).unwrap();
+        let sec_header = zc::PusTmSecHeaderWithoutTimestamp::try_from(self.sec_header)?;
         vec.extend_from_slice(sec_header.as_bytes());
         ser_len += sec_header.as_bytes().len();
         if let Some(timestamp) = self.sec_header.timestamp {
@@ -387,10 +469,15 @@ impl<'raw_data> PusTm<'raw_data> {
             vec.extend_from_slice(src_data);
             ser_len += src_data.len();
         }
+        if let Some(spare_bytes) = self.spare_bytes {
+            vec.extend_from_slice(spare_bytes);
+            ser_len += spare_bytes.len();
+        }
         let crc16 = crc_procedure(
+            PusCrcKind::CcittFalse,
             self.calc_crc_on_serialization,
             &self.crc16,
-            start_idx,
+            0,
             ser_len,
             &vec[start_idx..start_idx + ser_len],
         )?;
@@ -400,10 +487,24 @@ impl<'raw_data> PusTm<'raw_data> {
 
     /// Create a [PusTm] instance from a raw slice. On success, it returns a tuple containing
     /// the instance and the found byte length of the packet. The timestamp length needs to be
-    /// known beforehand.
+    /// known beforehand, for example from [crate::time::CcsdsTimeProvider::len_as_bytes] of the
+    /// time provider used by the mission. [PusTm::timestamp_as] can be used to parse the
+    /// timestamp afterwards.
     pub fn from_bytes(
         slice: &'raw_data [u8],
         timestamp_len: usize,
+    ) -> Result<(Self, usize), PusError> {
+        Self::from_bytes_with_spare_bytes(slice, timestamp_len, 0)
+    }
+
+    /// Like [Self::from_bytes], but the last `num_spare_bytes` bytes before the CRC16, which is
+    /// located with the CCSDS data length field, are extracted as spare bytes instead of being
+    /// part of the source data. Returns [PusError::RawDataTooShort] if the packet is too short
+    /// to contain the spare bytes.
+    pub fn from_bytes_with_spare_bytes(
+        slice: &'raw_data [u8],
+        timestamp_len: usize,
+        num_spare_bytes: usize,
     ) -> Result<(Self, usize), PusError> {
         let raw_data_len = slice.len();
         if raw_data_len < PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA {
@@ -413,7 +514,9 @@ impl<'raw_data> PusTm<'raw_data> {
         let (sp_header, _) = SpHeader::from_be_bytes(&slice[0..CCSDS_HEADER_LEN])?;
         current_idx += 6;
         let total_len = sp_header.total_len();
-        if raw_data_len < total_len || total_len < PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA {
+        if raw_data_len < total_len
+            || total_len < PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA + timestamp_len + num_spare_bytes
+        {
             return Err(PusError::RawDataTooShort(raw_data_len));
         }
         let sec_header_zc = zc::PusTmSecHeaderWithoutTimestamp::from_bytes(
@@ -435,7 +538,16 @@ impl<'raw_data> PusTm<'raw_data> {
             sp_header,
             sec_header: PusTmSecondaryHeader::try_from(zc_sec_header_wrapper).unwrap(),
             raw_data: Some(&slice[0..total_len]),
-            source_data: user_data_from_raw(current_idx, total_len, raw_data_len, slice)?,
+            source_data: user_data_from_raw(
+                current_idx,
+                total_len - num_spare_bytes,
+                raw_data_len,
+                slice,
+            )?,
+            spare_bytes: match num_spare_bytes {
+                0 => None,
+                _ => Some(&raw_data[total_len - 2 - num_spare_bytes..total_len - 2]),
+            },
             calc_crc_on_serialization: false,
             crc16: Some(crc_from_raw_data(raw_data)?),
         };
@@ -450,11 +562,138 @@ impl<'raw_data> PusTm<'raw_data> {
     }
 }
 
+/// Builder for unsegmented [PusTm] packets which allows to set the fields of the space packet
+/// header, the secondary header and the source data in a chained fashion. All numeric fields
+/// default to 0, and the packet has neither a timestamp nor source data by default.
+///
+/// # Example
+///
+/// ```
+/// use spacepackets::CcsdsPacket;
+/// use spacepackets::ecss::PusPacket;
+/// use spacepackets::tm::PusTmBuilder;
+///
+/// let timestamp = [0; 7];
+/// let source_data = [1, 2, 3];
+/// let pus_tm = PusTmBuilder::new()
+///     .apid(0x02)
+///     .seq_count(5)
+///     .service(3)
+///     .subservice(25)
+///     .msg_counter(1)
+///     .dest_id(7)
+///     .timestamp(&timestamp)
+///     .source_data(&source_data)
+///     .build()
+///     .unwrap();
+/// assert_eq!(pus_tm.apid(), 0x02);
+/// assert_eq!(pus_tm.service(), 3);
+/// assert_eq!(pus_tm.timestamp(), Some(timestamp.as_slice()));
+/// assert_eq!(pus_tm.user_data(), Some(source_data.as_slice()));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PusTmBuilder<'raw_data> {
+    apid: u16,
+    seq_count: u16,
+    sec_header: PusTmSecondaryHeader<'raw_data>,
+    source_data: Option<&'raw_data [u8]>,
+}
+
+impl Default for PusTmBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'raw_data> PusTmBuilder<'raw_data> {
+    pub fn new() -> Self {
+        Self {
+            apid: 0,
+            seq_count: 0,
+            sec_header: PusTmSecondaryHeader::new_simple_no_timestamp(0, 0),
+            source_data: None,
+        }
+    }
+
+    /// The APID is checked against [crate::MAX_APID] in [Self::build].
+    pub fn apid(mut self, apid: u16) -> Self {
+        self.apid = apid;
+        self
+    }
+
+    /// The sequence count is checked against [crate::MAX_SEQ_COUNT] in [Self::build].
+    pub fn seq_count(mut self, seq_count: u16) -> Self {
+        self.seq_count = seq_count;
+        self
+    }
+
+    pub fn service(mut self, service: u8) -> Self {
+        self.sec_header.service = service;
+        self
+    }
+
+    pub fn subservice(mut self, subservice: u8) -> Self {
+        self.sec_header.subservice = subservice;
+        self
+    }
+
+    pub fn msg_counter(mut self, msg_counter: u16) -> Self {
+        self.sec_header.msg_counter = msg_counter;
+        self
+    }
+
+    pub fn dest_id(mut self, dest_id: u16) -> Self {
+        self.sec_header.dest_id = dest_id;
+        self
+    }
+
+    /// Only the four lowest bits are used.
+    pub fn sc_time_ref_status(mut self, sc_time_ref_status: u8) -> Self {
+        self.sec_header.sc_time_ref_status = sc_time_ref_status & 0b1111;
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: &'raw_data [u8]) -> Self {
+        self.sec_header.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn source_data(mut self, source_data: &'raw_data [u8]) -> Self {
+        self.source_data = Some(source_data);
+        self
+    }
+
+    /// Create the telemetry packet with a correct CCSDS data length field. Returns
+    /// [PusError::SpHeaderError] if the APID or the sequence count exceed the bit width of their
+    /// space packet header fields, and [PusError::PacketTooLarge] if the timestamp and the
+    /// source data are too long for the packet length to fit into the CCSDS data length field.
+    pub fn build(self) -> Result<PusTm<'raw_data>, PusError> {
+        let mut sp_header = SpHeader::const_new_from_single_fields(
+            PacketType::Tm,
+            true,
+            self.apid,
+            SequenceFlags::Unsegmented,
+            self.seq_count,
+            0,
+        )?;
+        let mut pus_tm = PusTm::new(&mut sp_header, self.sec_header, self.source_data, false);
+        if pus_tm.len_packed() > PUS_TM_MAX_LEN {
+            return Err(PusError::PacketTooLarge {
+                advertised: pus_tm.len_packed(),
+                max: PUS_TM_MAX_LEN,
+            });
+        }
+        pus_tm.update_ccsds_data_len();
+        Ok(pus_tm)
+    }
+}
+
 impl PartialEq for PusTm<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.sp_header == other.sp_header
             && self.sec_header == other.sec_header
             && self.source_data == other.source_data
+            && self.spare_bytes == other.spare_bytes
     }
 }
 
@@ -492,11 +731,154 @@ impl GenericPusTmSecondaryHeader for PusTm<'_> {
     });
 }
 
+/// Zero-copy reader for a raw PUS telemetry packet. Only the packet length is validated on
+/// creation, all fields are read lazily from the underlying buffer when they are requested.
+///
+/// Like for [PusTm::from_bytes], the timestamp length needs to be known beforehand. The CRC16 is
+/// not verified automatically, [PusTmReader::verify_crc] can be used to do this explicitly.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PusTmReader<'raw_data> {
+    raw_data: &'raw_data [u8],
+    timestamp_len: usize,
+}
+
+impl<'raw_data> PusTmReader<'raw_data> {
+    /// Create a new reader. The length of the packet is determined by the CCSDS data length
+    /// field and the raw data of the reader is truncated to it.
+    pub fn new(slice: &'raw_data [u8], timestamp_len: usize) -> Result<Self, PusError> {
+        if slice.len() < PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA + timestamp_len {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        let total_len = u16::from_be_bytes([slice[4], slice[5]]) as usize + CCSDS_HEADER_LEN + 1;
+        if slice.len() < total_len || total_len < PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA + timestamp_len
+        {
+            return Err(PusError::RawDataTooShort(slice.len()));
+        }
+        Ok(Self {
+            raw_data: &slice[..total_len],
+            timestamp_len,
+        })
+    }
+
+    /// Raw bytes of the whole packet, including the CRC16.
+    pub fn raw_bytes(&self) -> &'raw_data [u8] {
+        self.raw_data
+    }
+
+    pub fn timestamp(&self) -> Option<&'raw_data [u8]> {
+        if self.timestamp_len == 0 {
+            return None;
+        }
+        let stamp_start = CCSDS_HEADER_LEN + PUC_TM_MIN_SEC_HEADER_LEN;
+        Some(&self.raw_data[stamp_start..stamp_start + self.timestamp_len])
+    }
+
+    pub fn source_data(&self) -> Option<&'raw_data [u8]> {
+        let source_data_start = CCSDS_HEADER_LEN + PUC_TM_MIN_SEC_HEADER_LEN + self.timestamp_len;
+        let crc_pos = self.raw_data.len() - size_of::<CrcType>();
+        if crc_pos == source_data_start {
+            return None;
+        }
+        Some(&self.raw_data[source_data_start..crc_pos])
+    }
+
+    /// CRC16 stored in the last two bytes of the packet.
+    pub fn crc16(&self) -> u16 {
+        let len = self.raw_data.len();
+        u16::from_be_bytes([self.raw_data[len - 2], self.raw_data[len - 1]])
+    }
+
+    /// Verify the CRC16 of the packet. Returns [PusError::IncorrectCrc] with the stored CRC16
+    /// if the check fails.
+    pub fn verify_crc(&self) -> Result<(), PusError> {
+        verify_crc16_from_raw(self.raw_data, self.crc16())
+    }
+
+    /// Convert the reader into a full [PusTm] instance, including the verification of the CRC16.
+    pub fn into_pus_tm(self) -> Result<PusTm<'raw_data>, PusError> {
+        PusTm::from_bytes(self.raw_data, self.timestamp_len).map(|(pus_tm, _)| pus_tm)
+    }
+}
+
+impl CcsdsPacket for PusTmReader<'_> {
+    fn ccsds_version(&self) -> u8 {
+        (self.raw_data[0] >> 5) & 0b111
+    }
+
+    fn packet_id(&self) -> PacketId {
+        PacketId::from(u16::from_be_bytes([self.raw_data[0], self.raw_data[1]]))
+    }
+
+    fn psc(&self) -> PacketSequenceCtrl {
+        PacketSequenceCtrl::from(u16::from_be_bytes([self.raw_data[2], self.raw_data[3]]))
+    }
+
+    fn data_len(&self) -> u16 {
+        u16::from_be_bytes([self.raw_data[4], self.raw_data[5]])
+    }
+}
+
+impl PusPacket for PusTmReader<'_> {
+    fn pus_version(&self) -> PusVersion {
+        PusVersion::from_raw(self.raw_data[CCSDS_HEADER_LEN] >> 4)
+    }
+
+    fn service(&self) -> u8 {
+        self.raw_data[CCSDS_HEADER_LEN + 1]
+    }
+
+    fn subservice(&self) -> u8 {
+        self.raw_data[CCSDS_HEADER_LEN + 2]
+    }
+
+    fn user_data(&self) -> Option<&[u8]> {
+        self.source_data()
+    }
+
+    fn crc16(&self) -> Option<u16> {
+        Some(PusTmReader::crc16(self))
+    }
+}
+
+//noinspection RsTraitImplementation
+impl GenericPusTmSecondaryHeader for PusTmReader<'_> {
+    fn pus_version(&self) -> PusVersion {
+        PusPacket::pus_version(self)
+    }
+
+    fn service(&self) -> u8 {
+        PusPacket::service(self)
+    }
+
+    fn subservice(&self) -> u8 {
+        PusPacket::subservice(self)
+    }
+
+    fn sc_time_ref_status(&self) -> u8 {
+        self.raw_data[CCSDS_HEADER_LEN] & 0b1111
+    }
+
+    fn msg_counter(&self) -> u16 {
+        u16::from_be_bytes([
+            self.raw_data[CCSDS_HEADER_LEN + 3],
+            self.raw_data[CCSDS_HEADER_LEN + 4],
+        ])
+    }
+
+    fn dest_id(&self) -> u16 {
+        u16::from_be_bytes([
+            self.raw_data[CCSDS_HEADER_LEN + 5],
+            self.raw_data[CCSDS_HEADER_LEN + 6],
+        ])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ecss::PusVersion::PusC;
-    use crate::SpHeader;
+    use crate::time::{cds, cuc, CcsdsTimeProvider};
+    use crate::{SpHeader, SpHeaderError};
 
     fn base_ping_reply_full_ctor(timestamp: &[u8]) -> PusTm {
         let mut sph = SpHeader::tm_unseg(0x123, 0x234, 0).unwrap();
@@ -547,6 +929,154 @@ mod tests {
         assert_eq!(buf[22], 3);
     }
 
+    #[test]
+    fn test_serialization_with_time_providers() {
+        let mut sph = SpHeader::tm_unseg(0x123, 0x234, 0).unwrap();
+        let mut cds_stamp_buf: [u8; 16] = [0; 16];
+        let cds_stamp = cds::TimeProvider::new_with_u16_days(0, 0);
+        let sec_header = PusTmSecondaryHeader::new_with_time_provider(
+            17,
+            2,
+            0,
+            0,
+            &cds_stamp,
+            &mut cds_stamp_buf,
+        )
+        .unwrap();
+        assert_eq!(sec_header.timestamp.unwrap().len(), 7);
+        let tm_cds = PusTm::new(&mut sph, sec_header, None, true);
+        let mut cuc_stamp_buf: [u8; 16] = [0; 16];
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::new_with_fine_fractions(0, 0).unwrap();
+        let sec_header = PusTmSecondaryHeader::new_with_time_provider(
+            17,
+            2,
+            0,
+            0,
+            &cuc_stamp,
+            &mut cuc_stamp_buf,
+        )
+        .unwrap();
+        assert_eq!(sec_header.timestamp.unwrap().len(), 8);
+        let tm_cuc = PusTm::new(&mut sph, sec_header, None, true);
+        assert_eq!(tm_cds.len_packed(), 22);
+        assert_eq!(tm_cuc.len_packed(), 23);
+        assert_eq!(tm_cuc.data_len(), tm_cds.data_len() + 1);
+        let mut buf: [u8; 32] = [0; 32];
+        let ser_len = tm_cuc.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(ser_len, 23);
+        assert_eq!(u16::from_be_bytes(buf[4..6].try_into().unwrap()), 16);
+        assert_eq!(&buf[13..21], tm_cuc.timestamp().unwrap());
+    }
+
+    #[test]
+    fn test_timestamp_with_time_provider() {
+        let mut tm = base_ping_reply_full_ctor(dummy_timestamp());
+        tm.calc_own_crc16();
+        assert_eq!(tm.len_packed(), 22);
+        let cuc_stamp = cuc::TimeProviderCcsdsEpoch::new_with_fine_fractions(20, 0).unwrap();
+        let mut cuc_stamp_buf: [u8; 16] = [0; 16];
+        tm.set_timestamp_with_time_provider(&cuc_stamp, &mut cuc_stamp_buf)
+            .unwrap();
+        assert!(tm.crc16().is_none());
+        assert_eq!(tm.timestamp().unwrap().len(), 8);
+        assert_eq!(tm.len_packed(), 23);
+        assert_eq!(tm.sp_header.total_len(), 23);
+        let mut buf: [u8; 32] = [0; 32];
+        let ser_len = tm.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(ser_len, 23);
+
+        let (tm_read, read_len) = PusTm::from_bytes(&buf, cuc_stamp.len_as_bytes()).unwrap();
+        assert_eq!(read_len, 23);
+        assert_eq!(tm_read, tm);
+        let read_stamp = tm_read
+            .timestamp_as::<cuc::TimeProviderCcsdsEpoch>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(read_stamp, cuc_stamp);
+        assert!(tm_read
+            .timestamp_as::<cds::TimeProvider>()
+            .unwrap()
+            .is_err());
+        // The timestamp can not be longer than the packet.
+        assert_eq!(
+            PusTm::from_bytes(&buf, 16).unwrap_err(),
+            PusError::RawDataTooShort(32)
+        );
+
+        tm.set_timestamp(None);
+        tm.update_ccsds_data_len();
+        assert!(tm.timestamp_as::<cds::TimeProvider>().is_none());
+        assert_eq!(tm.len_packed(), PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA);
+    }
+
+    #[test]
+    fn test_builder() {
+        let timestamp = dummy_timestamp();
+        let pus_tm = PusTmBuilder::new()
+            .apid(0x123)
+            .seq_count(0x234)
+            .service(3)
+            .subservice(25)
+            .msg_counter(0x1f1f)
+            .dest_id(0x4242)
+            .sc_time_ref_status(0b11111)
+            .timestamp(timestamp)
+            .source_data(&[1, 2, 3])
+            .build()
+            .unwrap();
+        let mut sph = SpHeader::tm_unseg(0x123, 0x234, 0).unwrap();
+        let mut sec_header = PusTmSecondaryHeader::new(3, 25, 0x1f1f, 0x4242, Some(timestamp));
+        sec_header.sc_time_ref_status = 0b1111;
+        let expected = PusTm::new(&mut sph, sec_header, Some(&[1, 2, 3]), true);
+        assert_eq!(pus_tm, expected);
+        assert_eq!(pus_tm.sp_header.total_len(), pus_tm.len_packed());
+        assert_eq!(pus_tm.len_packed(), 25);
+
+        let pus_tm = PusTmBuilder::default()
+            .service(17)
+            .subservice(2)
+            .build()
+            .unwrap();
+        assert!(pus_tm.timestamp().is_none());
+        assert!(pus_tm.user_data().is_none());
+        assert_eq!(pus_tm.len_packed(), PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA);
+        assert_eq!(
+            pus_tm.sp_header.total_len(),
+            PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA
+        );
+    }
+
+    #[test]
+    fn test_builder_invalid_params() {
+        assert_eq!(
+            PusTmBuilder::new().apid(0x800).build().unwrap_err(),
+            PusError::SpHeaderError(SpHeaderError::ApidTooLarge(0x800))
+        );
+        assert_eq!(
+            PusTmBuilder::new().seq_count(0x4000).build().unwrap_err(),
+            PusError::SpHeaderError(SpHeaderError::SeqCountTooLarge(0x4000))
+        );
+        let large_stamp = [0; u16::MAX as usize];
+        assert_eq!(
+            PusTmBuilder::new()
+                .timestamp(&large_stamp)
+                .build()
+                .unwrap_err(),
+            PusError::PacketTooLarge {
+                advertised: u16::MAX as usize + PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA,
+                max: PUS_TM_MAX_LEN
+            }
+        );
+        let max_data = [0; PUS_TM_MAX_LEN - PUS_TM_MIN_LEN_WITHOUT_SOURCE_DATA - 7];
+        let pus_tm = PusTmBuilder::new()
+            .timestamp(dummy_timestamp())
+            .source_data(&max_data)
+            .build()
+            .unwrap();
+        assert_eq!(pus_tm.len_packed(), PUS_TM_MAX_LEN);
+        assert_eq!(pus_tm.sp_header.data_len, u16::MAX);
+    }
+
     #[test]
     fn test_setters() {
         let timestamp = dummy_timestamp();
@@ -575,6 +1105,98 @@ mod tests {
         verify_ping_reply(&tm_deserialized, false, 22, dummy_timestamp());
     }
 
+    #[test]
+    fn test_reader() {
+        let timestamp = dummy_timestamp();
+        let mut pus_tm = base_hk_reply(timestamp, &[1, 2, 3]);
+        pus_tm.set_msg_counter(0x1234);
+        pus_tm.set_dest_id(0x5678);
+        pus_tm.set_sc_time_ref_status(0b1010);
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tm.write_to_bytes(&mut buf).unwrap();
+        let reader = PusTmReader::new(&buf, timestamp.len()).unwrap();
+        assert_eq!(reader.raw_bytes(), &buf[0..size]);
+        assert_eq!(reader.ccsds_version(), 0);
+        assert_eq!(reader.apid(), 0x123);
+        assert_eq!(reader.seq_count(), 0x234);
+        assert_eq!(reader.ptype(), PacketType::Tm);
+        assert!(reader.sec_header_flag());
+        assert_eq!(reader.data_len(), pus_tm.data_len());
+        assert_eq!(PusPacket::pus_version(&reader), PusC);
+        assert_eq!(PusPacket::service(&reader), 3);
+        assert_eq!(PusPacket::subservice(&reader), 5);
+        assert_eq!(reader.msg_counter(), 0x1234);
+        assert_eq!(reader.dest_id(), 0x5678);
+        assert_eq!(reader.sc_time_ref_status(), 0b1010);
+        assert_eq!(reader.timestamp(), Some(timestamp));
+        assert_eq!(reader.source_data(), Some([1, 2, 3].as_slice()));
+        assert_eq!(reader.user_data(), Some([1, 2, 3].as_slice()));
+        assert_eq!(
+            reader.crc16(),
+            u16::from_be_bytes([buf[size - 2], buf[size - 1]])
+        );
+        assert!(reader.verify_crc().is_ok());
+        let (read_tm, _) = PusTm::from_bytes(&buf, timestamp.len()).unwrap();
+        assert_eq!(reader.into_pus_tm().unwrap(), read_tm);
+
+        let size = base_ping_reply_full_ctor(timestamp)
+            .write_to_bytes(&mut buf)
+            .unwrap();
+        let reader = PusTmReader::new(&buf[0..size], timestamp.len()).unwrap();
+        assert_eq!(reader.source_data(), None);
+        let reader = PusTmReader::new(&buf[0..size], 0).unwrap();
+        assert_eq!(reader.timestamp(), None);
+        assert_eq!(reader.source_data(), Some(timestamp));
+    }
+
+    #[test]
+    fn test_reader_invalid() {
+        let timestamp = dummy_timestamp();
+        let pus_tm = base_hk_reply(timestamp, &[1, 2, 3]);
+        let mut buf: [u8; 32] = [0; 32];
+        let size = pus_tm.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(
+            PusTmReader::new(&buf[0..size - 1], 7).unwrap_err(),
+            PusError::RawDataTooShort(size - 1)
+        );
+        assert_eq!(
+            PusTmReader::new(&buf, 16).unwrap_err(),
+            PusError::RawDataTooShort(32)
+        );
+        buf[size - 3] ^= 0xff;
+        let reader = PusTmReader::new(&buf, 7).unwrap();
+        let crc16 = reader.crc16();
+        assert_eq!(
+            reader.verify_crc().unwrap_err(),
+            PusError::IncorrectCrc(crc16)
+        );
+        assert!(reader.into_pus_tm().is_err());
+    }
+
+    #[test]
+    fn test_deserialization_invalid_version() {
+        let timestamp = dummy_timestamp();
+        let pus_tm = base_ping_reply_full_ctor(timestamp);
+        let mut buf: [u8; 32] = [0; 32];
+        let ser_len = pus_tm
+            .write_to_bytes(&mut buf)
+            .expect("Serialization failed");
+        buf[6] = 0b0111 << 4;
+        let crc16 = crate::ecss::calc_pus_crc16(&buf[0..ser_len - 2]);
+        buf[ser_len - 2..ser_len].copy_from_slice(&crc16.to_be_bytes());
+        let (tm_deserialized, _) = PusTm::from_bytes(&buf, 7).expect("Deserialization failed");
+        assert_eq!(
+            PusPacket::pus_version(&tm_deserialized),
+            PusVersion::Invalid(0b0111)
+        );
+        let res = tm_deserialized.write_to_bytes(&mut [0; 32]);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err(),
+            PusError::VersionNotSupported(PusVersion::Invalid(0b0111))
+        );
+    }
+
     #[test]
     fn test_manual_field_update() {
         let mut sph = SpHeader::tm_unseg(0x123, 0x234, 0).unwrap();
@@ -642,6 +1264,51 @@ mod tests {
         assert!(res.is_ok());
         assert_eq!(res.unwrap(), 25);
         assert_eq!(vec.len(), 26);
+        let mut buf: [u8; 32] = [0; 32];
+        let size = hk_reply.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(vec[1..], buf[0..size]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_spare_bytes() {
+        let src_data = [1, 2, 3];
+        let spare_bytes = [0, 0];
+        let mut pus_tm = base_hk_reply(dummy_timestamp(), &src_data);
+        pus_tm.set_spare_bytes(Some(&spare_bytes));
+        pus_tm.update_ccsds_data_len();
+        assert_eq!(pus_tm.spare_bytes(), Some(spare_bytes.as_slice()));
+        assert_eq!(pus_tm.len_packed(), 27);
+        assert_eq!(pus_tm.sp_header.total_len(), 27);
+        let mut buf: [u8; 32] = [0; 32];
+        let written = pus_tm.write_to_bytes(&mut buf).unwrap();
+        assert_eq!(written, pus_tm.len_packed());
+        assert_eq!(buf[20..23], src_data);
+        assert_eq!(buf[23..25], spare_bytes);
+        assert_eq!(crate::ecss::calc_pus_crc16(&buf[0..written]), 0);
+        let mut vec = Vec::new();
+        assert_eq!(pus_tm.append_to_vec(&mut vec).unwrap(), written);
+        assert_eq!(vec, buf[0..written]);
+        pus_tm.calc_crc_on_serialization = false;
+        pus_tm.calc_own_crc16();
+        assert_eq!(
+            pus_tm.crc16(),
+            Some(u16::from_be_bytes([buf[written - 2], buf[written - 1]]))
+        );
+
+        let (read_tm, read_len) = PusTm::from_bytes_with_spare_bytes(&buf, 7, 2).unwrap();
+        assert_eq!(read_len, written);
+        assert_eq!(read_tm.source_data(), Some(src_data.as_slice()));
+        assert_eq!(read_tm.spare_bytes(), Some(spare_bytes.as_slice()));
+        assert_eq!(read_tm, pus_tm);
+        // Without the spare byte count, the spare bytes are part of the source data.
+        let (read_tm, _) = PusTm::from_bytes(&buf, 7).unwrap();
+        assert_eq!(read_tm.source_data(), Some(&buf[20..25]));
+        assert_eq!(read_tm.spare_bytes(), None);
+        assert_eq!(
+            PusTm::from_bytes_with_spare_bytes(&buf, 7, 6).unwrap_err(),
+            PusError::RawDataTooShort(32)
+        );
     }
 
     fn verify_raw_ping_reply(buf: &[u8]) {
@@ -654,7 +1321,7 @@ mod tests {
         assert_eq!(buf[3], 0x34);
         assert_eq!(((buf[4] as u16) << 8) | buf[5] as u16, 15);
         // SC time ref status is 0
-        assert_eq!(buf[6], (PusC as u8) << 4);
+        assert_eq!(buf[6], PusC.raw() << 4);
         assert_eq!(buf[7], 17);
         assert_eq!(buf[8], 2);
         // MSG counter 0